@@ -39,6 +39,48 @@
 //!
 //! * nRF51 devices only support payloads of up to 27 bytes.
 //!
+//! The `S0` byte is the only associated data authenticated by the peripheral (the BLE link layer
+//! masks some of its bits before passing it in), arbitrary associated data is not supported by
+//! the hardware. The MIC of a ciphertext packet can be read with [`Ccm::mic`].
+//!
+//! # CCM Data Structure
+//!
+//! The `CNFPTR` register points to a [`CcmData`] structure, laid out as follows (see the "CCM
+//! data structure" table in the CCM chapter of the product specification):
+//!
+//! ```notrust
+//! +------------+-----------------------------+-------------+
+//! | Offset     | Field                       | Size        |
+//! +------------+-----------------------------+-------------+
+//! | 0          | KEY                         | 16 bytes    |
+//! | 16         | PKTCTR (39-bit counter)     | 5 (8) bytes |
+//! | 24         | Direction bit (bit 0)       | 1 byte      |
+//! | 25         | IV                          | 8 bytes     |
+//! +------------+-----------------------------+-------------+
+//! ```
+//!
+//! The hardware builds the 13-byte CCM nonce from the counter, the direction bit and the IV, as
+//! specified by the Bluetooth Core Specification. A nonce in that format can be given directly with
+//! [`CcmData::with_nonce`] or [`Ccm::encrypt_packet_with_nonce`].
+//!
+//! # On-the-fly Radio Encryption
+//!
+//! [`Ccm::encrypt_radio_packet`] and [`Ccm::decrypt_radio_packet`] arm the peripheral to encrypt
+//! or decrypt a packet while the RADIO transmits or receives it, without copying it. They take
+//! `'static` buffers, bundled in [`RadioBuffers`], that are only given back by
+//! [`RadioCrypt::wait`] once the peripheral is done with them. The RADIO `PACKETPTR` must point to
+//! the `cipher_packet` buffer, and the following event chain must be set up through the
+//! pre-programmed PPI channels:
+//!
+//! * Transmit: `RADIO.EVENTS_READY` → `CCM.TASKS_KSGEN` (PPI channel 24), then the
+//!   `ENDKSGEN_CRYPT` short starts the encryption, which completes before the RADIO reaches the
+//!   payload.
+//! * Receive: `RADIO.EVENTS_READY` → `CCM.TASKS_KSGEN` (PPI channel 24) and
+//!   `RADIO.EVENTS_ADDRESS` → `CCM.TASKS_CRYPT` (PPI channel 25), so the decryption follows the
+//!   payload as the RADIO writes it.
+//!
+//! On the nRF5340 the same events and tasks have to be connected through DPPI instead.
+//!
 //! # Scratch Area
 //!
 //! The peripheral also needs an area in RAM to store temporary values used during
@@ -58,6 +100,7 @@ use crate::{
 };
 
 use core::sync::atomic::{compiler_fence, Ordering};
+use embedded_dma::{ReadBuffer, WriteBuffer};
 
 #[cfg(not(any(feature = "51", feature = "5340-net")))]
 use crate::pac::ccm::mode::{DATARATE_A, LENGTH_A};
@@ -70,6 +113,8 @@ const HEADER_SIZE: usize = 3;
 const LENGTH_HEADER_INDEX: usize = 1;
 const MIC_SIZE: usize = 4;
 const MAXIMUM_LENGTH_5BITS: usize = 31;
+const NONCE_SIZE: usize = 13;
+const COUNTER_SIZE: usize = 5;
 
 // 39-bits counter
 const MAXIMUM_COUNTER: u64 = 0x7F_FFFF_FFFF;
@@ -146,6 +191,41 @@ impl CcmData {
         }
     }
 
+    /// Creates a new `CcmData` instance from a key and a 13-byte CCM nonce.
+    ///
+    /// The nonce is split as in the Bluetooth Core Specification: the first five bytes hold the
+    /// 39-bit little-endian counter with the direction bit as the most significant bit of the fifth
+    /// byte, and the last eight bytes hold the initialization vector.
+    pub fn with_nonce(key: [u8; 16], nonce: [u8; NONCE_SIZE]) -> Self {
+        let mut ccm_data = Self::new(key, [0; 8]);
+        ccm_data.set_nonce(nonce);
+        ccm_data
+    }
+
+    /// Updates the counter, direction bit and initialization vector from a 13-byte CCM nonce.
+    ///
+    /// See [`CcmData::with_nonce`] for the nonce layout.
+    pub fn set_nonce(&mut self, nonce: [u8; NONCE_SIZE]) {
+        let mut packet_counter = [0; 8];
+        packet_counter[..COUNTER_SIZE].copy_from_slice(&nonce[..COUNTER_SIZE]);
+        packet_counter[COUNTER_SIZE - 1] &= 0x7F;
+        self.packet_counter = packet_counter;
+        self.direction = nonce[COUNTER_SIZE - 1] >> 7;
+        self.initialization_vector
+            .copy_from_slice(&nonce[COUNTER_SIZE..]);
+    }
+
+    /// Returns the 13-byte CCM nonce used for the next operation.
+    ///
+    /// See [`CcmData::with_nonce`] for the nonce layout.
+    pub fn nonce(&self) -> [u8; NONCE_SIZE] {
+        let mut nonce = [0; NONCE_SIZE];
+        nonce[..COUNTER_SIZE].copy_from_slice(&self.packet_counter[..COUNTER_SIZE]);
+        nonce[COUNTER_SIZE - 1] |= self.direction << 7;
+        nonce[COUNTER_SIZE..].copy_from_slice(&self.initialization_vector);
+        nonce
+    }
+
     /// Updates the key.
     #[inline(always)]
     pub fn set_key(&mut self, key: [u8; 16]) {
@@ -185,6 +265,70 @@ impl CcmData {
     }
 }
 
+/// The buffers used by an on-the-fly encryption or decryption of a RADIO packet.
+///
+/// They are handed to [`Ccm::encrypt_radio_packet`] or [`Ccm::decrypt_radio_packet`] and given
+/// back by [`RadioCrypt::wait`], or together with the error if the operation can't be started.
+/// All buffers must reside in RAM.
+pub struct RadioBuffers<C, P> {
+    /// Key, counter, direction and IV of the link.
+    pub ccm_data: &'static mut CcmData,
+    /// The unencrypted packet.
+    pub clear_packet: C,
+    /// The packet transmitted or received by the RADIO.
+    pub cipher_packet: P,
+    /// Scratch area for the peripheral, see the module level documentation.
+    pub scratch: &'static mut [u8],
+}
+
+/// An on-the-fly encryption or decryption of a RADIO packet.
+///
+/// Returned by [`Ccm::encrypt_radio_packet`] and [`Ccm::decrypt_radio_packet`], it owns the
+/// buffers while the peripheral uses them. Dropping it stops the operation.
+pub struct RadioCrypt<'a, C, P> {
+    ccm: &'a mut Ccm,
+    // FIXME: Always `Some`, only using `Option` here to allow moving the buffers out in `wait`.
+    buffers: Option<RadioBuffers<C, P>>,
+    decrypt: bool,
+}
+
+impl<C, P> RadioCrypt<'_, C, P> {
+    /// Blocks until the packet has been encrypted or decrypted and returns the buffers.
+    ///
+    /// When decrypting, the MIC is checked. The counter in the `CcmData` is incremented if the
+    /// operation succeeds.
+    pub fn wait(mut self) -> (Result<(), CcmError>, RadioBuffers<C, P>) {
+        let mut buffers = self
+            .buffers
+            .take()
+            .unwrap_or_else(|| unsafe { core::hint::unreachable_unchecked() });
+        let regs = &self.ccm.regs;
+        while regs.events_endcrypt.read().bits() == 0 && regs.events_error.read().bits() == 0 {}
+
+        // "Subsequent reads and writes cannot be moved ahead of preceding reads."
+        compiler_fence(Ordering::Acquire);
+
+        let result = if regs.events_error.read().bits() == 1 {
+            Err(CcmError::EasyDMAError)
+        } else if self.decrypt && regs.micstatus.read().micstatus().is_check_failed() {
+            Err(CcmError::InvalidMIC)
+        } else {
+            buffers.ccm_data.increment_counter();
+            Ok(())
+        };
+        (result, buffers)
+    }
+}
+
+impl<C, P> Drop for RadioCrypt<'_, C, P> {
+    fn drop(&mut self) {
+        let regs = &self.ccm.regs;
+        regs.shorts.reset();
+        // NOTE(unsafe) 1 is a valid pattern to write to this register
+        regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+    }
+}
+
 /// A safe, blocking wrapper around the AES-CCM peripheral.
 pub struct Ccm {
     regs: CCM,
@@ -342,6 +486,22 @@ impl Ccm {
         Ok(())
     }
 
+    /// Encrypts a packet with an explicit key and 13-byte CCM nonce.
+    ///
+    /// Works like [`Ccm::encrypt_packet`], with the nonce layout described in
+    /// [`CcmData::with_nonce`].
+    pub fn encrypt_packet_with_nonce(
+        &mut self,
+        key: [u8; 16],
+        nonce: [u8; NONCE_SIZE],
+        clear_packet: &[u8],
+        cipher_packet: &mut [u8],
+        scratch: &mut [u8],
+    ) -> Result<(), CcmError> {
+        let mut ccm_data = CcmData::with_nonce(key, nonce);
+        self.encrypt_packet(&mut ccm_data, clear_packet, cipher_packet, scratch)
+    }
+
     /// Decrypts a packet and checks its MIC.
     ///
     /// This method will return an error if the MIC verification fails. The slices passed to this
@@ -477,4 +637,236 @@ impl Ccm {
         ccm_data.increment_counter();
         Ok(())
     }
+
+    /// Decrypts a packet with an explicit key and 13-byte CCM nonce, and checks its MIC.
+    ///
+    /// Works like [`Ccm::decrypt_packet`], with the nonce layout described in
+    /// [`CcmData::with_nonce`].
+    pub fn decrypt_packet_with_nonce(
+        &mut self,
+        key: [u8; 16],
+        nonce: [u8; NONCE_SIZE],
+        clear_packet: &mut [u8],
+        cipher_packet: &[u8],
+        scratch: &mut [u8],
+    ) -> Result<(), CcmError> {
+        let mut ccm_data = CcmData::with_nonce(key, nonce);
+        self.decrypt_packet(&mut ccm_data, clear_packet, cipher_packet, scratch)
+    }
+
+    /// Returns the MIC of a ciphertext packet.
+    ///
+    /// The MIC is made of the last four bytes of the payload, as given by the `Packet length`
+    /// field.
+    pub fn mic(cipher_packet: &[u8]) -> Result<[u8; MIC_SIZE], CcmError> {
+        let payload_len = *cipher_packet
+            .get(LENGTH_HEADER_INDEX)
+            .ok_or(CcmError::WrongPacketLength)? as usize;
+        if payload_len < MIC_SIZE || cipher_packet.len() < payload_len + HEADER_SIZE {
+            return Err(CcmError::WrongPacketLength);
+        }
+
+        let end = HEADER_SIZE + payload_len;
+        let mut mic = [0; MIC_SIZE];
+        mic.copy_from_slice(&cipher_packet[end - MIC_SIZE..end]);
+        Ok(mic)
+    }
+
+    /// Arms the peripheral to encrypt a packet while the RADIO transmits it.
+    ///
+    /// The `cipher_packet` is the buffer transmitted by the RADIO, and the buffers must have the
+    /// same sizes as for [`Ccm::encrypt_packet`]. See the module level documentation for the
+    /// required event chain. `data_rate` must match the data rate of the RADIO.
+    pub fn encrypt_radio_packet<C, P>(
+        &mut self,
+        mut buffers: RadioBuffers<C, P>,
+        data_rate: DataRate,
+    ) -> Result<RadioCrypt<'_, C, P>, (CcmError, RadioBuffers<C, P>)>
+    where
+        C: ReadBuffer<Word = u8> + 'static,
+        P: WriteBuffer<Word = u8> + 'static,
+    {
+        // NOTE(unsafe) The buffers are `'static` and owned by the returned `RadioCrypt`, which
+        // stops the peripheral before giving them back.
+        let (clear_packet, cipher_packet) = unsafe {
+            let (ptr, len) = buffers.clear_packet.read_buffer();
+            let clear_packet = core::slice::from_raw_parts(ptr, len);
+            let (ptr, len) = buffers.cipher_packet.write_buffer();
+            (clear_packet, core::slice::from_raw_parts_mut(ptr, len))
+        };
+
+        if !(slice_in_ram(clear_packet)
+            && slice_in_ram(cipher_packet)
+            && slice_in_ram(buffers.scratch))
+        {
+            return Err((CcmError::BufferNotInRAM, buffers));
+        }
+
+        if clear_packet.len() < HEADER_SIZE {
+            return Err((CcmError::WrongPacketLength, buffers));
+        }
+
+        let payload_len = clear_packet[LENGTH_HEADER_INDEX] as usize;
+        if clear_packet.len() < payload_len + HEADER_SIZE
+            || cipher_packet.len() < payload_len + HEADER_SIZE + MIC_SIZE
+            || payload_len + MIC_SIZE > u8::MAX as usize
+        {
+            return Err((CcmError::WrongPacketLength, buffers));
+        }
+
+        self.start_radio_crypt(
+            clear_packet.as_ptr(),
+            cipher_packet.as_mut_ptr(),
+            buffers,
+            payload_len + MIC_SIZE,
+            data_rate,
+            false,
+        )
+    }
+
+    /// Arms the peripheral to decrypt a packet while the RADIO receives it.
+    ///
+    /// The `cipher_packet` is the buffer the RADIO receives into, and limits the size of the
+    /// received packet. The `clear_packet` needs room for the same packet, minus the MIC. See the
+    /// module level documentation for the required event chain. `data_rate` must match the data
+    /// rate of the RADIO.
+    pub fn decrypt_radio_packet<C, P>(
+        &mut self,
+        mut buffers: RadioBuffers<C, P>,
+        data_rate: DataRate,
+    ) -> Result<RadioCrypt<'_, C, P>, (CcmError, RadioBuffers<C, P>)>
+    where
+        C: WriteBuffer<Word = u8> + 'static,
+        // The RADIO receives into the cipher packet, so it has to be writable too.
+        P: WriteBuffer<Word = u8> + 'static,
+    {
+        // NOTE(unsafe) The buffers are `'static` and owned by the returned `RadioCrypt`, which
+        // stops the peripheral before giving them back.
+        let (clear_packet, cipher_packet) = unsafe {
+            let (ptr, len) = buffers.clear_packet.write_buffer();
+            let clear_packet = core::slice::from_raw_parts_mut(ptr, len);
+            let (ptr, len) = buffers.cipher_packet.write_buffer();
+            (clear_packet, core::slice::from_raw_parts(ptr, len))
+        };
+
+        if !(slice_in_ram(clear_packet)
+            && slice_in_ram(cipher_packet)
+            && slice_in_ram(buffers.scratch))
+        {
+            return Err((CcmError::BufferNotInRAM, buffers));
+        }
+
+        if cipher_packet.len() < HEADER_SIZE + MIC_SIZE
+            || clear_packet.len() < cipher_packet.len() - MIC_SIZE
+        {
+            return Err((CcmError::WrongPacketLength, buffers));
+        }
+
+        let max_payload_len = (cipher_packet.len() - HEADER_SIZE).min(u8::MAX as usize);
+
+        self.start_radio_crypt(
+            cipher_packet.as_ptr(),
+            clear_packet.as_mut_ptr(),
+            buffers,
+            max_payload_len,
+            data_rate,
+            true,
+        )
+    }
+
+    /// Configures the peripheral for an on-the-fly operation, `payload_len` includes the MIC.
+    fn start_radio_crypt<C, P>(
+        &mut self,
+        in_ptr: *const u8,
+        out_ptr: *mut u8,
+        buffers: RadioBuffers<C, P>,
+        payload_len: usize,
+        data_rate: DataRate,
+        decrypt: bool,
+    ) -> Result<RadioCrypt<'_, C, P>, (CcmError, RadioBuffers<C, P>)> {
+        if buffers.scratch.len() < (payload_len + 16).max(MINIMUM_SCRATCH_AREA_SIZE) {
+            return Err((CcmError::InsufficientScratchArea, buffers));
+        }
+
+        #[cfg(feature = "51")]
+        {
+            if payload_len > MAXIMUM_LENGTH_5BITS {
+                return Err((CcmError::WrongPacketLength, buffers));
+            }
+            let _ = data_rate;
+            self.regs.mode.write(|w| {
+                if decrypt {
+                    w.mode().decryption()
+                } else {
+                    w.mode().encryption()
+                }
+            });
+        }
+
+        #[cfg(not(feature = "51"))]
+        {
+            let length_variant = if payload_len <= MAXIMUM_LENGTH_5BITS {
+                LENGTH_A::DEFAULT
+            } else {
+                #[cfg(any(
+                    feature = "52840",
+                    feature = "52833",
+                    feature = "52811",
+                    feature = "52810",
+                    feature = "52805",
+                    feature = "5340-net"
+                ))]
+                // NOTE(unsafe) Any 8bits pattern is safe to write to this register
+                self.regs
+                    .maxpacketsize
+                    .write(|w| unsafe { w.maxpacketsize().bits(payload_len as u8) });
+
+                LENGTH_A::EXTENDED
+            };
+
+            // The blocking methods reset the data rate, so it is always written here.
+            self.regs.mode.write(|w| {
+                let w = w
+                    .datarate()
+                    .variant(data_rate.into())
+                    .length()
+                    .variant(length_variant);
+                if decrypt {
+                    w.mode().decryption()
+                } else {
+                    w.mode().encryption()
+                }
+            });
+        }
+
+        // Setup the pointers
+        // NOTE(unsafe) These addresses are in RAM, checked by the callers
+        unsafe {
+            self.regs
+                .cnfptr
+                .write(|w| w.bits(buffers.ccm_data as *const _ as u32));
+            self.regs.inptr.write(|w| w.bits(in_ptr as u32));
+            self.regs.outptr.write(|w| w.bits(out_ptr as u32));
+            self.regs
+                .scratchptr
+                .write(|w| w.bits(buffers.scratch.as_ptr() as u32));
+        }
+
+        // Clear events
+        self.regs.events_endcrypt.reset();
+        self.regs.events_error.reset();
+        self.regs.events_endksgen.reset();
+
+        // When receiving, CRYPT is triggered by the RADIO ADDRESS event instead.
+        self.regs.shorts.write(|w| w.endksgen_crypt().bit(!decrypt));
+
+        // "Preceding reads and writes cannot be moved past subsequent writes."
+        compiler_fence(Ordering::Release);
+
+        Ok(RadioCrypt {
+            ccm: self,
+            buffers: Some(buffers),
+            decrypt,
+        })
+    }
 }
@@ -28,6 +28,20 @@ pub const HFCLK_FREQ: u32 = 64_000_000;
 /// Low Frequency Clock Frequency (in Hz).
 pub const LFCLK_FREQ: u32 = 32_768;
 
+/// `HFCLKSTAT.SRC`: the high frequency clock is sourced from the crystal oscillator.
+const HFCLKSTAT_SRC_XTAL: u32 = 1 << 0;
+/// `HFCLKSTAT.STATE`: the high frequency clock requested by `HFCLKSTART` is running.
+const HFCLKSTAT_STATE_RUNNING: u32 = 1 << 16;
+
+/// Source of the high frequency clock, as reported by [`Clocks::hfclk_source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HfClkSource {
+    /// Internal RC oscillator.
+    Internal,
+    /// External crystal oscillator.
+    ExternalOscillator,
+}
+
 /// A high level abstraction for the CLOCK peripheral.
 pub struct Clocks<H, L, LSTAT> {
     hfclk: H,
@@ -49,7 +63,14 @@ impl Clocks<Internal, Internal, LfOscStopped> {
 
 impl<H, L, LSTAT> Clocks<H, L, LSTAT> {
     /// Use an external oscillator as the high frequency clock source.
+    ///
+    /// Requests the crystal and blocks until `EVENTS_HFCLKSTARTED` reports it running, so
+    /// [`Clocks::hfclk_source`] returns [`HfClkSource::ExternalOscillator`] afterwards.
     pub fn enable_ext_hfosc(self) -> Clocks<ExternalOscillator, L, LSTAT> {
+        // A stale event would end the wait below before the crystal has started.
+        self.periph
+            .events_hfclkstarted
+            .write(|w| unsafe { w.bits(0) });
         self.periph.tasks_hfclkstart.write(|w| unsafe { w.bits(1) });
 
         // Datasheet says this is likely to take 0.36ms
@@ -77,6 +98,20 @@ impl<H, L, LSTAT> Clocks<H, L, LSTAT> {
         }
     }
 
+    /// Returns the oscillator currently sourcing the high frequency clock, read from
+    /// `HFCLKSTAT`.
+    ///
+    /// The type state only tracks what was requested; this reports what the hardware runs on,
+    /// e.g. to confirm the crystal is up before relying on it for radio timing.
+    pub fn hfclk_source(&self) -> HfClkSource {
+        let stat = self.periph.hfclkstat.read().bits();
+        if stat & HFCLKSTAT_STATE_RUNNING != 0 && stat & HFCLKSTAT_SRC_XTAL != 0 {
+            HfClkSource::ExternalOscillator
+        } else {
+            HfClkSource::Internal
+        }
+    }
+
     /// Start the Low Frequency clock.
     pub fn start_lfclk(self) -> Clocks<H, L, LfOscStarted> {
         self.periph.tasks_lfclkstart.write(|w| unsafe { w.bits(1) });
@@ -118,6 +153,78 @@ impl<H, L> Clocks<H, L, LfOscStarted> {
     }
 }
 
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+impl Clocks<ExternalOscillator, Internal, LfOscStopped> {
+    /// Start the Low Frequency clock from the internal RC oscillator and recalibrate it
+    /// periodically against the high frequency crystal.
+    ///
+    /// The calibration timer fires every `interval` quarter seconds (1 to 127), e.g. `16` for 4
+    /// seconds, which keeps the RC oscillator within its specified accuracy (±500 ppm) as the
+    /// temperature changes. Its timeout and the end of each calibration are handled by
+    /// [`Clocks::on_calibration_interrupt`], which has to be called from the `POWER_CLOCK`
+    /// interrupt handler. The interrupt also has to be unmasked in the NVIC.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is not within 1 to 127.
+    pub fn start_lfclk_rc_calibrated(
+        self,
+        interval: u8,
+    ) -> Clocks<ExternalOscillator, Internal, LfOscStarted> {
+        assert!((1..=127).contains(&interval));
+
+        let clocks = self.set_lfclk_src_rc().start_lfclk();
+
+        clocks.periph.events_done.write(|w| unsafe { w.bits(0) });
+        clocks.periph.events_ctto.write(|w| unsafe { w.bits(0) });
+        clocks
+            .periph
+            .ctiv
+            .write(|w| unsafe { w.bits(u32::from(interval)) });
+        clocks
+            .periph
+            .intenset
+            .write(|w| w.done().set().ctto().set());
+        clocks.periph.tasks_ctstart.write(|w| unsafe { w.bits(1) });
+
+        clocks
+    }
+}
+
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+impl Clocks<ExternalOscillator, Internal, LfOscStarted> {
+    /// Drives the periodic calibration set up by [`Clocks::start_lfclk_rc_calibrated`]. Call
+    /// this from the `POWER_CLOCK` interrupt handler.
+    ///
+    /// Starts a calibration when the calibration timer times out, and restarts the timer once
+    /// the calibration is done.
+    pub fn on_calibration_interrupt(&mut self) {
+        if self.periph.events_ctto.read().bits() != 0 {
+            self.periph.events_ctto.write(|w| unsafe { w.bits(0) });
+            self.periph.tasks_cal.write(|w| unsafe { w.bits(1) });
+        }
+        if self.periph.events_done.read().bits() != 0 {
+            self.periph.events_done.write(|w| unsafe { w.bits(0) });
+            self.periph.tasks_ctstart.write(|w| unsafe { w.bits(1) });
+        }
+    }
+
+    /// Calibrates the RC oscillator once and blocks until the calibration is done.
+    ///
+    /// This is useful after a large temperature change, or when the periodic calibration is not
+    /// used.
+    ///
+    /// The `DONE` event is also consumed by [`Clocks::on_calibration_interrupt`], so don't let the
+    /// interrupt handler preempt this method, e.g. call it while holding the lock that shares the
+    /// `Clocks` with the handler.
+    pub fn calibrate_lfclk_rc(&mut self) {
+        self.periph.events_done.write(|w| unsafe { w.bits(0) });
+        self.periph.tasks_cal.write(|w| unsafe { w.bits(1) });
+        while self.periph.events_done.read().bits() == 0 {}
+        self.periph.events_done.write(|w| unsafe { w.bits(0) });
+    }
+}
+
 impl<H, L> Clocks<H, L, LfOscStopped> {
     /// Use the internal RC Oscillator for the low frequency clock source.
     #[cfg(feature = "51")]
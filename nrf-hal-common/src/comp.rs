@@ -3,17 +3,45 @@
 //! The comparator (COMP) compares an input voltage (Vin) against a second input voltage (Vref).
 //! Vin can be derived from an analog input pin (AIN0-AIN7).
 //! Vref can be derived from multiple sources depending on the operation mode of the comparator.
+//!
+//! The UP, DOWN and CROSS events can be routed through the PPI, e.g. to capture a timer or start
+//! a SAADC sampling when the input crosses a threshold, or enabled as interrupts to wake the CPU
+//! from System ON sleep. Waking from System OFF is only possible with the LPCOMP.
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::{gpio::p0::Parts, pac::Peripherals};
+//! # use nrf_hal_common::comp::{Comp, PowerMode, Transition, VRef};
+//! let p = Peripherals::take().unwrap();
+//! let port0 = Parts::new(p.P0);
+//! let input = port0.p0_04.into_floating_input();
+//!
+//! let comp = Comp::new(p.COMP, &input);
+//! // Trigger at 2/3 and release at 1/2 of 1.8 V.
+//! comp.vref(VRef::Int1V8)
+//!     .hysteresis_threshold_up(42)
+//!     .hysteresis_threshold_down(31)
+//!     .power_mode(PowerMode::LowPower)
+//!     .enable_interrupt(Transition::Up);
+//! comp.enable();
+//!
+//! if comp.is_above() {
+//!     // Already above the threshold.
+//! }
+//! ```
 
 use crate::gpio::{p0::*, Floating, Input};
 #[cfg(not(feature = "5340-app"))]
 use crate::pac::comp::{
-    extrefsel::EXTREFSEL_A, psel::PSEL_A, EVENTS_CROSS, EVENTS_DOWN, EVENTS_UP,
+    extrefsel::EXTREFSEL_A, psel::PSEL_A, EVENTS_CROSS, EVENTS_DOWN, EVENTS_UP, TASKS_SAMPLE,
+    TASKS_START, TASKS_STOP,
 };
 use crate::pac::COMP;
 
 #[cfg(feature = "5340-app")]
 use crate::pac::comp_ns::{
-    extrefsel::EXTREFSEL_A, psel::PSEL_A, EVENTS_CROSS, EVENTS_DOWN, EVENTS_UP,
+    extrefsel::EXTREFSEL_A, psel::PSEL_A, EVENTS_CROSS, EVENTS_DOWN, EVENTS_UP, TASKS_SAMPLE,
+    TASKS_START, TASKS_STOP,
 };
 /// A safe wrapper around the `COMP` peripheral.
 pub struct Comp {
@@ -24,20 +52,22 @@ impl Comp {
     /// Takes ownership of the `COMP` peripheral, returning a safe wrapper.
     pub fn new<P: CompInputPin>(comp: COMP, input_pin: &P) -> Self {
         comp.psel.write(|w| w.psel().variant(input_pin.ain()));
-        comp.mode.write(|w| w.sp().normal());
-        comp.mode.write(|w| w.main().se());
+        comp.mode.write(|w| w.sp().normal().main().se());
         comp.refsel.write(|w| w.refsel().int1v2());
         Self { comp }
     }
 
     /// Sets the speed and power mode of the comparator.
+    ///
+    /// See [`PowerMode`] for the tradeoff. Only takes effect the next time the comparator is
+    /// enabled.
     #[inline(always)]
     pub fn power_mode(&self, mode: PowerMode) -> &Self {
-        match mode {
-            PowerMode::LowPower => self.comp.mode.write(|w| w.sp().low()),
-            PowerMode::Normal => self.comp.mode.write(|w| w.sp().normal()),
-            PowerMode::HighSpeed => self.comp.mode.write(|w| w.sp().high()),
-        }
+        self.comp.mode.modify(|_, w| match mode {
+            PowerMode::LowPower => w.sp().low(),
+            PowerMode::Normal => w.sp().normal(),
+            PowerMode::HighSpeed => w.sp().high(),
+        });
         self
     }
 
@@ -69,17 +99,19 @@ impl Comp {
     /// Sets comparator mode to differential with external Vref pin.
     #[inline(always)]
     pub fn differential<P: CompRefPin>(&self, ref_pin: &P) -> &Self {
-        self.comp.mode.write(|w| w.main().diff());
+        self.comp.mode.modify(|_, w| w.main().diff());
         self.aref_pin(ref_pin);
         self
     }
 
     /// Upward hysteresis threshold in single ended mode `Vup = (value+1)/64*Vref`.
+    ///
+    /// The output goes high once Vin rises above Vup and low once it falls below Vdown.
     #[inline(always)]
     pub fn hysteresis_threshold_up(&self, value: u8) -> &Self {
         self.comp
             .th
-            .write(|w| unsafe { w.thup().bits(value.min(63)) });
+            .modify(|_, w| unsafe { w.thup().bits(value.min(63)) });
         self
     }
 
@@ -88,11 +120,14 @@ impl Comp {
     pub fn hysteresis_threshold_down(&self, value: u8) -> &Self {
         self.comp
             .th
-            .write(|w| unsafe { w.thdown().bits(value.min(63)) });
+            .modify(|_, w| unsafe { w.thdown().bits(value.min(63)) });
         self
     }
 
     /// Enables/disables differential comparator hysteresis (50mV).
+    ///
+    /// In single ended mode, the hysteresis is set by [`Comp::hysteresis_threshold_up`] and
+    /// [`Comp::hysteresis_threshold_down`] instead.
     #[inline(always)]
     pub fn hysteresis(&self, enabled: bool) -> &Self {
         self.comp.hyst.write(|w| match enabled {
@@ -203,6 +238,30 @@ impl Comp {
         }
     }
 
+    /// Returns `true` if Vin is above Vref, sampling the comparator output.
+    #[inline(always)]
+    pub fn is_above(&self) -> bool {
+        self.read() == CompResult::Above
+    }
+
+    /// Returns reference to `Start` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_start(&self) -> &TASKS_START {
+        &self.comp.tasks_start
+    }
+
+    /// Returns reference to `Stop` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_stop(&self) -> &TASKS_STOP {
+        &self.comp.tasks_stop
+    }
+
+    /// Returns reference to `Sample` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_sample(&self) -> &TASKS_SAMPLE {
+        &self.comp.tasks_sample
+    }
+
     /// Consumes `self` and returns back the raw `COMP` peripheral.
     #[inline(always)]
     pub fn free(self) -> COMP {
@@ -216,10 +275,19 @@ pub enum OperationMode {
     SingleEnded,
 }
 
+/// Speed and power mode, the `MODE.SP` field.
+///
+/// Faster modes react to a crossing with a shorter propagation delay, at the cost of a higher
+/// current consumption while the comparator is running. The figures are chip specific, see the
+/// product specification.
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum PowerMode {
+    /// Lowest current consumption and slowest response, for slowly changing signals such as a
+    /// battery voltage.
     LowPower,
+    /// Balanced speed and power.
     Normal,
+    /// Fastest response, with the highest current consumption.
     HighSpeed,
 }
 
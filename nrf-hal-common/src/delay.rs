@@ -1,22 +1,37 @@
 //! Delays.
 
 use crate::clocks::HFCLK_FREQ;
+use crate::time::Hertz;
 use core::convert::TryInto;
 use cortex_m::peripheral::syst::SystClkSource;
 use cortex_m::peripheral::SYST;
 use embedded_hal::delay::DelayNs;
 
 /// System timer (SysTick) as a delay provider.
+///
+/// Implements the blocking [`DelayNs`] trait of embedded-hal 1.0, as well as the embedded-hal 0.2
+/// delay traits when the `embedded-hal-02` feature is enabled.
 pub struct Delay {
     syst: SYST,
+    core_freq: u32,
 }
 
 impl Delay {
-    /// Configures the system timer (SysTick) as a delay provider.
-    pub fn new(mut syst: SYST) -> Self {
+    /// Configures the system timer (SysTick) as a delay provider, for a core running at the
+    /// default 64 MHz.
+    pub fn new(syst: SYST) -> Self {
+        Self::with_frequency(syst, Hertz(HFCLK_FREQ))
+    }
+
+    /// Configures the system timer (SysTick) as a delay provider, for a core running at
+    /// `core_freq`, e.g. 128 MHz for the nRF5340 application core.
+    pub fn with_frequency(mut syst: SYST, core_freq: Hertz) -> Self {
         syst.set_clock_source(SystClkSource::Core);
 
-        Delay { syst }
+        Delay {
+            syst,
+            core_freq: core_freq.0,
+        }
     }
 
     /// Releases the system timer (SysTick) resource.
@@ -72,7 +87,7 @@ impl DelayNs for Delay {
         // The SysTick Reload Value register supports values between 1 and 0x00FFFFFF.
         const MAX_RVR: u32 = 0x00FF_FFFF;
 
-        let mut total_rvr: u32 = (u64::from(ns) * u64::from(HFCLK_FREQ) / 1_000_000_000)
+        let mut total_rvr: u32 = (u64::from(ns) * u64::from(self.core_freq) / 1_000_000_000)
             .try_into()
             .unwrap();
 
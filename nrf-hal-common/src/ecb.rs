@@ -13,6 +13,21 @@ use core::sync::atomic::{compiler_fence, Ordering};
 #[derive(Debug, Copy, Clone)]
 pub struct EncryptionError {}
 
+/// Error type returned by [`Ecb::encrypt_blocks`].
+#[derive(Debug, Copy, Clone)]
+pub enum BlocksError {
+    /// The data length is not a multiple of the 16 byte block size.
+    InvalidLength,
+    /// The encryption of a block failed, see [`Ecb::encrypt_block`].
+    Encryption(EncryptionError),
+}
+
+impl From<EncryptionError> for BlocksError {
+    fn from(e: EncryptionError) -> Self {
+        BlocksError::Encryption(e)
+    }
+}
+
 /// A safe, blocking wrapper around the AES-ECB peripheral.
 ///
 /// It's really just blockwise AES and not an ECB stream cipher. Blocks can be
@@ -99,4 +114,28 @@ impl Ecb {
         }
         Ok(buf.cipher_text)
     }
+
+    /// Blocking encryption of multiple blocks.
+    ///
+    /// Encrypts `data` in place with `key`, one 16 byte block at a time. This is the plain ECB
+    /// mode, chaining modes such as CBC or CTR can be built on top of it.
+    ///
+    /// # Errors
+    ///
+    /// [`BlocksError::InvalidLength`] is returned, and nothing is encrypted, when the length of
+    /// `data` is not a multiple of 16. [`BlocksError::Encryption`] is returned as explained in
+    /// [`Ecb::encrypt_block`], in which case the blocks before the failing one have already been
+    /// encrypted.
+    pub fn encrypt_blocks(&mut self, data: &mut [u8], key: [u8; 16]) -> Result<(), BlocksError> {
+        if !data.chunks_exact(16).remainder().is_empty() {
+            return Err(BlocksError::InvalidLength);
+        }
+
+        for chunk in data.chunks_exact_mut(16) {
+            let mut block = [0; 16];
+            block.copy_from_slice(chunk);
+            chunk.copy_from_slice(&self.encrypt_block(block, key)?);
+        }
+        Ok(())
+    }
 }
@@ -35,6 +35,69 @@ pub enum Level {
     High,
 }
 
+/// Pin level that asserts the port's DETECT signal, see [`Pin::set_sense`].
+///
+/// Despite the name, sensing is level-triggered: DETECT is asserted for as
+/// long as the pin is at the selected level (or, in [`DetectMode::Latched`],
+/// until the pin's LATCH bit is cleared). A DETECT rising edge wakes the
+/// chip from System OFF and fires the GPIOTE PORT event.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SenseEdge {
+    /// The pin does not contribute to DETECT.
+    Disabled,
+    /// Sense a high level.
+    High,
+    /// Sense a low level.
+    Low,
+}
+
+/// How the per-pin sense signals are combined into the port's DETECT signal.
+#[cfg(not(feature = "51"))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DetectMode {
+    /// DETECT is the OR of the pins' sense signals (reset value).
+    ///
+    /// If one pin keeps DETECT asserted, other pins cannot produce a new rising
+    /// edge, so wakeups from a second pin can be lost.
+    Default,
+    /// DETECT is the OR of the LATCH register.
+    ///
+    /// Every sensing pin sets its LATCH bit, and DETECT stays asserted until all
+    /// bits are cleared with [`PortDetect::clear_latch`](p0::PortDetect::clear_latch).
+    /// Use this to find out which pins caused a wakeup.
+    Latched,
+}
+
+/// Iterator over the numbers of the pins set in a port's LATCH register, as returned by
+/// [`PortDetect::latch`](p0::PortDetect::latch).
+#[cfg(not(feature = "51"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatchedPins {
+    bits: u32,
+}
+
+#[cfg(not(feature = "51"))]
+impl LatchedPins {
+    /// Returns the remaining pins as a bit mask, bit `n` representing pin `n`.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+}
+
+#[cfg(not(feature = "51"))]
+impl Iterator for LatchedPins {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.bits == 0 {
+            return None;
+        }
+        let pin = self.bits.trailing_zeros() as u8;
+        self.bits &= self.bits - 1;
+        Some(pin)
+    }
+}
+
 /// A GPIO port with up to 32 pins.
 #[derive(Debug, Eq, PartialEq)]
 pub enum Port {
@@ -349,6 +412,69 @@ impl<MODE> Pin<MODE> {
     }
 }
 
+impl<MODE> Pin<Input<MODE>> {
+    /// Disconnects the input buffer while keeping the pull configuration.
+    ///
+    /// This saves power on pins that are used as analog inputs or left unused.
+    pub fn disconnect_input(self) -> Pin<Disconnected> {
+        self.conf().modify(|_, w| w.input().disconnect());
+
+        Pin {
+            _mode: PhantomData,
+            pin_port: self.pin_port,
+        }
+    }
+
+    /// Configures which level of this pin asserts the port's DETECT signal.
+    ///
+    /// This is what allows the pin to wake the chip from System OFF without a
+    /// GPIOTE channel.
+    pub fn set_sense(&mut self, sense: SenseEdge) {
+        self.conf().modify(|_, w| match sense {
+            SenseEdge::Disabled => w.sense().disabled(),
+            SenseEdge::High => w.sense().high(),
+            SenseEdge::Low => w.sense().low(),
+        });
+    }
+}
+
+impl Pin<Output<PushPull>> {
+    /// Changes the drive strength of the pin without reconfiguring it.
+    pub fn set_drive(&mut self, drive: DriveConfig) {
+        self.conf()
+            .modify(|_, w| w.drive().variant(drive.variant()));
+    }
+}
+
+impl Pin<Output<OpenDrain>> {
+    /// Changes the open-drain drive configuration of the pin without
+    /// reconfiguring it.
+    pub fn set_drive(&mut self, config: OpenDrainConfig) {
+        self.conf()
+            .modify(|_, w| w.drive().variant(config.variant()));
+    }
+}
+
+impl Pin<Output<OpenDrainIO>> {
+    /// Changes the open-drain drive configuration of the pin without
+    /// reconfiguring it.
+    pub fn set_drive(&mut self, config: OpenDrainConfig) {
+        self.conf()
+            .modify(|_, w| w.drive().variant(config.variant()));
+    }
+
+    /// Disconnects the input buffer, turning the pin into an output-only
+    /// open-drain pin.
+    pub fn disconnect_input(self) -> Pin<Output<OpenDrain>> {
+        self.conf().modify(|_, w| w.input().disconnect());
+
+        Pin {
+            _mode: PhantomData,
+            pin_port: self.pin_port,
+        }
+    }
+}
+
 impl<MODE> ErrorType for Pin<MODE> {
     type Error = Infallible;
 }
@@ -501,6 +627,19 @@ use crate::pac::p0_ns::pin_cnf;
 )))]
 use crate::pac::p0::pin_cnf;
 
+impl DriveConfig {
+    fn variant(self) -> pin_cnf::DRIVE_A {
+        use self::DriveConfig::*;
+
+        match self {
+            Standard0Standard1 => pin_cnf::DRIVE_A::S0S1,
+            Standard0HighDrive1 => pin_cnf::DRIVE_A::S0H1,
+            HighDrive0Standard1 => pin_cnf::DRIVE_A::H0S1,
+            HighDrive0HighDrive1 => pin_cnf::DRIVE_A::H0H1,
+        }
+    }
+}
+
 impl OpenDrainConfig {
     fn variant(self) -> pin_cnf::DRIVE_A {
         use self::OpenDrainConfig::*;
@@ -540,9 +679,13 @@ macro_rules! gpio {
                 OpenDrainIO,
 
                 PhantomData,
+                SenseEdge,
                 $PX
             };
 
+            #[cfg(not(feature = "51"))]
+            use super::{DetectMode, LatchedPins};
+
             use core::convert::Infallible;
             use embedded_hal::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
 
@@ -550,12 +693,20 @@ macro_rules! gpio {
             // This chunk allows you to obtain an nrf-hal gpio from the
             // upstream nrf52 gpio definitions by defining a trait
             // ===============================================================
+            /// Number of pins in this port.
+            pub const PIN_COUNT: usize = [$($i),+].len();
+
             /// GPIO parts
             pub struct Parts {
                 $(
                     /// Pin
                     pub $pxi: $PXi<$MODE>,
                 )+
+                /// Port-wide output access, see [`PortOutputs`].
+                pub outputs: PortOutputs,
+                /// Port-wide sense/latch access, see [`PortDetect`].
+                #[cfg(not(feature = "51"))]
+                pub detect: PortDetect,
             }
 
             impl Parts {
@@ -566,8 +717,115 @@ macro_rules! gpio {
                                 _mode: PhantomData,
                             },
                         )+
+                        outputs: PortOutputs { _private: () },
+                        #[cfg(not(feature = "51"))]
+                        detect: PortDetect { _private: () },
                     }
                 }
+
+                /// Degrades every pin of the port and returns them as an array indexed
+                /// by pin number.
+                ///
+                /// This is useful when pins are selected at runtime, e.g. from a
+                /// configuration table.
+                pub fn into_array(self) -> [Pin<Disconnected>; PIN_COUNT] {
+                    [
+                        $(
+                            self.$pxi.degrade(),
+                        )+
+                    ]
+                }
+            }
+
+            /// Drives several output pins of this port with a single register write.
+            ///
+            /// Bit `n` of every mask refers to pin `n` of the port. Only pins that
+            /// have been configured as outputs are affected by the output registers,
+            /// but note that this handle can drive *any* output pin of the port,
+            /// including pins that have been moved elsewhere.
+            pub struct PortOutputs {
+                _private: (),
+            }
+
+            impl PortOutputs {
+                /// Drives all pins in `mask` high in a single write to `OUTSET`.
+                #[inline]
+                pub fn set(&mut self, mask: u32) {
+                    // NOTE(unsafe) atomic write to a stateless register
+                    unsafe { (*$PX::ptr()).outset.write(|w| w.bits(mask)); }
+                }
+
+                /// Drives all pins in `mask` low in a single write to `OUTCLR`.
+                #[inline]
+                pub fn clear(&mut self, mask: u32) {
+                    // NOTE(unsafe) atomic write to a stateless register
+                    unsafe { (*$PX::ptr()).outclr.write(|w| w.bits(mask)); }
+                }
+
+                /// Drives the pins in `set` high and the pins in `clear` low.
+                ///
+                /// This is one write to `OUTSET` immediately followed by one write to
+                /// `OUTCLR`, so all rising edges happen together and all falling edges
+                /// happen together one bus cycle later. Pins present in both masks end
+                /// up low.
+                #[inline]
+                pub fn write_mask(&mut self, set: u32, clear: u32) {
+                    self.set(set);
+                    self.clear(clear);
+                }
+
+                /// Returns the current contents of the `OUT` register.
+                #[inline]
+                pub fn read(&self) -> u32 {
+                    // NOTE(unsafe) atomic read with no side effects
+                    unsafe { (*$PX::ptr()).out.read().bits() }
+                }
+            }
+
+            /// Access to the port's DETECT configuration and LATCH register.
+            ///
+            /// Pins are enabled for sensing with `set_sense`. When a sensing pin
+            /// reaches its configured level, its bit in LATCH is set, regardless of
+            /// the [`DetectMode`]. After a wakeup from System OFF, LATCH therefore
+            /// reports which pins triggered.
+            #[cfg(not(feature = "51"))]
+            pub struct PortDetect {
+                _private: (),
+            }
+
+            #[cfg(not(feature = "51"))]
+            impl PortDetect {
+                /// Selects how pin sense signals are combined into DETECT.
+                pub fn set_detect_mode(&mut self, mode: DetectMode) {
+                    unsafe { &(*$PX::ptr()).detectmode }.write(|w| match mode {
+                        DetectMode::Default => w.detectmode().default(),
+                        DetectMode::Latched => w.detectmode().ldetect(),
+                    });
+                }
+
+                /// Returns the pins whose LATCH bit is set, i.e. that have met their
+                /// sense criteria since the bit was last cleared.
+                ///
+                /// A single GPIOTE port event can be caused by several pins. To service
+                /// them all, reset the port event, then handle and clear the latched pins
+                /// until none are left. With [`DetectMode::Latched`], a new port event is
+                /// only generated once all latched pins of the port have been cleared.
+                #[inline]
+                pub fn latch(&self) -> LatchedPins {
+                    LatchedPins {
+                        bits: unsafe { (*$PX::ptr()).latch.read().bits() },
+                    }
+                }
+
+                /// Clears the LATCH bits in `mask`.
+                ///
+                /// A bit that is cleared while its pin still meets the sense criteria
+                /// is set again immediately.
+                #[inline]
+                pub fn clear_latch(&mut self, mask: u32) {
+                    // NOTE(unsafe) LATCH bits are write-one-to-clear
+                    unsafe { (*$PX::ptr()).latch.write(|w| w.bits(mask)); }
+                }
             }
 
             // ===============================================================
@@ -755,6 +1013,66 @@ macro_rules! gpio {
                     }
                 }
 
+                impl<MODE> $PXi<Input<MODE>> {
+                    /// Disconnects the input buffer while keeping the pull configuration.
+                    ///
+                    /// See [`Pin::disconnect_input`](super::Pin::disconnect_input).
+                    pub fn disconnect_input(self) -> $PXi<Disconnected> {
+                        unsafe { &(*$PX::ptr()).pin_cnf[$i] }.modify(|_, w| w.input().disconnect());
+
+                        $PXi {
+                            _mode: PhantomData,
+                        }
+                    }
+
+                    /// Configures which level of this pin asserts the port's DETECT signal.
+                    ///
+                    /// See [`Pin::set_sense`](super::Pin::set_sense).
+                    pub fn set_sense(&mut self, sense: SenseEdge) {
+                        unsafe { &(*$PX::ptr()).pin_cnf[$i] }.modify(|_, w| match sense {
+                            SenseEdge::Disabled => w.sense().disabled(),
+                            SenseEdge::High => w.sense().high(),
+                            SenseEdge::Low => w.sense().low(),
+                        });
+                    }
+                }
+
+                impl $PXi<Output<PushPull>> {
+                    /// Changes the drive strength of the pin without reconfiguring it.
+                    pub fn set_drive(&mut self, drive: DriveConfig) {
+                        unsafe { &(*$PX::ptr()).pin_cnf[$i] }
+                            .modify(|_, w| w.drive().variant(drive.variant()));
+                    }
+                }
+
+                impl $PXi<Output<OpenDrain>> {
+                    /// Changes the open-drain drive configuration of the pin without
+                    /// reconfiguring it.
+                    pub fn set_drive(&mut self, config: OpenDrainConfig) {
+                        unsafe { &(*$PX::ptr()).pin_cnf[$i] }
+                            .modify(|_, w| w.drive().variant(config.variant()));
+                    }
+                }
+
+                impl $PXi<Output<OpenDrainIO>> {
+                    /// Changes the open-drain drive configuration of the pin without
+                    /// reconfiguring it.
+                    pub fn set_drive(&mut self, config: OpenDrainConfig) {
+                        unsafe { &(*$PX::ptr()).pin_cnf[$i] }
+                            .modify(|_, w| w.drive().variant(config.variant()));
+                    }
+
+                    /// Disconnects the input buffer, turning the pin into an output-only
+                    /// open-drain pin.
+                    pub fn disconnect_input(self) -> $PXi<Output<OpenDrain>> {
+                        unsafe { &(*$PX::ptr()).pin_cnf[$i] }.modify(|_, w| w.input().disconnect());
+
+                        $PXi {
+                            _mode: PhantomData,
+                        }
+                    }
+                }
+
                 impl<MODE> ErrorType for $PXi<MODE> {
                     type Error = Infallible;
                 }
@@ -18,9 +18,14 @@ use crate::pac::P1;
 #[cfg(feature = "5340-net")]
 use crate::pac::P1_NS as P1;
 
+use core::future::poll_fn;
+use core::task::Poll;
+
 use crate::gpio::{
     Floating, Input, Level, OpenDrain, Output, Pin, Port, PullDown, PullUp, PushPull,
 };
+use crate::rtc::{self, Rtc, RtcCompareReg};
+use crate::waker::AtomicWaker;
 
 #[cfg(not(any(feature = "9160", feature = "5340-net")))]
 use {
@@ -48,6 +53,12 @@ const NUM_CHANNELS: usize = 8;
 #[cfg(feature = "51")]
 const NUM_CHANNELS: usize = 4;
 
+#[allow(clippy::declare_interior_mutable_const)]
+const NEW_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Wakers of the tasks waiting in [`GpioteChannel::wait_for_edge_async`], one per channel.
+static WAKERS: [AtomicWaker; NUM_CHANNELS] = [NEW_WAKER; NUM_CHANNELS];
+
 /// A safe wrapper around the GPIOTE peripheral.
 pub struct Gpiote {
     gpiote: GPIOTE,
@@ -59,7 +70,14 @@ impl Gpiote {
         Self { gpiote }
     }
 
-    fn channel(&self, channel: usize) -> GpioteChannel {
+    /// Returns GPIOTE channel number `channel`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not a valid channel number for this chip (0..8,
+    /// or 0..4 on the nRF51).
+    pub fn channel(&self, channel: usize) -> GpioteChannel {
+        assert!(channel < NUM_CHANNELS);
         GpioteChannel {
             gpiote: &self.gpiote,
             channel,
@@ -100,12 +118,32 @@ impl Gpiote {
         }
     }
 
+    /// Returns reference to the port event, shared by all pins with sense enabled.
+    pub fn port_event(&self) -> &EVENTS_PORT {
+        &self.gpiote.events_port
+    }
+
     /// Marks all GPIOTE events as handled
     pub fn reset_events(&self) {
         (0..NUM_CHANNELS).for_each(|ch| self.gpiote.events_in[ch].write(|w| w));
         self.gpiote.events_port.write(|w| w);
     }
 
+    /// Wakes the tasks waiting in [`GpioteChannel::wait_for_edge_async`].
+    ///
+    /// Call this from the GPIOTE interrupt handler. It masks the interrupt of every channel whose
+    /// event has fired, so don't call it for channels handled by a [`DebouncedInput`].
+    pub fn on_interrupt() {
+        let gpiote = unsafe { &*GPIOTE::ptr() };
+        let enabled = gpiote.intenset.read().bits();
+        for (channel, waker) in WAKERS.iter().enumerate() {
+            if enabled & (1 << channel) != 0 && gpiote.events_in[channel].read().bits() != 0 {
+                unsafe { gpiote.intenclr.write(|w| w.bits(1 << channel)) };
+                waker.wake();
+            }
+        }
+    }
+
     /// Consumes `self` and return back the raw `GPIOTE` peripheral.
     pub fn free(self) -> GPIOTE {
         self.gpiote
@@ -113,8 +151,8 @@ impl Gpiote {
 }
 
 pub struct GpioteChannel<'a> {
-    gpiote: &'a GPIOTE,
-    channel: usize,
+    pub(crate) gpiote: &'a GPIOTE,
+    pub(crate) channel: usize,
 }
 
 impl<'a> GpioteChannel<'_> {
@@ -145,6 +183,53 @@ impl<'a> GpioteChannel<'_> {
         self.gpiote.events_in[self.channel].write(|w| w);
     }
 
+    /// Waits for the next event of the channel, configured as an input with
+    /// [`input_pin`](Self::input_pin).
+    ///
+    /// Depending on the polarity set with [`GpioteChannelEvent::hi_to_lo`],
+    /// [`GpioteChannelEvent::lo_to_hi`] or [`GpioteChannelEvent::toggle`], this completes on the
+    /// next falling, rising or any edge. Events from before the call are discarded.
+    ///
+    /// [`Gpiote::on_interrupt`] has to be called from the GPIOTE interrupt handler, and the
+    /// interrupt has to be unmasked in the NVIC. Dropping the future masks the channel interrupt.
+    ///
+    #[cfg_attr(feature = "52840", doc = "```no_run")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common as hal;
+    /// # async fn wait(p: hal::pac::Peripherals) {
+    /// use hal::{gpio::p0::Parts as P0Parts, gpiote::Gpiote};
+    ///
+    /// let port0 = P0Parts::new(p.P0);
+    /// let button = port0.p0_11.into_pullup_input().degrade();
+    ///
+    /// let gpiote = Gpiote::new(p.GPIOTE);
+    /// gpiote.channel0().input_pin(&button).hi_to_lo();
+    ///
+    /// loop {
+    ///     gpiote.channel0().wait_for_edge_async().await;
+    ///     // The button was pressed.
+    /// }
+    /// # }
+    /// ```
+    pub async fn wait_for_edge_async(&mut self) {
+        self.reset_events();
+        let mask = DisableOnDrop(self.gpiote, self.channel);
+
+        poll_fn(|cx| {
+            WAKERS[self.channel].register(cx.waker());
+            if self.is_event_triggered() {
+                Poll::Ready(())
+            } else {
+                unsafe { self.gpiote.intenset.write(|w| w.bits(1 << self.channel)) };
+                Poll::Pending
+            }
+        })
+        .await;
+
+        drop(mask);
+        self.reset_events();
+    }
+
     /// Triggers `task out` (as configured with task_out_polarity, defaults to Toggle).
     pub fn out(&self) {
         self.gpiote.tasks_out[self.channel].write(|w| unsafe { w.bits(1) });
@@ -183,6 +268,15 @@ impl<'a> GpioteChannel<'_> {
     }
 }
 
+/// Masks the channel interrupt when an edge wait completes or is cancelled.
+struct DisableOnDrop<'a>(&'a GPIOTE, usize);
+
+impl Drop for DisableOnDrop<'_> {
+    fn drop(&mut self) {
+        unsafe { self.0.intenclr.write(|w| w.bits(1 << self.1)) };
+    }
+}
+
 pub struct GpiotePort<'a> {
     gpiote: &'a GPIOTE,
 }
@@ -201,6 +295,9 @@ impl<'a> GpiotePort<'_> {
         self.gpiote.intenclr.write(|w| w.port().set_bit());
     }
     /// Checks if port event has been triggered.
+    ///
+    /// The pins that caused it are reported by the port's LATCH register, see
+    /// [`PortDetect::latch`](crate::gpio::p0::PortDetect::latch).
     pub fn is_event_triggered(&self) -> bool {
         self.gpiote.events_port.read().bits() != 0
     }
@@ -299,19 +396,17 @@ impl<'a, P: GpioteInputPin> GpiotePortEvent<'_, P> {
     }
 }
 
+fn port_ptr(port: Port) -> *const <P0 as core::ops::Deref>::Target {
+    match port {
+        Port::Port0 => P0::ptr(),
+        #[cfg(any(feature = "52833", feature = "52840", feature = "5340-net"))]
+        Port::Port1 => P1::ptr(),
+    }
+}
+
 fn config_port_event_pin<P: GpioteInputPin>(pin: &P, sense: PortEventSense) {
     // Set pin sense to specified mode to trigger port events.
-    unsafe {
-        &(*{
-            match pin.port() {
-                Port::Port0 => P0::ptr(),
-                #[cfg(any(feature = "52833", feature = "52840", feature = "5340-net"))]
-                Port::Port1 => P1::ptr(),
-            }
-        })
-        .pin_cnf[pin.pin() as usize]
-    }
-    .modify(|_r, w| match sense {
+    unsafe { &(*port_ptr(pin.port())).pin_cnf[pin.pin() as usize] }.modify(|_r, w| match sense {
         PortEventSense::Disabled => w.sense().disabled(),
         PortEventSense::High => w.sense().high(),
         PortEventSense::Low => w.sense().low(),
@@ -351,9 +446,28 @@ impl<'a, P: GpioteOutputPin> GpioteTask<'_, P> {
         self.task_out_polarity = polarity;
         self
     }
+
+    /// Returns reference to task_out endpoint for PPI.
+    ///
+    /// The pin is driven as configured with [`task_out_polarity`](Self::task_out_polarity).
+    pub fn task_out(&self) -> &TASKS_OUT {
+        &self.gpiote.tasks_out[self.channel]
+    }
+
+    /// Returns reference to task_set endpoint for PPI, which drives the pin high.
+    #[cfg(not(feature = "51"))]
+    pub fn task_set(&self) -> &TASKS_SET {
+        &self.gpiote.tasks_set[self.channel]
+    }
+
+    /// Returns reference to task_clr endpoint for PPI, which drives the pin low.
+    #[cfg(not(feature = "51"))]
+    pub fn task_clr(&self) -> &TASKS_CLR {
+        &self.gpiote.tasks_clr[self.channel]
+    }
 }
 
-fn config_channel_task_pin<P: GpioteOutputPin>(
+pub(crate) fn config_channel_task_pin<P: GpioteOutputPin>(
     gpiote: &GPIOTE,
     channel: usize,
     pin: &P,
@@ -460,3 +574,120 @@ impl GpioteOutputPin for Pin<Output<PushPull>> {
         self.port()
     }
 }
+
+/// A GPIOTE input event with software debouncing.
+///
+/// GPIOTE has no hardware debounce. Instead, when the channel event fires, the channel
+/// interrupt is masked and an RTC compare is armed at the end of the debounce window. Edges
+/// caused by contact bounce during the window are discarded, and the channel is re-armed when
+/// the compare fires. Each accepted edge is reported once by [`was_pressed`](Self::was_pressed).
+///
+/// [`on_interrupt`](Self::on_interrupt) must be called from both the `GPIOTE` interrupt and the
+/// interrupt of the RTC instance, which must be unmasked in the NVIC by the caller. The RTC keeps
+/// running while the debouncer is in use.
+///
+#[cfg_attr(feature = "52840", doc = "```no_run")]
+#[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+/// # use nrf_hal_common as hal;
+/// use hal::{
+///     gpio::p0::Parts as P0Parts,
+///     gpiote::{DebouncedInput, Gpiote},
+///     pac::Peripherals,
+///     rtc::{Rtc, RtcCompareReg},
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+/// let port0 = P0Parts::new(p.P0);
+/// let button = port0.p0_11.into_pullup_input().degrade();
+///
+/// let gpiote = Gpiote::new(p.GPIOTE);
+/// gpiote.channel0().input_pin(&button).hi_to_lo();
+///
+/// let rtc = Rtc::new(p.RTC1, 0).unwrap();
+/// let mut debounced = DebouncedInput::new(&gpiote.channel0(), rtc, RtcCompareReg::Compare0, 20);
+///
+/// // In the GPIOTE and RTC1 interrupt handlers:
+/// debounced.on_interrupt();
+///
+/// // In the application:
+/// if debounced.was_pressed() {
+///     // ...
+/// }
+/// ```
+pub struct DebouncedInput<T> {
+    rtc: Rtc<T>,
+    reg: RtcCompareReg,
+    channel: usize,
+    window: u32,
+    armed: bool,
+    pressed: bool,
+}
+
+impl<T: rtc::Instance> DebouncedInput<T> {
+    /// Debounces the events of `channel`, which must already be configured as an input, with a
+    /// window of `window_ms` milliseconds timed by compare register `reg` of `rtc`.
+    ///
+    /// The window is rounded down to whole RTC ticks, with a minimum of two ticks.
+    pub fn new(channel: &GpioteChannel, rtc: Rtc<T>, reg: RtcCompareReg, window_ms: u32) -> Self {
+        let ticks = window_ms as u64 * rtc.frequency().0 as u64 / 1000;
+        rtc.enable_counter();
+        channel.reset_events();
+        unsafe {
+            channel
+                .gpiote
+                .intenset
+                .write(|w| w.bits(1 << channel.channel))
+        };
+
+        Self {
+            rtc,
+            reg,
+            channel: channel.channel,
+            window: ticks.clamp(2, rtc::COUNTER_MASK as u64) as u32,
+            armed: false,
+            pressed: false,
+        }
+    }
+
+    /// Handles the GPIOTE channel and RTC compare events, to be called from both interrupts.
+    pub fn on_interrupt(&mut self) {
+        let gpiote = unsafe { &*GPIOTE::ptr() };
+
+        if self.armed {
+            if self.rtc.is_event_triggered(self.reg.into()) {
+                self.rtc.reset_event(self.reg.into());
+                self.rtc.disable_interrupt(self.reg.into(), None);
+                self.armed = false;
+                // Discard the bounces that happened during the window.
+                gpiote.events_in[self.channel].write(|w| w);
+                unsafe { gpiote.intenset.write(|w| w.bits(1 << self.channel)) };
+            }
+        } else if gpiote.events_in[self.channel].read().bits() != 0 {
+            gpiote.events_in[self.channel].write(|w| w);
+            unsafe { gpiote.intenclr.write(|w| w.bits(1 << self.channel)) };
+            self.pressed = true;
+            self.armed = true;
+
+            let deadline = (self.rtc.get_counter() + self.window) & rtc::COUNTER_MASK;
+            self.rtc.reset_event(self.reg.into());
+            // The deadline is masked to 24 bits, so this cannot fail.
+            let _ = self.rtc.set_compare(self.reg, deadline);
+            self.rtc.enable_interrupt(self.reg.into(), None);
+        }
+    }
+
+    /// Returns `true` once for every debounced edge since the last call.
+    pub fn was_pressed(&mut self) -> bool {
+        core::mem::replace(&mut self.pressed, false)
+    }
+
+    /// Stops debouncing and returns the RTC.
+    ///
+    /// The GPIOTE channel configuration is left untouched, but its interrupt is disabled.
+    pub fn free(mut self) -> Rtc<T> {
+        let gpiote = unsafe { &*GPIOTE::ptr() };
+        unsafe { gpiote.intenclr.write(|w| w.bits(1 << self.channel)) };
+        self.rtc.disable_interrupt(self.reg.into(), None);
+        self.rtc
+    }
+}
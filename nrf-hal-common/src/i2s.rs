@@ -746,6 +746,180 @@ impl<TxB, RxB> Drop for TransferFullDuplex<TxB, RxB> {
     }
 }
 
+/// Double-buffered I2S streaming for gap-free playback and capture.
+///
+/// The TXD.PTR and RXD.PTR registers are double-buffered: the TXPTRUPD and RXPTRUPD events signal
+/// that the pointers have been latched for the next `N` words, so new pointers can be written.
+/// [`DoubleBufferedI2S::on_interrupt`] swaps the pointers on every such event, handing the buffer
+/// that was just played back to the caller for refilling, and the buffer that was just recorded to
+/// the caller for processing.
+///
+/// `N` is the buffer size in 32-bit words, i.e. the number of frames in 16-bit stereo mode.
+pub struct DoubleBufferedI2S<const N: usize> {
+    i2s: I2S,
+    tx: Option<[&'static mut [u32; N]; 2]>,
+    rx: Option<[&'static mut [u32; N]; 2]>,
+    /// Index of the buffer EasyDMA is currently transferring.
+    tx_active: usize,
+    rx_active: usize,
+    /// Whether the first pointer update, latching buffer 0, has been seen.
+    tx_running: bool,
+    rx_running: bool,
+    /// Index of the last completed buffer, if it hasn't been fetched yet.
+    tx_ready: Option<usize>,
+    rx_ready: Option<usize>,
+}
+
+impl<const N: usize> DoubleBufferedI2S<N> {
+    /// Sets up streaming of `tx` and/or `rx`. Transmission and reception are enabled according
+    /// to which buffer pairs are given.
+    ///
+    /// The buffers must be located in RAM and `N` must not exceed the EasyDMA limit.
+    pub fn new(
+        i2s: I2S,
+        tx: Option<[&'static mut [u32; N]; 2]>,
+        rx: Option<[&'static mut [u32; N]; 2]>,
+    ) -> Result<Self, Error> {
+        if N > MAX_DMA_MAXCNT as usize {
+            return Err(Error::BufferTooLong);
+        }
+        let buffers = tx.iter().chain(rx.iter()).flatten();
+        for buffer in buffers {
+            let ptr = buffer.as_ptr() as usize;
+            if !(SRAM_LOWER..=SRAM_UPPER).contains(&ptr) {
+                return Err(Error::DMABufferNotInDataMemory);
+            }
+        }
+        i2s.set_tx_enabled(tx.is_some());
+        i2s.set_rx_enabled(rx.is_some());
+        Ok(Self {
+            i2s,
+            tx,
+            rx,
+            tx_active: 0,
+            rx_active: 0,
+            tx_running: false,
+            rx_running: false,
+            tx_ready: None,
+            rx_ready: None,
+        })
+    }
+
+    /// Starts streaming from/to the first buffers.
+    ///
+    /// Fill both TX buffers before starting. Note that the interrupt also has to be unmasked in
+    /// the NVIC, or [`DoubleBufferedI2S::on_interrupt`] won't get called.
+    pub fn start(&mut self) {
+        self.tx_active = 0;
+        self.rx_active = 0;
+        self.tx_running = false;
+        self.rx_running = false;
+        self.tx_ready = None;
+        self.rx_ready = None;
+
+        let i2s = &self.i2s.i2s;
+        i2s.events_txptrupd.reset();
+        i2s.events_rxptrupd.reset();
+        i2s.events_stopped.reset();
+        i2s.rxtxd.maxcnt.write(|w| unsafe { w.bits(N as u32) });
+        if let Some(tx) = &self.tx {
+            i2s.txd
+                .ptr
+                .write(|w| unsafe { w.ptr().bits(tx[0].as_ptr() as u32) });
+            i2s.intenset.write(|w| w.txptrupd().set());
+        }
+        if let Some(rx) = &mut self.rx {
+            i2s.rxd
+                .ptr
+                .write(|w| unsafe { w.ptr().bits(rx[0].as_mut_ptr() as u32) });
+            i2s.intenset.write(|w| w.rxptrupd().set());
+        }
+
+        // Conservative compiler fence to prevent starting the transfer before the
+        // pointers and maxcount have been set.
+        compiler_fence(Ordering::SeqCst);
+
+        self.i2s.start();
+    }
+
+    /// Stops streaming. Words of incomplete buffers are lost.
+    pub fn stop(&mut self) {
+        let i2s = &self.i2s.i2s;
+        i2s.intenclr
+            .write(|w| w.txptrupd().clear().rxptrupd().clear());
+        self.i2s.stop();
+        i2s.events_stopped.reset();
+        i2s.events_txptrupd.reset();
+        i2s.events_rxptrupd.reset();
+    }
+
+    /// Swaps the buffers. Call this from the interrupt handler of the I2S.
+    pub fn on_interrupt(&mut self) {
+        let i2s = &self.i2s.i2s;
+
+        if i2s.events_txptrupd.read().bits() != 0 {
+            i2s.events_txptrupd.reset();
+            if let Some(tx) = &self.tx {
+                if self.tx_running {
+                    // The active buffer has been played back, the other one is latched now.
+                    self.tx_ready = Some(self.tx_active);
+                    self.tx_active ^= 1;
+                }
+                self.tx_running = true;
+                let next = tx[self.tx_active ^ 1].as_ptr();
+                i2s.txd.ptr.write(|w| unsafe { w.ptr().bits(next as u32) });
+            }
+        }
+
+        if i2s.events_rxptrupd.read().bits() != 0 {
+            i2s.events_rxptrupd.reset();
+            if let Some(rx) = &mut self.rx {
+                if self.rx_running {
+                    // The active buffer has been recorded, the other one is latched now.
+                    self.rx_ready = Some(self.rx_active);
+                    self.rx_active ^= 1;
+                }
+                self.rx_running = true;
+                let next = rx[self.rx_active ^ 1].as_mut_ptr();
+                i2s.rxd.ptr.write(|w| unsafe { w.ptr().bits(next as u32) });
+            }
+        }
+    }
+
+    /// Returns the TX buffer played back last, once, to be refilled.
+    ///
+    /// The buffer has to be refilled before the other one has been played back, i.e. within `N`
+    /// frames.
+    pub fn get_tx_buffer(&mut self) -> Option<&mut [u32; N]> {
+        let ready = self.tx_ready.take()?;
+        let tx = self.tx.as_mut()?;
+        Some(&mut *tx[ready])
+    }
+
+    /// Returns the RX buffer recorded last, once.
+    ///
+    /// The buffer stays valid until the other one is complete, i.e. for `N` frames.
+    pub fn get_rx_buffer(&mut self) -> Option<&[u32; N]> {
+        let ready = self.rx_ready.take()?;
+        // Prevent optimizations creating issues with the EasyDMA-modified buffer.
+        compiler_fence(Ordering::SeqCst);
+        Some(&*self.rx.as_ref()?[ready])
+    }
+
+    /// Stops streaming and returns the I2S and the buffers.
+    #[allow(clippy::type_complexity)]
+    pub fn free(
+        mut self,
+    ) -> (
+        I2S,
+        Option<[&'static mut [u32; N]; 2]>,
+        Option<[&'static mut [u32; N]; 2]>,
+    ) {
+        self.stop();
+        (self.i2s, self.tx, self.rx)
+    }
+}
+
 pub trait SupportedWordSize: private::Sealed {}
 impl private::Sealed for i8 {}
 impl SupportedWordSize for i8 {}
@@ -78,6 +78,16 @@ pub mod ieee802154;
 pub mod lpcomp;
 #[cfg(not(feature = "51"))]
 pub mod nvmc;
+#[cfg(any(
+    feature = "52810",
+    feature = "52811",
+    feature = "52832",
+    feature = "52833",
+    feature = "52840",
+    feature = "9160",
+    feature = "5340-app"
+))]
+pub mod pdm;
 #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
 pub mod ppi;
 #[cfg(not(any(feature = "51", feature = "52805", feature = "5340-net")))]
@@ -89,11 +99,14 @@ pub mod pwm;
     feature = "5340-net"
 )))]
 pub mod qdec;
+#[cfg(not(any(feature = "5340-app", feature = "5340-net")))]
+pub mod reset;
 #[cfg(not(any(feature = "9160", feature = "5340-app")))]
 pub mod rng;
 pub mod rtc;
 #[cfg(not(any(feature = "51", feature = "5340-net")))]
 pub mod saadc;
+pub mod soft_pwm;
 #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
 pub mod spi;
 #[cfg(not(feature = "51"))]
@@ -110,7 +123,7 @@ pub mod twi;
 pub mod twim;
 #[cfg(not(feature = "51"))]
 pub mod twis;
-#[cfg(feature = "51")]
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
 pub mod uart;
 #[cfg(not(feature = "51"))]
 pub mod uarte;
@@ -118,6 +131,7 @@ pub mod uarte;
 pub mod uicr;
 #[cfg(feature = "nrf-usbd")]
 pub mod usbd;
+pub mod waker;
 pub mod wdt;
 
 pub mod prelude {
@@ -126,6 +140,18 @@ pub mod prelude {
     pub use crate::time::U32Ext;
 }
 
+/// Everything needed to drive the HAL's async peripherals.
+///
+/// Contains the blocking [`prelude`], the `embedded-hal-async` traits implemented by the HAL and
+/// the waker storage used to hook custom interrupt handlers into async drivers.
+pub mod async_prelude {
+    pub use crate::prelude::*;
+    pub use crate::waker::AtomicWaker;
+    pub use embedded_hal_async::delay::DelayNs;
+    #[cfg(not(feature = "51"))]
+    pub use embedded_hal_async::{i2c::I2c, spi::SpiBus};
+}
+
 /// Length of Nordic EasyDMA differs for MCUs
 pub mod target_constants {
     #[cfg(feature = "51")]
@@ -6,11 +6,33 @@
 //! Additionally, the low power comparator can be used as an analog wakeup source from System OFF.
 //! The comparator threshold can be programmed to a range of fractions of the supply voltage
 //! or to use an external analog reference input pin.
+//!
+//! For example, to wake up once the battery voltage, divided down to an analog input, rises above
+//! 9/16 of Vdd again:
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::{gpio::p0::Parts, pac::Peripherals};
+//! # use nrf_hal_common::lpcomp::{LpComp, Transition, VRef};
+//! let p = Peripherals::take().unwrap();
+//! let port0 = Parts::new(p.P0);
+//! let battery = port0.p0_04.into_floating_input();
+//!
+//! let lpcomp = LpComp::new(p.LPCOMP, &battery);
+//! lpcomp.vref(VRef::_9_16Vdd).hysteresis(true);
+//! lpcomp.enable_wakeup(Transition::Up);
+//! if !lpcomp.is_above() {
+//!     p.POWER.systemoff.write(|w| w.systemoff().enter());
+//! }
+//! ```
 
 use {
     crate::gpio::{p0::*, Floating, Input},
     crate::pac::{
-        lpcomp::{extrefsel::EXTREFSEL_A, psel::PSEL_A, EVENTS_CROSS, EVENTS_DOWN, EVENTS_UP},
+        lpcomp::{
+            extrefsel::EXTREFSEL_A, psel::PSEL_A, EVENTS_CROSS, EVENTS_DOWN, EVENTS_UP,
+            TASKS_SAMPLE, TASKS_START, TASKS_STOP,
+        },
         LPCOMP,
     },
 };
@@ -59,7 +81,6 @@ impl LpComp {
     }
 
     /// `Analog detect` event configuration, used for analog signal power up from OFF.
-    #[cfg(not(feature = "51"))]
     #[inline(always)]
     pub fn analog_detect(&self, event: Transition) -> &Self {
         self.lpcomp.anadetect.write(|w| match event {
@@ -70,6 +91,20 @@ impl LpComp {
         self
     }
 
+    /// Configures the comparator to wake the chip from System OFF on `event`, and starts it.
+    ///
+    /// Enter System OFF afterwards; the LPCOMP keeps running there. The wakeup is a reset, after
+    /// which [`ResetReason::LpcompWakeup`](crate::reset::ResetReason::LpcompWakeup) is flagged.
+    /// Only a transition wakes the chip, so check [`LpComp::is_above`] before entering System
+    /// OFF if the input may already be past the threshold.
+    #[inline(always)]
+    pub fn enable_wakeup(&self, event: Transition) -> &Self {
+        self.analog_detect(event);
+        self.enable();
+        self.reset_events();
+        self
+    }
+
     /// Enables `COMP_LPCOMP` interrupt triggering on the specified event.
     #[inline(always)]
     pub fn enable_interrupt(&self, event: Transition) -> &Self {
@@ -171,6 +206,30 @@ impl LpComp {
         }
     }
 
+    /// Returns `true` if Vin is above Vref, sampling the comparator output.
+    #[inline(always)]
+    pub fn is_above(&self) -> bool {
+        self.read() == CompResult::Above
+    }
+
+    /// Returns reference to `Start` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_start(&self) -> &TASKS_START {
+        &self.lpcomp.tasks_start
+    }
+
+    /// Returns reference to `Stop` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_stop(&self) -> &TASKS_STOP {
+        &self.lpcomp.tasks_stop
+    }
+
+    /// Returns reference to `Sample` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_sample(&self) -> &TASKS_SAMPLE {
+        &self.lpcomp.tasks_sample
+    }
+
     /// Consumes `self` and returns back the raw `LPCOMP` peripheral.
     #[inline(always)]
     pub fn free(self) -> LPCOMP {
@@ -191,6 +250,11 @@ pub enum Transition {
     Cross,
 }
 
+/// Comparator reference, a fraction of Vdd from the reference ladder or the external reference
+/// pin.
+///
+/// The ladder has 7 steps on the nRF51 and 15 steps on the nRF52, interleaving the eighths with
+/// the sixteenths.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum VRef {
     _1_8Vdd = 0,
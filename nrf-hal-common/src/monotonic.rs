@@ -68,12 +68,16 @@ maximum frequency (16MHz) is `2^32/(16*10^6) = 268` seconds, using a 1MHz TIMER
 overflow `2^32/(10^6) = 4295` seconds or 1.2 hours. For more information on overflow please see the
 [`Timer`](crate::timer::Timer) documentation.
 
-The RTC uses a 24-bit wide counter. The time to overflow can be calculated using:
-`T_overflow = 2^(24+overflow_bits)/freq` Therefore, with the frequency 32.768 KHz and the overflow
-counter being u8, the RTC would overflow after about 36.5 hours.
+[`MonotonicTimer64`] extends the TIMER counter to 64 bits in software, which is preferable for
+fast timers: at 16 MHz it takes over 36 000 years to overflow.
+
+The RTC uses a 24-bit wide counter, which [`MonotonicRtc`] extends to the 32-bit instant in
+software. The time to overflow can be calculated using: `T_overflow = 2^32/freq`. Therefore, with
+the frequency 32.768 KHz, the RTC instant would overflow after about 36.4 hours.
 **/
 use crate::clocks::{Clocks, LfOscStarted};
 use core::marker::PhantomData;
+use core::sync::atomic::{compiler_fence, Ordering};
 pub use rtic_monotonic::Monotonic;
 
 #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
@@ -149,10 +153,26 @@ pub enum Error {
 /// using the [`Rtc`](crate::rtc::Rtc) (§6.22 in the
 /// [data sheet](https://infocenter.nordicsemi.com/pdf/nRF52840_PS_v1.1.pdf)) peripheral. It is only
 /// possible to instantiate this abstraction with frequencies using an integer prescaler between 0
-/// and 4095.
+/// and 4095, i.e. frequencies dividing 32 768 Hz down to 8 Hz. Other frequencies fail to compile.
+///
+/// The frequency trades resolution for range. The 32-bit instant overflows after `2^32 / FREQ`
+/// seconds, and delays up to half of that can be scheduled:
+///
+///| frequency \[Hz\]         | resolution                   | time until overflow              |
+///|--------------------------|------------------------------|----------------------------------|
+///| <center> 32768 </center> | <center> 30.5 µs </center>   | <center> 36 hours 24 min </center> |
+///| <center> 16384 </center> | <center> 61.0 µs </center>   | <center> 3 days 49 min </center> |
+///| <center> 1024 </center>  | <center> 977 µs </center>    | <center> 48 days 13 hours </center> |
+///| <center> 8 </center>     | <center> 125 ms </center>    | <center> 17 years </center>      |
+///
+/// CC\[0\] holds the next scheduled instant. The 24-bit counter is extended in software by
+/// counting half periods, using the `OVRFLW` event and CC\[1\] at the middle of the counter range,
+/// like [`ExtendedRtc`](crate::rtc::ExtendedRtc). This is free of races as long as the interrupt
+/// is handled within half a counter period, so the RTC interrupt stays enabled while the queue is
+/// empty.
 pub struct MonotonicRtc<T: RtcInstance, const FREQ: u32> {
     instance: PhantomData<T>,
-    overflow: u8,
+    period: u32,
 }
 
 impl<T, const FREQ: u32> MonotonicRtc<T, FREQ>
@@ -161,29 +181,44 @@ where
 {
     const MAX_PRESCALER: u32 = 4096;
 
+    /// The prescaler giving `FREQ`, computed at compile time.
+    ///
+    /// Referencing it with a frequency that cannot be derived from the 32 768 Hz clock fails to
+    /// compile.
+    const PRESCALER: u32 = match Self::prescaler() {
+        Ok(presc) => presc,
+        Err(Error::TooLargePrescaler(_)) => panic!("the RTC frequency is below 8 Hz"),
+        Err(Error::InvalidFrequency(_)) => panic!("the RTC frequency does not divide 32 768 Hz"),
+    };
+
     /// Instantiates a new [`Monotonic`](rtic_monotonic) RTC for the specified [`RtcInstance`].
     ///
-    /// This function permits construction of the `MonotonicRtc` for a given frequency.
+    /// This function permits construction of the `MonotonicRtc` for a given frequency. An invalid
+    /// frequency is rejected at compile time, so this never returns an error; the `Result` is
+    /// kept for compatibility.
     pub fn new<H, L>(_: T, _: &Clocks<H, L, LfOscStarted>) -> Result<Self, Error> {
-        let presc = Self::prescaler()?;
-        unsafe { T::reg().prescaler.write(|w| w.bits(presc)) };
+        unsafe { T::reg().prescaler.write(|w| w.bits(Self::PRESCALER)) };
 
         Ok(Self {
             instance: PhantomData,
-            overflow: 0,
+            period: 0,
         })
     }
 
     /// Checks if the given frequency is valid.
     const fn prescaler() -> Result<u32, Error> {
+        if FREQ == 0 || FREQ > 32_768 {
+            return Err(Error::InvalidFrequency(FREQ));
+        }
+
         let intermediate: u32 = 32_768 / FREQ;
-        let presc: u32 = (32_768 / FREQ) - 1;
+        let presc: u32 = intermediate - 1;
 
         if presc >= Self::MAX_PRESCALER {
             return Err(Error::TooLargePrescaler(FREQ));
         }
 
-        match 32_768 / intermediate == FREQ && presc < Self::MAX_PRESCALER {
+        match 32_768 / intermediate == FREQ && 32_768 % FREQ == 0 {
             true => Ok(presc),
             _ => Err(Error::InvalidFrequency(FREQ)),
         }
@@ -199,16 +234,13 @@ impl<T: RtcInstance, const FREQ: u32> Monotonic for MonotonicRtc<T, FREQ> {
     const DISABLE_INTERRUPT_ON_EMPTY_QUEUE: bool = false;
 
     fn now(&mut self) -> Self::Instant {
-        let rtcreg = T::reg();
-        let cnt = rtcreg.counter.read().bits();
+        let period = self.period;
+        compiler_fence(Ordering::Acquire);
+        let counter = T::reg().counter.read().bits();
 
-        let ovf = (if rtcreg.events_ovrflw.read().bits() == 1 {
-            self.overflow.wrapping_add(1)
-        } else {
-            self.overflow
-        }) as u32;
-
-        fugit::TimerInstantU32::<FREQ>::from_ticks((ovf << 24) | cnt)
+        // Only the lower 32 bits of the extended counter fit in the instant.
+        let ticks = crate::rtc::extend_counter(period, counter) as u32;
+        fugit::TimerInstantU32::<FREQ>::from_ticks(ticks)
     }
 
     fn set_compare(&mut self, instant: Self::Instant) {
@@ -240,10 +272,21 @@ impl<T: RtcInstance, const FREQ: u32> Monotonic for MonotonicRtc<T, FREQ> {
         }
     }
 
+    fn on_interrupt(&mut self) {
+        self.period = crate::rtc::advance_period(T::reg(), 1, self.period);
+        compiler_fence(Ordering::Release);
+    }
+
     unsafe fn reset(&mut self) {
         let rtc = T::reg();
-        rtc.intenset.write(|w| w.compare0().set().ovrflw().set());
-        rtc.evtenset.write(|w| w.compare0().set().ovrflw().set());
+        self.period = 0;
+        rtc.cc[1].write(|w| w.bits(0x80_0000));
+        rtc.events_ovrflw.write(|w| w.bits(0));
+        rtc.events_compare[1].write(|w| w.bits(0));
+        rtc.intenset
+            .write(|w| w.compare0().set().compare1().set().ovrflw().set());
+        rtc.evtenset
+            .write(|w| w.compare0().set().compare1().set().ovrflw().set());
 
         rtc.tasks_clear.write(|w| w.bits(1));
         rtc.tasks_start.write(|w| w.bits(1));
@@ -321,6 +364,98 @@ impl<T: TimerInstance, const FREQ: u32> Monotonic for MonotonicTimer<T, FREQ> {
     }
 }
 
+/// A [`Monotonic`] timer implementation with a 64-bit counter.
+///
+/// Works like [`MonotonicTimer`], but extends the 32-bit TIMER counter to 64 bits in software so
+/// it never overflows in practice, even at 16 MHz. The extension uses two compare channels at
+/// the start and the middle of the 32-bit range: each of their events advances a period
+/// counter, and [`Monotonic::now`] combines the period with the hardware counter. This is free
+/// of races as long as the interrupt is handled within half a 32-bit period.
+///
+/// CC\[0\] holds the next scheduled instant, CC\[1\] is used to capture the counter and
+/// CC\[2\]/CC\[3\] track the periods. The TIMER interrupt therefore stays enabled while the
+/// queue is empty.
+pub struct MonotonicTimer64<T: TimerInstance, const FREQ: u32> {
+    instance: PhantomData<T>,
+    period: u32,
+}
+
+impl<T: TimerInstance, const FREQ: u32> MonotonicTimer64<T, FREQ> {
+    fn internal_new<const PRESC: u8>() -> Self {
+        let reg = T::reg();
+        reg.prescaler
+            .write(|w| unsafe { w.prescaler().bits(PRESC) });
+        reg.bitmode.write(|w| w.bitmode()._32bit());
+        reg.mode.write(|w| w.mode().timer());
+        reg.cc[2].write(|w| unsafe { w.bits(0) });
+        reg.cc[3].write(|w| unsafe { w.bits(0x8000_0000) });
+        Self {
+            instance: PhantomData,
+            period: 0,
+        }
+    }
+}
+
+impl<T: TimerInstance, const FREQ: u32> Monotonic for MonotonicTimer64<T, FREQ> {
+    type Instant = fugit::TimerInstantU64<FREQ>;
+    type Duration = fugit::TimerDurationU64<FREQ>;
+
+    // The period events have to be handled even without scheduled tasks.
+    const DISABLE_INTERRUPT_ON_EMPTY_QUEUE: bool = false;
+
+    fn now(&mut self) -> Self::Instant {
+        let reg: &TimerRegBlock = T::reg();
+        let period = self.period;
+        compiler_fence(Ordering::Acquire);
+        reg.tasks_capture[1].write(|w| unsafe { w.bits(1) });
+        let counter = reg.cc[1].read().bits();
+
+        // In odd periods the counter is expected in the upper half of its range. Flipping the
+        // top bit makes a counter that already wrapped, with the event not yet handled, count
+        // as the continuation of the current period.
+        let ticks = (u64::from(period) << 31) + u64::from(counter ^ ((period & 1) << 31));
+        Self::Instant::from_ticks(ticks)
+    }
+
+    fn set_compare(&mut self, instant: Self::Instant) {
+        // Only the lower 32 bits can be compared in hardware. An instant further away than
+        // that may fire early, which is harmless since the queue checks `now` again.
+        let ticks = instant.duration_since_epoch().ticks() as u32;
+        T::reg().cc[0].write(|w| unsafe { w.bits(ticks) });
+    }
+
+    fn clear_compare_flag(&mut self) {
+        T::reg().events_compare[0].write(|w| unsafe { w.bits(0) });
+    }
+
+    fn on_interrupt(&mut self) {
+        let reg = T::reg();
+        for idx in [2, 3] {
+            if reg.events_compare[idx].read().bits() != 0 {
+                reg.events_compare[idx].write(|w| unsafe { w.bits(0) });
+                self.period = self.period.wrapping_add(1);
+            }
+        }
+        compiler_fence(Ordering::Release);
+    }
+
+    fn zero() -> Self::Instant {
+        Self::Instant::from_ticks(0)
+    }
+
+    unsafe fn reset(&mut self) {
+        let reg = T::reg();
+        self.period = 0;
+        for event in reg.events_compare.iter() {
+            event.write(|w| w.bits(0));
+        }
+        reg.intenset
+            .write(|w| w.compare0().set().compare2().set().compare3().set());
+        reg.tasks_clear.write(|w| w.bits(1));
+        reg.tasks_start.write(|w| w.bits(1));
+    }
+}
+
 macro_rules! impl_instance {
     (TimerRegBlock,$peripheral:ident) => {
         impl TimerInstance for $peripheral {
@@ -399,6 +534,15 @@ macro_rules! freq_gate {
                     Self::internal_new::<$presc>()
                 }
             }
+
+            impl<T:TimerInstance> MonotonicTimer64<T,$freq>
+            {
+                /// Instantiates a new [`Monotonic`] enabled
+                /// timer for the specified [`TimerInstance`]
+                pub fn new(_: T) -> Self {
+                    Self::internal_new::<$presc>()
+                }
+            }
         )+
     )
 }
@@ -18,7 +18,10 @@ use embedded_storage::nor_flash::{
 
 type WORD = u32;
 const WORD_SIZE: usize = core::mem::size_of::<WORD>();
+#[cfg(not(feature = "5340-net"))]
 const PAGE_SIZE: usize = 4 * 1024;
+#[cfg(feature = "5340-net")]
+const PAGE_SIZE: usize = 2 * 1024;
 
 /// Interface to an NVMC instance.
 pub struct Nvmc<T: Instance> {
@@ -32,13 +35,32 @@ where
 {
     /// Takes ownership of the peripheral and storage area.
     ///
-    /// The storage area must be page-aligned.
+    /// The storage area must be page-aligned and located in flash. It should be reserved in the
+    /// linker script, so that it can't overlap the program, e.g. by shrinking the `FLASH` region
+    /// in `memory.x` and placing the storage after it.
     pub fn new(nvmc: T, storage: &'static mut [u8]) -> Nvmc<T> {
         assert!(storage.as_ptr() as usize % PAGE_SIZE == 0);
         assert!(storage.len() % PAGE_SIZE == 0);
+        #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+        assert!(storage.as_ptr() as usize + storage.len() <= flash_size());
         Self { nvmc, storage }
     }
 
+    /// Erases the page starting at byte `offset` of the storage area.
+    pub fn erase_page(&mut self, offset: u32) -> Result<(), NvmcError> {
+        self.erase(offset, offset.saturating_add(PAGE_SIZE as u32))
+    }
+
+    /// Writes `words` starting at byte `offset` of the storage area, which must be word-aligned.
+    ///
+    /// Flash writes can only clear bits, so the target area should have been erased.
+    pub fn write_words(&mut self, offset: u32, words: &[u32]) -> Result<(), NvmcError> {
+        let bytes = unsafe {
+            core::slice::from_raw_parts(words.as_ptr() as *const u8, words.len() * WORD_SIZE)
+        };
+        self.write(offset, bytes)
+    }
+
     /// Consumes `self` and returns back the raw peripheral and associated storage.
     pub fn free(self) -> (T, &'static mut [u8]) {
         (self.nvmc, self.storage)
@@ -78,7 +100,7 @@ where
 
     #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
     #[inline]
-    fn erase_page(&mut self, page_offset: usize) {
+    fn erase_page_at(&mut self, page_offset: usize) {
         let bits = &mut (self.storage[page_offset * PAGE_SIZE]) as *mut _ as u32;
         self.nvmc.erasepage().write(|w| unsafe { w.bits(bits) });
         self.wait_ready();
@@ -86,7 +108,7 @@ where
 
     #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
     #[inline]
-    fn erase_page(&mut self, page_offset: usize) {
+    fn erase_page_at(&mut self, page_offset: usize) {
         self.direct_write_word(page_offset * PAGE_SIZE / WORD_SIZE, 0xffffffff);
         self.wait_ready();
     }
@@ -135,6 +157,11 @@ where
     }
 }
 
+/// Erases whole pages of 4 KiB (2 KiB on the nRF5340 network core) and writes whole words.
+///
+/// On chips that implement `MultiwriteNorFlash`, writing to a word that has been written before
+/// stores the AND of the old and new data, as that trait promises. On the others, a write that
+/// would have to set a cleared bit fails with [`NvmcError::NotErased`] instead.
 impl<T> NorFlash for Nvmc<T>
 where
     T: Instance,
@@ -154,7 +181,7 @@ where
         let (page_from, page_to) = (from / PAGE_SIZE, to / PAGE_SIZE);
         self.enable_erase();
         for page_offset in page_from..page_to {
-            self.erase_page(page_offset);
+            self.erase_page_at(page_offset);
         }
         self.enable_read();
         Ok(())
@@ -168,11 +195,28 @@ where
         if offset % WORD_SIZE != 0 || bytes.len() % WORD_SIZE != 0 {
             return Err(NvmcError::Unaligned);
         }
+        // Writes can only clear bits, setting them requires an erase.
+        #[cfg(not(any(
+            feature = "52805",
+            feature = "52810",
+            feature = "52811",
+            feature = "52833",
+            feature = "52840",
+            feature = "9160",
+        )))]
+        {
+            self.wait_ready();
+            let target = &self.storage[offset..][..bytes.len()];
+            if target.iter().zip(bytes).any(|(old, new)| old & new != *new) {
+                return Err(NvmcError::NotErased);
+            }
+        }
         let word_offset = offset / WORD_SIZE;
         self.enable_write();
         for (word_offset, bytes) in (word_offset..).zip(bytes.chunks_exact(WORD_SIZE)) {
             self.write_word(word_offset, u32::from_ne_bytes(bytes.try_into().unwrap()));
         }
+        self.wait_ready();
         self.enable_read();
         Ok(())
     }
@@ -195,6 +239,13 @@ where
 ))]
 impl<T: Instance> embedded_storage::nor_flash::MultiwriteNorFlash for Nvmc<T> {}
 
+/// Size of the code flash, from the FICR.
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+fn flash_size() -> usize {
+    let ficr = unsafe { &*crate::pac::FICR::ptr() };
+    ficr.codepagesize.read().bits() as usize * ficr.codesize.read().bits() as usize
+}
+
 pub trait Instance: Deref<Target = nvmc::RegisterBlock> + sealed::Sealed {}
 
 impl Instance for NVMC {}
@@ -213,6 +264,10 @@ pub enum NvmcError {
     Unaligned,
     /// An operation was attempted outside the boundaries
     OutOfBounds,
+    /// A write would have to set bits that are cleared, without erasing the page first.
+    ///
+    /// Only reported on chips that don't implement `MultiwriteNorFlash`.
+    NotErased,
 }
 
 impl NorFlashError for NvmcError {
@@ -220,6 +275,7 @@ impl NorFlashError for NvmcError {
         match self {
             NvmcError::Unaligned => NorFlashErrorKind::NotAligned,
             NvmcError::OutOfBounds => NorFlashErrorKind::OutOfBounds,
+            NvmcError::NotErased => NorFlashErrorKind::Other,
         }
     }
 }
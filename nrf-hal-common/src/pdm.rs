@@ -0,0 +1,378 @@
+//! HAL interface for the PDM peripheral.
+//!
+//! The Pulse Density Modulation (PDM) interface reads the signal of one or two external digital
+//! microphones and decimates it into 16-bit PCM samples, which are transferred to RAM with
+//! EasyDMA. In stereo mode, the samples are interleaved, left channel first.
+//!
+//! The output sample rate is the PDM clock frequency divided by the decimation ratio, e.g.
+//! 1.032 MHz / 64 = 16.125 kHz with the default settings.
+//!
+//! Note that the decimation filter needs some time to settle after the PDM has been started, so
+//! the first samples after [`Pdm::read`] or [`ContinuousPdm::start`] should be discarded.
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::{gpio::{p0::Parts, Level}, pac::Peripherals};
+//! # use nrf_hal_common::pdm::{Channels, Pdm, Pins};
+//! let p = Peripherals::take().unwrap();
+//! let port0 = Parts::new(p.P0);
+//! let pins = Pins {
+//!     clk: port0.p0_26.into_push_pull_output(Level::Low).degrade(),
+//!     din: port0.p0_25.into_floating_input().degrade(),
+//! };
+//! let mut pdm = Pdm::new(p.PDM, pins);
+//! pdm.channels(Channels::Mono).gain(6, 6);
+//!
+//! let mut samples = [0i16; 256];
+//! pdm.read(&mut samples).unwrap();
+//! ```
+
+#[cfg(not(any(feature = "5340-app", feature = "9160")))]
+use crate::pac::PDM as PDM_PAC;
+#[cfg(feature = "5340-app")]
+use crate::pac::PDM0_NS as PDM_PAC;
+#[cfg(feature = "9160")]
+use crate::pac::PDM_NS as PDM_PAC;
+use crate::{
+    gpio::{Floating, Input, Output, Pin, PushPull},
+    slice_in_ram,
+};
+use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+
+/// Maximum number of samples a single EasyDMA transfer can hold.
+pub const MAX_SAMPLES: usize = (1 << 15) - 1;
+
+/// Gain limit, in 0.5 dB steps.
+const MAX_GAIN: i8 = 40;
+
+/// A safe wrapper around the `PDM` peripheral with associated pins.
+pub struct Pdm {
+    pdm: PDM_PAC,
+    pins: Pins,
+}
+
+impl Pdm {
+    /// Takes ownership of the `PDM` peripheral and associated pins, returning a safe wrapper.
+    ///
+    /// The PDM starts out in mono mode with the default clock frequency and 0 dB gain.
+    pub fn new(pdm: PDM_PAC, pins: Pins) -> Self {
+        pdm.psel.clk.write(|w| {
+            unsafe { w.bits(pins.clk.psel_bits()) };
+            w.connect().connected()
+        });
+        pdm.psel.din.write(|w| {
+            unsafe { w.bits(pins.din.psel_bits()) };
+            w.connect().connected()
+        });
+        pdm.mode
+            .write(|w| w.operation().mono().edge().left_falling());
+        pdm.pdmclkctrl.write(|w| w.freq().default());
+        pdm.gainl.write(|w| w.gainl().default_gain());
+        pdm.gainr.write(|w| w.gainr().default_gain());
+        pdm.enable.write(|w| w.enable().enabled());
+
+        Self { pdm, pins }
+    }
+
+    /// Selects mono or stereo operation.
+    #[inline(always)]
+    pub fn channels(&self, channels: Channels) -> &Self {
+        self.pdm.mode.modify(|_, w| match channels {
+            Channels::Mono => w.operation().mono(),
+            Channels::Stereo => w.operation().stereo(),
+        });
+        self
+    }
+
+    /// Selects the clock edge the left (or mono) channel is sampled on.
+    #[inline(always)]
+    pub fn edge(&self, edge: Edge) -> &Self {
+        self.pdm.mode.modify(|_, w| match edge {
+            Edge::LeftFalling => w.edge().left_falling(),
+            Edge::LeftRising => w.edge().left_rising(),
+        });
+        self
+    }
+
+    /// Sets the PDM clock frequency, which determines the sample rate.
+    #[inline(always)]
+    pub fn frequency(&self, frequency: Frequency) -> &Self {
+        self.pdm.pdmclkctrl.write(|w| match frequency {
+            Frequency::_1000K => w.freq()._1000k(),
+            Frequency::Default => w.freq().default(),
+            Frequency::_1067K => w.freq()._1067k(),
+            #[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+            Frequency::_1231K => w.freq()._1231k(),
+            #[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+            Frequency::_1280K => w.freq()._1280k(),
+            #[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+            Frequency::_1333K => w.freq()._1333k(),
+        });
+        self
+    }
+
+    /// Sets the ratio between the PDM clock frequency and the output sample rate.
+    #[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+    #[inline(always)]
+    pub fn ratio(&self, ratio: Ratio) -> &Self {
+        self.pdm.ratio.write(|w| match ratio {
+            Ratio::_64 => w.ratio().ratio64(),
+            Ratio::_80 => w.ratio().ratio80(),
+        });
+        self
+    }
+
+    /// Sets the gain of the left and right channels in 0.5 dB steps, from -20 dB (`-40`) to
+    /// +20 dB (`40`). Values out of range are clamped.
+    ///
+    /// In mono mode, only the left gain is used.
+    #[inline(always)]
+    pub fn gain(&self, left: i8, right: i8) -> &Self {
+        let to_bits = |gain: i8| (gain.clamp(-MAX_GAIN, MAX_GAIN) + MAX_GAIN) as u8;
+        self.pdm
+            .gainl
+            .write(|w| unsafe { w.gainl().bits(to_bits(left)) });
+        self.pdm
+            .gainr
+            .write(|w| unsafe { w.gainr().bits(to_bits(right)) });
+        self
+    }
+
+    /// Fills `buffer` with samples, blocking until it is full.
+    ///
+    /// In stereo mode, the buffer holds interleaved left and right samples.
+    pub fn read(&mut self, buffer: &mut [i16]) -> Result<(), Error> {
+        if buffer.len() > MAX_SAMPLES {
+            return Err(Error::BufferTooLong);
+        }
+        if buffer.is_empty() {
+            return Ok(());
+        }
+        if !slice_in_ram(as_bytes(buffer)) {
+            return Err(Error::BufferNotInDataMemory);
+        }
+
+        self.pdm.events_started.reset();
+        self.pdm.events_end.reset();
+        self.set_buffer(buffer.as_mut_ptr(), buffer.len());
+
+        // Conservative compiler fence to prevent starting the transfer before the
+        // pointer and maxcount have been set.
+        compiler_fence(SeqCst);
+
+        self.pdm.tasks_start.write(|w| unsafe { w.bits(1) });
+        while self.pdm.events_end.read().bits() == 0 {}
+
+        // The PDM keeps sampling into the same buffer until it is stopped.
+        self.stop();
+
+        // Prevent optimizations creating issues with the EasyDMA-modified buffer.
+        compiler_fence(SeqCst);
+
+        Ok(())
+    }
+
+    /// Turns the PDM into a continuous acquisition that alternates between `first` and
+    /// `second`.
+    pub fn into_continuous<const N: usize>(
+        self,
+        first: &'static mut [i16; N],
+        second: &'static mut [i16; N],
+    ) -> ContinuousPdm<N> {
+        let () = ContinuousPdm::<N>::VALID;
+        ContinuousPdm {
+            pdm: self,
+            buffers: [first, second],
+            filling: 0,
+            ready: None,
+        }
+    }
+
+    /// Disables the PDM, disconnects the pins and returns the low-level peripheral handle and
+    /// the pins.
+    pub fn free(self) -> (PDM_PAC, Pins) {
+        self.pdm.enable.write(|w| w.enable().disabled());
+        self.pdm.psel.clk.reset();
+        self.pdm.psel.din.reset();
+        (self.pdm, self.pins)
+    }
+
+    fn set_buffer(&self, ptr: *mut i16, len: usize) {
+        self.pdm
+            .sample
+            .ptr
+            .write(|w| unsafe { w.sampleptr().bits(ptr as u32) });
+        self.pdm
+            .sample
+            .maxcnt
+            .write(|w| unsafe { w.buffsize().bits(len as u16) });
+    }
+
+    fn stop(&self) {
+        self.pdm.events_stopped.reset();
+        self.pdm.tasks_stop.write(|w| unsafe { w.bits(1) });
+        while self.pdm.events_stopped.read().bits() == 0 {}
+        self.pdm.events_stopped.reset();
+        self.pdm.events_started.reset();
+        self.pdm.events_end.reset();
+    }
+}
+
+/// Double-buffered PDM acquisition for gap-free continuous capture.
+///
+/// EasyDMA fills one buffer while the CPU processes the other. The SAMPLE.PTR register is
+/// double-buffered: once a buffer has STARTED, the pointer for the next one can be set, and the
+/// PDM continues into it without a gap after the END event. [`ContinuousPdm::on_interrupt`]
+/// swaps the pointer on every STARTED event and marks the buffer as ready on every END event.
+///
+/// `N` has to be between 1 and [`MAX_SAMPLES`].
+pub struct ContinuousPdm<const N: usize> {
+    pdm: Pdm,
+    buffers: [&'static mut [i16; N]; 2],
+    /// Index of the buffer EasyDMA is currently writing to.
+    filling: usize,
+    /// Index of the last completed buffer, if it hasn't been fetched yet.
+    ready: Option<usize>,
+}
+
+impl<const N: usize> ContinuousPdm<N> {
+    const VALID: () = assert!(
+        N >= 1 && N <= MAX_SAMPLES,
+        "PDM buffers hold 1 to 32767 samples"
+    );
+
+    /// Starts filling the first buffer.
+    ///
+    /// Note that the interrupt also has to be unmasked in the NVIC, or
+    /// [`ContinuousPdm::on_interrupt`] won't get called.
+    pub fn start(&mut self) {
+        self.filling = 0;
+        self.ready = None;
+        self.set_ptr(0);
+
+        let pdm = &self.pdm.pdm;
+        pdm.events_started.reset();
+        pdm.events_end.reset();
+        pdm.intenset.write(|w| w.started().set().end().set());
+
+        // Conservative compiler fence to prevent starting the transfer before the
+        // pointer and maxcount have been set.
+        compiler_fence(SeqCst);
+
+        pdm.tasks_start.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Stops the acquisition. Samples of an incomplete buffer are lost.
+    pub fn stop(&mut self) {
+        self.pdm
+            .pdm
+            .intenclr
+            .write(|w| w.started().clear().end().clear());
+        self.pdm.stop();
+    }
+
+    /// Swaps the buffers. Call this from the interrupt handler of the PDM.
+    pub fn on_interrupt(&mut self) {
+        let pdm = &self.pdm.pdm;
+
+        if pdm.events_end.read().bits() != 0 {
+            pdm.events_end.reset();
+            self.ready = Some(self.filling);
+            // SAMPLE.PTR already points to the other buffer since the STARTED event.
+            self.filling ^= 1;
+        }
+
+        if pdm.events_started.read().bits() != 0 {
+            pdm.events_started.reset();
+            self.set_ptr(self.filling ^ 1);
+        }
+    }
+
+    /// Returns the buffer completed last, once.
+    ///
+    /// The buffer stays valid until the other one is complete, i.e. for `N` sample periods.
+    pub fn get_ready_buffer(&mut self) -> Option<&[i16; N]> {
+        let ready = self.ready.take()?;
+        // Prevent optimizations creating issues with the EasyDMA-modified buffer.
+        compiler_fence(SeqCst);
+        Some(&*self.buffers[ready])
+    }
+
+    /// Stops the acquisition and returns the PDM and the buffers.
+    pub fn free(mut self) -> (Pdm, [&'static mut [i16; N]; 2]) {
+        self.stop();
+        (self.pdm, self.buffers)
+    }
+
+    fn set_ptr(&mut self, index: usize) {
+        let ptr = self.buffers[index].as_mut_ptr();
+        self.pdm.set_buffer(ptr, N);
+    }
+}
+
+fn as_bytes(buffer: &[i16]) -> &[u8] {
+    unsafe { core::slice::from_raw_parts(buffer.as_ptr() as *const u8, buffer.len() * 2) }
+}
+
+/// Pins for the PDM.
+pub struct Pins {
+    /// Clock output to the microphones.
+    pub clk: Pin<Output<PushPull>>,
+    /// Data input from the microphones.
+    pub din: Pin<Input<Floating>>,
+}
+
+/// Mono or stereo operation.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Channels {
+    /// A single microphone, sampled on the left edge.
+    Mono,
+    /// Two microphones sharing the data line, sampled on alternating clock edges.
+    Stereo,
+}
+
+/// Clock edge the left (or mono) channel is sampled on.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Edge {
+    /// Left channel on the falling edge, right channel on the rising edge.
+    LeftFalling,
+    /// Left channel on the rising edge, right channel on the falling edge.
+    LeftRising,
+}
+
+/// PDM clock frequency.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Frequency {
+    /// 1.000 MHz.
+    _1000K,
+    /// 1.032 MHz, the reset value.
+    Default,
+    /// 1.067 MHz.
+    _1067K,
+    /// 1.231 MHz.
+    #[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+    _1231K,
+    /// 1.280 MHz.
+    #[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+    _1280K,
+    /// 1.333 MHz.
+    #[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+    _1333K,
+}
+
+/// Ratio between the PDM clock frequency and the output sample rate.
+#[cfg(not(any(feature = "52810", feature = "52811", feature = "52832")))]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Ratio {
+    /// Decimation by 64, the reset value.
+    _64,
+    /// Decimation by 80.
+    _80,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    BufferTooLong,
+    BufferNotInDataMemory,
+}
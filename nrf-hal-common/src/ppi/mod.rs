@@ -45,12 +45,14 @@ mod sealed {
         const CH: usize;
     }
 
+    /// Implemented by task registers that can be used as a PPI task endpoint.
     pub trait Task {
         #[inline(always)]
         fn task_addr(&self) -> TaskAddr {
             TaskAddr(self as *const _ as *const u32 as u32)
         }
     }
+    /// Implemented by event registers that can be used as a PPI event endpoint.
     pub trait Event {
         #[inline(always)]
         fn event_addr(&self) -> EventAddr {
@@ -83,7 +85,25 @@ pub trait Ppi: PpiSealed {
     #[cfg(not(feature = "51"))]
     /// Sets the fork task that must be triggered when the configured event occurs. The user must
     /// provide a reference to the task.
-    fn set_fork_task_endpoint<T: Task>(&mut self, task: &T);
+    ///
+    /// This lets a single event drive two tasks, and is also available on fixed channels.
+    ///
+    #[cfg_attr(feature = "52840", doc = "```no_run")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common::{gpiote::Gpiote, pac, ppi::{self, ConfigurablePpi, Ppi}, timer::Timer};
+    /// let p = pac::Peripherals::take().unwrap();
+    /// let gpiote = Gpiote::new(p.GPIOTE);
+    /// let timer = Timer::new(p.TIMER0);
+    /// let mut ppi = ppi::Parts::new(p.PPI);
+    ///
+    /// // On every GPIOTE channel 0 event, start the timer and toggle the pin of channel 1.
+    /// ppi.ppi0
+    ///     .set_event_endpoint(gpiote.channel0().event())
+    ///     .set_task_endpoint(timer.task_start())
+    ///     .set_fork_task_endpoint(gpiote.channel1().task_out())
+    ///     .enable();
+    /// ```
+    fn set_fork_task_endpoint<T: Task>(&mut self, task: &T) -> &mut Self;
 
     #[cfg(not(feature = "51"))]
     /// Clear the fork task endpoint. Previously set task will no longer be triggered.
@@ -91,17 +111,65 @@ pub trait Ppi: PpiSealed {
 }
 
 /// Traits that extends the [Ppi](trait.Ppi.html) trait, marking a channel as fully configurable.
+///
+/// Only task registers can be passed as task endpoints and only event registers as event
+/// endpoints, so a mismatched wiring is rejected at compile time. Both setters return the channel,
+/// allowing the configuration to be chained:
+///
+#[cfg_attr(feature = "52840", doc = "```no_run")]
+#[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+/// # use nrf_hal_common::{pac, ppi::{self, ConfigurablePpi, Ppi}, timer::Timer};
+/// let p = pac::Peripherals::take().unwrap();
+/// let timer = Timer::new(p.TIMER0);
+/// let mut ppi = ppi::Parts::new(p.PPI);
+///
+/// // Stop the radio every time the timer reaches CC[0].
+/// ppi.ppi0
+///     .set_event_endpoint(timer.event_compare_cc0())
+///     .set_task_endpoint(&p.RADIO.tasks_disable)
+///     .enable();
+/// ```
 pub trait ConfigurablePpi: Ppi {
     /// Sets the task that must be triggered when the configured event occurs. The user must provide
     /// a reference to the task.
-    fn set_task_endpoint<T: Task>(&mut self, task: &T);
+    fn set_task_endpoint<T: Task>(&mut self, task: &T) -> &mut Self;
 
     /// Sets the event that will trigger the chosen task(s). The user must provide a reference to
     /// the event.
-    fn set_event_endpoint<E: Event>(&mut self, event: &E);
+    fn set_event_endpoint<E: Event>(&mut self, event: &E) -> &mut Self;
 }
 
 /// Trait for a PPI channel group.
+///
+/// A channel group enables or disables all of its channels at once. Its enable and disable tasks
+/// can themselves be used as PPI task endpoints, so a single event can arm or disarm a whole
+/// signal chain:
+///
+#[cfg_attr(feature = "52840", doc = "```no_run")]
+#[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+/// # use nrf_hal_common::{gpiote::Gpiote, pac, ppi::{self, ConfigurablePpi, Ppi, PpiChannelGroup}, timer::Timer};
+/// let p = pac::Peripherals::take().unwrap();
+/// let gpiote = Gpiote::new(p.GPIOTE);
+/// let timer = Timer::periodic(p.TIMER0);
+/// let mut ppi = ppi::Parts::new(p.PPI);
+///
+/// // Sample the SAADC every time the timer wraps around...
+/// ppi.ppi2
+///     .set_event_endpoint(timer.event_compare_cc0())
+///     .set_task_endpoint(&p.SAADC.tasks_sample);
+/// // ...but only while the channel group is enabled.
+/// ppi.chg0.add_channel(&ppi.ppi2);
+///
+/// // An event on GPIOTE channel 0 starts the sampling chain, one on channel 1 stops it.
+/// ppi.ppi0
+///     .set_event_endpoint(gpiote.channel0().event())
+///     .set_task_endpoint(ppi.chg0.task_enable())
+///     .enable();
+/// ppi.ppi1
+///     .set_event_endpoint(gpiote.channel1().event())
+///     .set_task_endpoint(ppi.chg0.task_disable())
+///     .enable();
+/// ```
 pub trait PpiChannelGroup: PpiChannelGroupSealed {
     /// Returns reference to `tasks_chg[x].en` endpoint for enabling channel group.
     fn task_enable(&self) -> &EN;
@@ -109,6 +177,10 @@ pub trait PpiChannelGroup: PpiChannelGroupSealed {
     fn task_disable(&self) -> &DIS;
     /// Sets bitmask for PPI channels which shall be included in this channel group.
     fn set_channels(&self, mask: u32);
+    /// Includes the given PPI channel in this channel group.
+    fn add_channel<P: Channel>(&self, channel: &P);
+    /// Removes the given PPI channel from this channel group.
+    fn remove_channel<P: Channel>(&self, channel: &P);
     /// Enables this channel group.
     fn enable(&self);
     /// Disables this channel group.
@@ -133,11 +205,12 @@ impl<P: Channel> Ppi for P {
 
     #[cfg(not(feature = "51"))]
     #[inline(always)]
-    fn set_fork_task_endpoint<T: Task>(&mut self, task: &T) {
+    fn set_fork_task_endpoint<T: Task>(&mut self, task: &T) -> &mut Self {
         let regs = unsafe { &*PPI::ptr() };
         regs.fork[P::CH]
             .tep
             .write(|w| unsafe { w.bits(task.task_addr().0) });
+        self
     }
 
     #[cfg(not(feature = "51"))]
@@ -152,19 +225,21 @@ impl<P: Channel> Ppi for P {
 // the type (guaranteed by the abstraction).
 impl<P: Channel + NotFixed> ConfigurablePpi for P {
     #[inline(always)]
-    fn set_task_endpoint<T: Task>(&mut self, task: &T) {
+    fn set_task_endpoint<T: Task>(&mut self, task: &T) -> &mut Self {
         let regs = unsafe { &*PPI::ptr() };
         regs.ch[P::CH]
             .tep
             .write(|w| unsafe { w.bits(task.task_addr().0) });
+        self
     }
 
     #[inline(always)]
-    fn set_event_endpoint<E: Event>(&mut self, event: &E) {
+    fn set_event_endpoint<E: Event>(&mut self, event: &E) -> &mut Self {
         let regs = unsafe { &*PPI::ptr() };
         regs.ch[P::CH]
             .eep
             .write(|w| unsafe { w.bits(event.event_addr().0) });
+        self
     }
 }
 
@@ -186,6 +261,16 @@ impl<G: ChannelGroup> PpiChannelGroup for G {
         regs.chg[Self::CHG].write(|w| unsafe { w.bits(mask) });
     }
     #[inline(always)]
+    fn add_channel<P: Channel>(&self, _channel: &P) {
+        let regs = unsafe { &*PPI::ptr() };
+        regs.chg[Self::CHG].modify(|r, w| unsafe { w.bits(r.bits() | 1 << P::CH) });
+    }
+    #[inline(always)]
+    fn remove_channel<P: Channel>(&self, _channel: &P) {
+        let regs = unsafe { &*PPI::ptr() };
+        regs.chg[Self::CHG].modify(|r, w| unsafe { w.bits(r.bits() & !(1 << P::CH)) });
+    }
+    #[inline(always)]
     fn enable(&self) {
         self.task_enable().write(|w| unsafe { w.bits(1) });
     }
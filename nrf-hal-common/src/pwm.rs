@@ -15,9 +15,12 @@ use crate::{
 };
 use core::{
     convert::Infallible,
+    future::poll_fn,
+    mem,
     ops::Deref,
     ptr::addr_of_mut,
     sync::atomic::{compiler_fence, Ordering},
+    task::Poll,
 };
 use embedded_dma::*;
 use embedded_hal::pwm::{ErrorType, SetDutyCycle};
@@ -505,6 +508,64 @@ where
         self.start_seq(Seq::Seq0).unwrap();
     }
 
+    /// Sets the duty cycles (15 bit) of all four PWM channels at once.
+    ///
+    /// The values are loaded in `Individual` mode as a single sequence step, so all channels
+    /// switch to their new duty cycle together at the start of the next PWM period. Each channel
+    /// keeps its current [`Polarity`], see [`set_polarity`](Self::set_polarity).
+    /// Will replace any ongoing sequence playback.
+    pub fn set_duties(&self, duties: [u16; 4]) {
+        let buffer = T::buffer();
+        let max_duty = self.max_duty();
+        unsafe {
+            for (word, duty) in (*buffer).iter_mut().zip(duties) {
+                *word = (*word & 0x8000) | (duty.min(max_duty) & 0x7FFF);
+            }
+        }
+        self.one_shot();
+        self.set_load_mode(LoadMode::Individual);
+        self.pwm
+            .seq0
+            .ptr
+            .write(|w| unsafe { w.bits(buffer as u32) });
+        self.pwm.seq0.cnt.write(|w| unsafe { w.bits(4) });
+        self.start_seq(Seq::Seq0).unwrap();
+    }
+
+    /// Sets the polarity of a PWM channel, keeping its 15 bit compare value.
+    ///
+    /// The polarity is bit 15 of each duty word in a sequence. [`set_duty_on`](Self::set_duty_on)
+    /// selects [`Polarity::FallingEdge`] and [`set_duty_off`](Self::set_duty_off) selects
+    /// [`Polarity::RisingEdge`].
+    /// Will replace any ongoing sequence playback.
+    pub fn set_polarity(&self, channel: Channel, polarity: Polarity) {
+        let buffer = T::buffer();
+        unsafe {
+            let word = &mut (*buffer)[usize::from(channel)];
+            *word = match polarity {
+                Polarity::FallingEdge => *word & 0x7FFF,
+                Polarity::RisingEdge => *word | 0x8000,
+            };
+        }
+        self.one_shot();
+        self.set_load_mode(LoadMode::Individual);
+        self.pwm
+            .seq0
+            .ptr
+            .write(|w| unsafe { w.bits(buffer as u32) });
+        self.pwm.seq0.cnt.write(|w| unsafe { w.bits(4) });
+        self.start_seq(Seq::Seq0).unwrap();
+    }
+
+    /// Returns the polarity of a PWM channel.
+    #[inline(always)]
+    pub fn polarity(&self, channel: Channel) -> Polarity {
+        match unsafe { (*T::buffer())[usize::from(channel)] } & 0x8000 {
+            0 => Polarity::FallingEdge,
+            _ => Polarity::RisingEdge,
+        }
+    }
+
     /// Returns the duty cycle value for a PWM channel.
     #[inline(always)]
     pub fn duty_on(&self, channel: Channel) -> u16 {
@@ -681,6 +742,120 @@ where
         })
     }
 
+    /// Plays `seq` from RAM and returns once playback has started.
+    ///
+    /// Each value is held for `refresh` additional PWM periods. The values are spread to the
+    /// channels according to the current [`LoadMode`], so the length of `seq` should be a
+    /// multiple of 4 in `Individual` and `Waveform` mode, and of 2 in `Grouped` mode.
+    ///
+    /// `mode` selects how often the whole buffer is played: once for [`Loop::Disabled`],
+    /// `n` times for [`Loop::Times`], or until stopped for [`Loop::Inf`]. Both sequence
+    /// registers are pointed at `seq`, so the hardware loop counter covers two playbacks and
+    /// odd counts start at `Seq1`. The end of playback is signalled by the
+    /// [`PwmEvent::LoopsDone`] event when looping, and by [`PwmEvent::SeqEnd`] of `Seq0` when
+    /// played once.
+    pub fn load_sequence(
+        &self,
+        seq: &'static [u16],
+        refresh: u32,
+        mode: Loop,
+    ) -> Result<(), Error> {
+        let (ptr, len) = (seq.as_ptr(), seq.len());
+        if (ptr as usize) < SRAM_LOWER || (ptr as usize) > SRAM_UPPER {
+            return Err(Error::DMABufferNotInDataMemory);
+        }
+        if len > MAX_SEQ_LEN {
+            return Err(Error::BufferTooLong);
+        }
+
+        compiler_fence(Ordering::SeqCst);
+        self.pwm.seq0.ptr.write(|w| unsafe { w.bits(ptr as u32) });
+        self.pwm.seq0.cnt.write(|w| unsafe { w.bits(len as u32) });
+        self.pwm.seq1.ptr.write(|w| unsafe { w.bits(ptr as u32) });
+        self.pwm.seq1.cnt.write(|w| unsafe { w.bits(len as u32) });
+        self.set_seq_refresh(Seq::Seq0, refresh);
+        self.set_seq_refresh(Seq::Seq1, refresh);
+        self.set_seq_end_delay(Seq::Seq0, 0);
+        self.set_seq_end_delay(Seq::Seq1, 0);
+        self.reset_event(PwmEvent::LoopsDone);
+
+        let start = match mode {
+            Loop::Disabled | Loop::Times(0) | Loop::Times(1) => {
+                self.set_loop(Loop::Disabled);
+                Seq::Seq0
+            }
+            Loop::Times(n) => {
+                self.set_loop(Loop::Times(n / 2 + (n & 1)));
+                if n & 1 == 1 {
+                    Seq::Seq1
+                } else {
+                    Seq::Seq0
+                }
+            }
+            Loop::Inf => {
+                self.set_loop(Loop::Inf);
+                Seq::Seq0
+            }
+        };
+        self.start_seq(start).unwrap();
+        Ok(())
+    }
+
+    /// Plays `seq` like [`load_sequence`](Self::load_sequence) and waits for playback to finish
+    /// without blocking.
+    ///
+    /// [`Pwm::on_interrupt`] has to be called from the interrupt handler of the PWM instance,
+    /// and the interrupt has to be unmasked in the NVIC. With [`Loop::Inf`] the future never
+    /// completes. Dropping the future stops playback.
+    pub async fn play_sequence_async(
+        &self,
+        seq: &'static [u16],
+        refresh: u32,
+        mode: Loop,
+    ) -> Result<(), Error> {
+        let end = match mode {
+            Loop::Disabled | Loop::Times(0) | Loop::Times(1) => PwmEvent::SeqEnd(Seq::Seq0),
+            _ => PwmEvent::LoopsDone,
+        };
+        self.load_sequence(seq, refresh, mode)?;
+
+        let stop = StopOnDrop(self);
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+            if self.is_event_triggered(end) {
+                Poll::Ready(())
+            } else {
+                // The interrupt handler disables the interrupt when it wakes the task.
+                self.enable_interrupt(end);
+                Poll::Pending
+            }
+        })
+        .await;
+        mem::forget(stop);
+
+        self.reset_event(end);
+        Ok(())
+    }
+
+    /// Wakes the task waiting for sequence playback of this PWM instance to finish.
+    ///
+    /// Call this from the interrupt handler of the PWM instance when using
+    /// [`Pwm::play_sequence_async`].
+    pub fn on_interrupt() {
+        let pwm = unsafe { &*T::PTR };
+        // Only events whose interrupt is enabled are awaited. SEQEND0 is also set on every
+        // playback of Seq0 in loop mode, and must not mask LOOPSDONE then.
+        let enabled = pwm.intenset.read();
+        if enabled.loopsdone().bit_is_set() && pwm.events_loopsdone.read().bits() != 0 {
+            pwm.intenclr.write(|w| w.loopsdone().clear());
+            T::waker().wake();
+        }
+        if enabled.seqend0().bit_is_set() && pwm.events_seqend[0].read().bits() != 0 {
+            pwm.intenclr.write(|w| w.seqend0().clear());
+            T::waker().wake();
+        }
+    }
+
     /// Enables interrupt triggering on the specified event.
     #[inline(always)]
     pub fn enable_interrupt(&self, event: PwmEvent) -> &Self {
@@ -868,6 +1043,18 @@ where
     }
 }
 
+/// Stops sequence playback that is still running when an async playback is cancelled.
+struct StopOnDrop<'a, T: Instance>(&'a Pwm<T>);
+
+impl<T: Instance> Drop for StopOnDrop<'_, T> {
+    fn drop(&mut self) {
+        self.0
+            .disable_interrupt(PwmEvent::LoopsDone)
+            .disable_interrupt(PwmEvent::SeqEnd(Seq::Seq0));
+        let _ = self.0.stop();
+    }
+}
+
 /// Pins for the Pwm
 pub struct Pins {
     /// Channel 0 pin, `None` if it was unused
@@ -1211,6 +1398,20 @@ impl From<CounterMode> for bool {
     }
 }
 
+/// Output polarity of a PWM channel, stored in bit 15 of each duty word.
+///
+/// In up counter mode, the lower 15 bits are the compare value at which the output changes
+/// level within the period. The names follow the product specification, and give the first edge
+/// of the output within the period.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Polarity {
+    /// Bit 15 set: the output is low until the compare value, then high.
+    RisingEdge,
+    /// Bit 15 clear: the output is high until the compare value, then low. This is the polarity
+    /// used by [`Pwm::set_duty_on`].
+    FallingEdge,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum Loop {
     Disabled,
@@ -1345,10 +1546,31 @@ impl Instance for crate::pac::PWM3_NS {
     }
 }
 mod sealed {
-    pub trait Sealed {}
+    use super::RegisterBlock;
+    use crate::waker::AtomicWaker;
+
+    pub trait Sealed {
+        const PTR: *const RegisterBlock;
+
+        /// The waker of the task waiting in `Pwm::play_sequence_async`.
+        fn waker() -> &'static AtomicWaker;
+    }
+
+    macro_rules! impl_sealed {
+        ($pwm:ty) => {
+            impl Sealed for $pwm {
+                const PTR: *const RegisterBlock = <$pwm>::PTR;
+
+                fn waker() -> &'static AtomicWaker {
+                    static WAKER: AtomicWaker = AtomicWaker::new();
+                    &WAKER
+                }
+            }
+        };
+    }
 
     #[cfg(not(any(feature = "5340-app", feature = "9160")))]
-    impl Sealed for crate::pac::PWM0 {}
+    impl_sealed!(crate::pac::PWM0);
 
     #[cfg(not(any(
         feature = "52810",
@@ -1356,7 +1578,7 @@ mod sealed {
         feature = "5340-app",
         feature = "9160"
     )))]
-    impl Sealed for crate::pac::PWM1 {}
+    impl_sealed!(crate::pac::PWM1);
 
     #[cfg(not(any(
         feature = "52810",
@@ -1364,7 +1586,7 @@ mod sealed {
         feature = "5340-app",
         feature = "9160"
     )))]
-    impl Sealed for crate::pac::PWM2 {}
+    impl_sealed!(crate::pac::PWM2);
 
     #[cfg(not(any(
         feature = "52810",
@@ -1373,17 +1595,17 @@ mod sealed {
         feature = "5340-app",
         feature = "9160"
     )))]
-    impl Sealed for crate::pac::PWM3 {}
+    impl_sealed!(crate::pac::PWM3);
 
     #[cfg(any(feature = "9160", feature = "5340-app"))]
-    impl Sealed for crate::pac::PWM0_NS {}
+    impl_sealed!(crate::pac::PWM0_NS);
 
     #[cfg(any(feature = "9160", feature = "5340-app"))]
-    impl Sealed for crate::pac::PWM1_NS {}
+    impl_sealed!(crate::pac::PWM1_NS);
 
     #[cfg(any(feature = "9160", feature = "5340-app"))]
-    impl Sealed for crate::pac::PWM2_NS {}
+    impl_sealed!(crate::pac::PWM2_NS);
 
     #[cfg(any(feature = "9160", feature = "5340-app"))]
-    impl Sealed for crate::pac::PWM3_NS {}
+    impl_sealed!(crate::pac::PWM3_NS);
 }
@@ -2,6 +2,34 @@
 //!
 //! The Quadrature decoder (QDEC) provides buffered decoding of quadrature-encoded sensor signals.
 //! It is suitable for mechanical and optical sensors.
+//!
+//! The steps are accumulated in hardware, so the position only has to be read every now and then,
+//! or whenever the REPORTRDY event signals movement:
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::{gpio::p0::Parts, pac::Peripherals};
+//! # use nrf_hal_common::qdec::{NumSamples, Pins, Qdec, QdecEvent, SamplePeriod};
+//! let p = Peripherals::take().unwrap();
+//! let port0 = Parts::new(p.P0);
+//! let pins = Pins {
+//!     a: port0.p0_31.into_pullup_input().degrade(),
+//!     b: port0.p0_30.into_pullup_input().degrade(),
+//!     led: None,
+//! };
+//! let qdec = Qdec::new(p.QDEC, pins, SamplePeriod::_128us);
+//! qdec.debounce(true)
+//!     .enable_interrupt(NumSamples::_10smpl)
+//!     .enable_event_interrupt(QdecEvent::AccOverflow);
+//! qdec.enable();
+//!
+//! let mut position = 0i32;
+//! // In the QDEC interrupt handler:
+//! if qdec.is_event_triggered(QdecEvent::ReportReady) {
+//!     qdec.reset_event(QdecEvent::ReportReady);
+//!     position += qdec.read_and_clear();
+//! }
+//! ```
 
 use {
     crate::gpio::{Input, Pin, PullUp},
@@ -131,6 +159,84 @@ impl Qdec {
         self.qdec.accread.read().bits() as i16
     }
 
+    /// Returns the signed number of steps since the last read and clears the accumulator.
+    ///
+    /// Unlike [`Qdec::read`], the full 32-bit `ACCREAD` value is returned. The double transitions
+    /// counted until this read are available from [`Qdec::double_transitions`].
+    #[inline(always)]
+    pub fn read_and_clear(&self) -> i32 {
+        self.qdec.tasks_readclracc.write(|w| unsafe { w.bits(1) });
+        self.qdec.accread.read().bits() as i32
+    }
+
+    /// Returns the number of double transitions (invalid, skipped steps) that were accumulated up
+    /// to the last [`Qdec::read_and_clear`] or [`Qdec::read`].
+    ///
+    /// A non-zero value means that the sample period is too long for the encoder speed.
+    #[inline(always)]
+    pub fn double_transitions(&self) -> u8 {
+        self.qdec.accdblread.read().accdblread().bits()
+    }
+
+    /// Returns the last sample: `-1` or `1` for a step, `0` for no movement and `2` for a double
+    /// transition.
+    #[inline(always)]
+    pub fn sample(&self) -> i8 {
+        self.qdec.sample.read().bits() as i8
+    }
+
+    /// Checks if an event has been triggered.
+    #[inline(always)]
+    pub fn is_event_triggered(&self, event: QdecEvent) -> bool {
+        match event {
+            QdecEvent::SampleReady => self.qdec.events_samplerdy.read().bits() != 0,
+            QdecEvent::ReportReady => self.qdec.events_reportrdy.read().bits() != 0,
+            QdecEvent::AccOverflow => self.qdec.events_accof.read().bits() != 0,
+            QdecEvent::DoubleReady => self.qdec.events_dblrdy.read().bits() != 0,
+            QdecEvent::Stopped => self.qdec.events_stopped.read().bits() != 0,
+        }
+    }
+
+    /// Marks the event as handled.
+    #[inline(always)]
+    pub fn reset_event(&self, event: QdecEvent) {
+        match event {
+            QdecEvent::SampleReady => self.qdec.events_samplerdy.reset(),
+            QdecEvent::ReportReady => self.qdec.events_reportrdy.reset(),
+            QdecEvent::AccOverflow => self.qdec.events_accof.reset(),
+            QdecEvent::DoubleReady => self.qdec.events_dblrdy.reset(),
+            QdecEvent::Stopped => self.qdec.events_stopped.reset(),
+        }
+    }
+
+    /// Enables interrupt triggering on the specified event.
+    ///
+    /// For [`QdecEvent::ReportReady`], the report period is set by [`Qdec::enable_interrupt`].
+    #[inline(always)]
+    pub fn enable_event_interrupt(&self, event: QdecEvent) -> &Self {
+        self.qdec.intenset.write(|w| match event {
+            QdecEvent::SampleReady => w.samplerdy().set_bit(),
+            QdecEvent::ReportReady => w.reportrdy().set_bit(),
+            QdecEvent::AccOverflow => w.accof().set_bit(),
+            QdecEvent::DoubleReady => w.dblrdy().set_bit(),
+            QdecEvent::Stopped => w.stopped().set_bit(),
+        });
+        self
+    }
+
+    /// Disables interrupt triggering on the specified event.
+    #[inline(always)]
+    pub fn disable_event_interrupt(&self, event: QdecEvent) -> &Self {
+        self.qdec.intenclr.write(|w| match event {
+            QdecEvent::SampleReady => w.samplerdy().set_bit(),
+            QdecEvent::ReportReady => w.reportrdy().set_bit(),
+            QdecEvent::AccOverflow => w.accof().set_bit(),
+            QdecEvent::DoubleReady => w.dblrdy().set_bit(),
+            QdecEvent::Stopped => w.stopped().set_bit(),
+        });
+        self
+    }
+
     /// Consumes `self` and returns back the raw `QDEC` peripheral.
     #[inline]
     pub fn free(self) -> (QDEC, Pins) {
@@ -192,3 +298,18 @@ pub enum LedPolarity {
     ActiveHigh,
     ActiveLow,
 }
+
+/// QDEC events.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum QdecEvent {
+    /// A new sample has been written to `SAMPLE`.
+    SampleReady,
+    /// Non-null report ready, after the number of samples set by [`Qdec::enable_interrupt`].
+    ReportReady,
+    /// The accumulator overflowed, steps are being lost until it is read.
+    AccOverflow,
+    /// Double transition(s) detected.
+    DoubleReady,
+    /// The QDEC has been stopped.
+    Stopped,
+}
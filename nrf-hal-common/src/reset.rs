@@ -0,0 +1,143 @@
+//! Reset reason, as recorded by the `RESETREAS` register of the POWER peripheral.
+//!
+//! The reset reasons accumulate across resets until they are cleared, so read them early during
+//! boot and clear them with [`ResetReasons::read_and_clear`]. When no reason is flagged, the chip
+//! was reset by the power-on or brownout reset.
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::reset::{ResetReason, ResetReasons};
+//! let reasons = ResetReasons::read_and_clear();
+//! if reasons.contains(ResetReason::Watchdog) {
+//!     // Report the watchdog reset.
+//! }
+//! ```
+
+#[cfg(not(feature = "9160"))]
+use crate::pac::POWER;
+#[cfg(feature = "9160")]
+use crate::pac::POWER_NS as POWER;
+
+/// A single cause of a reset.
+///
+/// Not every chip can report every reason, unsupported reasons are never flagged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ResetReason {
+    /// Reset from the reset pin.
+    ResetPin,
+    /// Reset from the watchdog.
+    Watchdog,
+    /// Soft reset, through `AIRCR.SYSRESETREQ`.
+    SoftReset,
+    /// Reset from a CPU lock-up.
+    Lockup,
+    /// Wakeup from System OFF mode by the DETECT signal from GPIO.
+    GpioWakeup,
+    /// Wakeup from System OFF mode by the ANADETECT signal from LPCOMP.
+    #[cfg(not(feature = "9160"))]
+    LpcompWakeup,
+    /// Wakeup from System OFF mode by entering debug interface mode.
+    DebugInterfaceWakeup,
+    /// Wakeup from System OFF mode by NFC field detect.
+    #[cfg(not(feature = "9160"))]
+    NfcWakeup,
+    /// Wakeup from System OFF mode by VBUS rising into the valid range.
+    #[cfg(not(feature = "9160"))]
+    VbusWakeup,
+    /// Reset triggered through CTRL-AP.
+    #[cfg(feature = "9160")]
+    CtrlAp,
+}
+
+impl ResetReason {
+    const ALL: &'static [ResetReason] = &[
+        ResetReason::ResetPin,
+        ResetReason::Watchdog,
+        ResetReason::SoftReset,
+        ResetReason::Lockup,
+        ResetReason::GpioWakeup,
+        #[cfg(not(feature = "9160"))]
+        ResetReason::LpcompWakeup,
+        ResetReason::DebugInterfaceWakeup,
+        #[cfg(not(feature = "9160"))]
+        ResetReason::NfcWakeup,
+        #[cfg(not(feature = "9160"))]
+        ResetReason::VbusWakeup,
+        #[cfg(feature = "9160")]
+        ResetReason::CtrlAp,
+    ];
+
+    #[cfg(not(feature = "9160"))]
+    fn mask(self) -> u32 {
+        match self {
+            ResetReason::ResetPin => 1 << 0,
+            ResetReason::Watchdog => 1 << 1,
+            ResetReason::SoftReset => 1 << 2,
+            ResetReason::Lockup => 1 << 3,
+            ResetReason::GpioWakeup => 1 << 16,
+            ResetReason::LpcompWakeup => 1 << 17,
+            ResetReason::DebugInterfaceWakeup => 1 << 18,
+            ResetReason::NfcWakeup => 1 << 19,
+            ResetReason::VbusWakeup => 1 << 20,
+        }
+    }
+
+    #[cfg(feature = "9160")]
+    fn mask(self) -> u32 {
+        match self {
+            ResetReason::ResetPin => 1 << 0,
+            ResetReason::Watchdog => 1 << 1,
+            ResetReason::GpioWakeup => 1 << 2,
+            ResetReason::DebugInterfaceWakeup => 1 << 4,
+            ResetReason::SoftReset => 1 << 16,
+            ResetReason::Lockup => 1 << 17,
+            ResetReason::CtrlAp => 1 << 18,
+        }
+    }
+}
+
+/// The set of reset reasons flagged in `RESETREAS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ResetReasons(u32);
+
+impl ResetReasons {
+    /// Reads the reset reasons without clearing them.
+    pub fn read() -> Self {
+        let power = unsafe { &*POWER::ptr() };
+        ResetReasons(power.resetreas.read().bits())
+    }
+
+    /// Reads the reset reasons and clears them, so that the next reset is reported on its own.
+    pub fn read_and_clear() -> Self {
+        let reasons = Self::read();
+        let power = unsafe { &*POWER::ptr() };
+        // Bits are cleared by writing 1 to them.
+        power.resetreas.write(|w| unsafe { w.bits(reasons.0) });
+        reasons
+    }
+
+    /// Returns `true` if `reason` caused one of the resets since the reasons were last cleared.
+    pub fn contains(&self, reason: ResetReason) -> bool {
+        self.0 & reason.mask() != 0
+    }
+
+    /// Returns `true` if no reason is flagged, meaning a power-on or brownout reset.
+    pub fn is_power_on(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns an iterator over the flagged reset reasons.
+    pub fn iter(&self) -> impl Iterator<Item = ResetReason> + '_ {
+        ResetReason::ALL
+            .iter()
+            .copied()
+            .filter(move |reason| self.contains(*reason))
+    }
+
+    /// Returns the raw value of the `RESETREAS` register.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
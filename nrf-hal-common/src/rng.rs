@@ -2,8 +2,13 @@
 //!
 //! See nRF52832 product specification, chapter 26.
 
+use core::future::poll_fn;
+use core::task::Poll;
+
 use rand_core::{CryptoRng, RngCore};
 
+use crate::waker::AtomicWaker;
+
 #[cfg(not(feature = "5340-net"))]
 use crate::pac::RNG;
 #[cfg(feature = "5340-net")]
@@ -11,9 +16,29 @@ use crate::pac::RNG_NS as RNG;
 
 /// Interface to the RNG peripheral.
 ///
-/// Right now, this is very basic, only providing blocking interfaces.
+/// `Rng` implements [`RngCore`] and [`CryptoRng`], so it can be passed directly to libraries from
+/// the `rand` ecosystem:
+///
+#[cfg_attr(feature = "52840", doc = "```no_run")]
+#[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+/// # use nrf_hal_common::{pac, Rng};
+/// use rand_core::{CryptoRng, RngCore};
+///
+/// fn generate_key<R: RngCore + CryptoRng>(rng: &mut R) -> [u8; 16] {
+///     let mut key = [0; 16];
+///     rng.fill_bytes(&mut key);
+///     key
+/// }
+///
+/// let p = pac::Peripherals::take().unwrap();
+/// let mut rng = Rng::new(p.RNG);
+/// let key = generate_key(&mut rng);
+/// ```
 pub struct Rng(RNG);
 
+/// The waker of the task waiting in [`Rng::fill_bytes_async`].
+static WAKER: AtomicWaker = AtomicWaker::new();
+
 impl Rng {
     pub fn new(rng: RNG) -> Self {
         rng.config.write(|w| w.dercen().enabled());
@@ -37,6 +62,60 @@ impl Rng {
         self.0.tasks_stop.write(|w| unsafe { w.bits(1) });
     }
 
+    /// Fill the provided buffer with random bytes.
+    ///
+    /// Will block until the buffer is full.
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        self.random(buf)
+    }
+
+    /// Fill the provided buffer with random bytes without blocking.
+    ///
+    /// [`Rng::on_interrupt`] has to be called from the RNG interrupt handler, and the interrupt
+    /// has to be unmasked in the NVIC. Dropping the future stops the generator.
+    pub async fn fill_bytes_async(&mut self, buf: &mut [u8]) {
+        self.0.events_valrdy.write(|w| unsafe { w.bits(0) });
+        self.0.tasks_start.write(|w| unsafe { w.bits(1) });
+        let stop = StopOnDrop(&self.0);
+
+        for b in buf {
+            poll_fn(|cx| {
+                WAKER.register(cx.waker());
+                if self.0.events_valrdy.read().bits() != 0 {
+                    Poll::Ready(())
+                } else {
+                    self.0.intenset.write(|w| w.valrdy().set());
+                    Poll::Pending
+                }
+            })
+            .await;
+            self.0.events_valrdy.write(|w| unsafe { w.bits(0) });
+
+            *b = self.0.value.read().value().bits();
+        }
+
+        drop(stop);
+    }
+
+    /// Wakes the task waiting in [`Rng::fill_bytes_async`].
+    ///
+    /// Call this from the RNG interrupt handler.
+    pub fn on_interrupt() {
+        let rng = unsafe { &*RNG::ptr() };
+        if rng.events_valrdy.read().bits() != 0 {
+            rng.intenclr.write(|w| w.valrdy().clear());
+            WAKER.wake();
+        }
+    }
+
+    /// Enables or disables the bias correction of the generated values.
+    ///
+    /// Bias correction is enabled by [`Rng::new`]. It gives a uniform distribution of the
+    /// generated values, at the cost of a lower generation rate.
+    pub fn set_bias_correction(&mut self, enabled: bool) {
+        self.0.config.write(|w| w.dercen().bit(enabled));
+    }
+
     /// Return a random `u8`.
     pub fn random_u8(&mut self) -> u8 {
         let mut buf = [0; 1];
@@ -73,6 +152,16 @@ impl Rng {
     }
 }
 
+/// Stops the generator when an async request is completed or cancelled.
+struct StopOnDrop<'a>(&'a RNG);
+
+impl Drop for StopOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.intenclr.write(|w| w.valrdy().clear());
+        self.0.tasks_stop.write(|w| unsafe { w.bits(1) });
+    }
+}
+
 impl RngCore for Rng {
     fn next_u32(&mut self) -> u32 {
         self.random_u32()
@@ -83,7 +172,7 @@ impl RngCore for Rng {
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        self.random(dest)
+        Rng::fill_bytes(self, dest)
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
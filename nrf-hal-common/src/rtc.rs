@@ -1,6 +1,11 @@
 //! A high level interface for RTC peripherals.
 
+use core::future::poll_fn;
 use core::ops::Deref;
+use core::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use core::task::Poll;
+
+use crate::time::Hertz;
 
 #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
 use crate::pac::{rtc0_ns as rtc0, Interrupt, NVIC, RTC0_NS as RTC0, RTC1_NS as RTC1};
@@ -14,10 +19,18 @@ use crate::pac::RTC2;
 /// An opaque high level interface to an RTC peripheral.
 pub struct Rtc<T> {
     periph: T,
+    period: u32,
 }
 
 /// Interrupts/Events that can be generated by the RTCn peripheral.
 pub enum RtcInterrupt {
+    /// Generated on every increment of the counter, i.e. at `32_768 / (prescaler + 1)` Hz.
+    ///
+    /// This makes a periodic heartbeat that does not use a compare register, e.g. a 32 Hz base
+    /// for a software scheduler with a prescaler of 1023. It is not free, though: every interrupt
+    /// wakes the CPU, and an enabled `TICK` event adds to the RTC's own power consumption, so keep
+    /// it disabled when unused. Pick the largest prescaler that gives a usable tick rate, or route the
+    /// event via PPI (see [`Rtc::event_tick`]) if the CPU doesn't need to run on each tick.
     Tick,
     Overflow,
     Compare0,
@@ -27,6 +40,10 @@ pub enum RtcInterrupt {
 }
 
 /// Compare registers available on the RTCn.
+///
+/// Each register has its own event and interrupt, so several wakeups can be armed on a single
+/// RTC. Convert a register into an [`RtcInterrupt`] to enable, check or reset its event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RtcCompareReg {
     Compare0,
     Compare1,
@@ -34,6 +51,28 @@ pub enum RtcCompareReg {
     Compare3,
 }
 
+impl RtcCompareReg {
+    fn index(self) -> usize {
+        match self {
+            RtcCompareReg::Compare0 => 0,
+            RtcCompareReg::Compare1 => 1,
+            RtcCompareReg::Compare2 => 2,
+            RtcCompareReg::Compare3 => 3,
+        }
+    }
+}
+
+impl From<RtcCompareReg> for RtcInterrupt {
+    fn from(reg: RtcCompareReg) -> Self {
+        match reg {
+            RtcCompareReg::Compare0 => RtcInterrupt::Compare0,
+            RtcCompareReg::Compare1 => RtcInterrupt::Compare1,
+            RtcCompareReg::Compare2 => RtcInterrupt::Compare2,
+            RtcCompareReg::Compare3 => RtcInterrupt::Compare3,
+        }
+    }
+}
+
 impl<T> Rtc<T>
 where
     T: Instance,
@@ -47,7 +86,29 @@ where
 
         unsafe { rtc.prescaler.write(|w| w.bits(prescaler)) };
 
-        Ok(Rtc { periph: rtc })
+        Ok(Rtc {
+            periph: rtc,
+            period: 0,
+        })
+    }
+
+    /// Creates a new RTC peripheral instance ticking at `freq`.
+    ///
+    /// The RTC runs at 32_768 / (prescaler + 1) Hz, so `freq` must divide 32 768 and be at least
+    /// 8 Hz, e.g. 1024 Hz for a prescaler of 31. Other frequencies return
+    /// [`Error::InvalidFrequency`].
+    pub fn with_frequency(rtc: T, freq: Hertz) -> Result<Self, Error> {
+        match LFCLK_FREQ.checked_div(freq.0) {
+            Some(divider) if divider * freq.0 == LFCLK_FREQ => {
+                Self::new(rtc, divider - 1).map_err(|_| Error::InvalidFrequency)
+            }
+            _ => Err(Error::InvalidFrequency),
+        }
+    }
+
+    /// Returns the tick frequency, rounded down to whole Hertz.
+    pub fn frequency(&self) -> Hertz {
+        Hertz(LFCLK_FREQ / (self.periph.prescaler.read().bits() + 1))
     }
 
     /// Enable/start the Real Time Counter.
@@ -163,27 +224,43 @@ where
         };
     }
 
+    /// Returns reference to the `TICK` event endpoint for PPI.
+    ///
+    /// The event must also be enabled with [`Rtc::enable_event`] to be routed.
+    #[inline(always)]
+    pub fn event_tick(&self) -> &rtc0::EVENTS_TICK {
+        &self.periph.events_tick
+    }
+
     /// Set the compare value of a given register. The compare registers have a width
     /// of 24 bits.
+    ///
+    /// `Compare3` is reserved while the extended counter is in use.
     pub fn set_compare(&mut self, reg: RtcCompareReg, val: u32) -> Result<(), Error> {
         if val >= (1 << 24) {
             return Err(Error::CompareOutOfRange);
         }
 
-        let reg = match reg {
-            RtcCompareReg::Compare0 => 0,
-            RtcCompareReg::Compare1 => 1,
-            RtcCompareReg::Compare2 => 2,
-            RtcCompareReg::Compare3 => 3,
-        };
-
         unsafe {
-            self.periph.cc[reg].write(|w| w.bits(val));
+            self.periph.cc[reg.index()].write(|w| w.bits(val));
         }
 
         Ok(())
     }
 
+    /// Returns the compare value of a given register.
+    pub fn get_compare(&self, reg: RtcCompareReg) -> u32 {
+        self.periph.cc[reg.index()].read().bits()
+    }
+
+    /// Returns reference to the `COMPARE` event endpoint of a given register for PPI.
+    ///
+    /// The event must also be enabled with [`Rtc::enable_event`] to be routed.
+    #[inline(always)]
+    pub fn event_compare(&self, reg: RtcCompareReg) -> &rtc0::EVENTS_COMPARE {
+        &self.periph.events_compare[reg.index()]
+    }
+
     /// Obtain the current value of the Real Time Counter, 24 bits of range.
     pub fn get_counter(&self) -> u32 {
         self.periph.counter.read().bits()
@@ -204,11 +281,264 @@ where
         }
     }
 
+    /// Enables the extended counter used by [`Rtc::now64`].
+    ///
+    /// The 24-bit counter is extended in software with a period count that is advanced twice per
+    /// counter wrap: once on the `OVRFLW` event and once halfway through the range, on a compare
+    /// match with `CC[3]`. `Compare3` is therefore reserved while the extended counter is in use.
+    ///
+    /// [`Rtc::handle_overflow`] must be called from the RTC interrupt handler. Each event must be
+    /// handled within half the counter range (2^23 ticks, about 256 seconds with a prescaler of 0)
+    /// for [`Rtc::now64`] to remain correct; interrupts serviced later than that are
+    /// indistinguishable from a skipped wrap.
+    ///
+    /// If access to the NVIC is not provided, the interrupt must ALSO be enabled
+    /// there outside of this function (e.g. manually call `nvic.enable`, or through
+    /// the use of RTIC).
+    pub fn enable_extended_counter(&mut self, nvic: Option<&mut NVIC>) {
+        self.period = 0;
+        unsafe {
+            self.periph.cc[3].write(|w| w.bits(HALF_RANGE));
+        }
+        self.reset_event(RtcInterrupt::Overflow);
+        self.reset_event(RtcInterrupt::Compare3);
+        self.enable_event(RtcInterrupt::Overflow);
+        self.enable_event(RtcInterrupt::Compare3);
+        self.periph
+            .intenset
+            .write(|w| w.ovrflw().set().compare3().set());
+        if let Some(_nvic) = nvic {
+            unsafe { NVIC::unmask(T::INTERRUPT) };
+        }
+    }
+
+    /// Advances the extended counter. Call this from the RTC interrupt handler.
+    ///
+    /// Clears the `OVRFLW` and `COMPARE[3]` events if they are pending. Other events are left
+    /// untouched, so the handler may still service them afterwards.
+    pub fn handle_overflow(&mut self) {
+        self.period = advance_period(&self.periph, 3, self.period);
+    }
+
+    /// Obtain the current value of the extended counter, 64 bits of range.
+    ///
+    /// Only meaningful after [`Rtc::enable_extended_counter`] has been called. Clearing the
+    /// counter does not reset the extended count.
+    pub fn now64(&self) -> u64 {
+        extend_counter(self.period, self.get_counter())
+    }
+
     /// Destructure the high level interface. Does not reset any configuration made
     /// to the given RTC peripheral.
     pub fn release(self) -> T {
         self.periph
     }
+
+    /// Turns the RTC into an [`ExtendedRtc`], a 64-bit tick counter readable through a shared
+    /// reference.
+    ///
+    /// This enables the extended counter like [`Rtc::enable_extended_counter`], with the same
+    /// requirements: `Compare3` is reserved and [`ExtendedRtc::on_interrupt`] must be called from
+    /// the RTC interrupt handler within half the counter range.
+    pub fn into_extended(mut self, nvic: Option<&mut NVIC>) -> ExtendedRtc<T> {
+        self.enable_extended_counter(nvic);
+        ExtendedRtc {
+            rtc: self,
+            period: AtomicU32::new(0),
+        }
+    }
+}
+
+/// An RTC whose 24-bit counter is extended to 64 bits in software.
+///
+/// Unlike [`Rtc::now64`], the period count is kept in an atomic, so both
+/// [`ExtendedRtc::on_interrupt`] and [`ExtendedRtc::ticks64`] only need a shared reference and
+/// the interrupt handler can update the count while the application reads it.
+pub struct ExtendedRtc<T> {
+    rtc: Rtc<T>,
+    period: AtomicU32,
+}
+
+impl<T> ExtendedRtc<T>
+where
+    T: Instance,
+{
+    /// Advances the period count. Call this from the RTC interrupt handler.
+    ///
+    /// Clears the `OVRFLW` and `COMPARE[3]` events if they are pending, other events are left
+    /// untouched.
+    pub fn on_interrupt(&self) {
+        // Only the interrupt handler writes the count, so a plain load and store suffice.
+        let period = advance_period(&self.rtc.periph, 3, self.period.load(Ordering::Relaxed));
+        self.period.store(period, Ordering::Release);
+    }
+
+    /// Returns the number of ticks since the extended counter was enabled.
+    ///
+    /// A wrap of the counter between reading the period count and the counter is handled: the
+    /// counter then lies in the other half of its range than the period expects, which
+    /// the extension folds back into the right period.
+    pub fn ticks64(&self) -> u64 {
+        let period = self.period.load(Ordering::Acquire);
+        extend_counter(period, self.rtc.get_counter())
+    }
+
+    /// Returns the underlying RTC, e.g. to use the other compare registers.
+    pub fn rtc(&self) -> &Rtc<T> {
+        &self.rtc
+    }
+
+    /// Disables the extended counter interrupts and returns the RTC.
+    pub fn free(mut self) -> Rtc<T> {
+        self.rtc
+            .periph
+            .intenclr
+            .write(|w| w.ovrflw().clear().compare3().clear());
+        self.rtc.period = *self.period.get_mut();
+        self.rtc
+    }
+}
+
+/// An RTC used as an async delay provider.
+///
+/// The delay arms one compare register relative to the running counter, and the task sleeps
+/// until its interrupt fires. Unlike [`TimerDelay`](crate::timer::TimerDelay), this only needs
+/// the low frequency clock, so the high frequency clock can stay off while waiting. The
+/// resolution is one RTC tick, and delays are rounded up to at least two ticks.
+///
+/// [`RtcDelay::on_interrupt`] has to be called from the RTC interrupt handler, and the interrupt
+/// has to be unmasked in the NVIC.
+pub struct RtcDelay<T> {
+    rtc: Rtc<T>,
+    reg: RtcCompareReg,
+}
+
+impl<T> RtcDelay<T>
+where
+    T: Instance,
+{
+    /// Uses the compare register `reg` of `rtc` for delays, and starts its counter.
+    pub fn new(rtc: Rtc<T>, reg: RtcCompareReg) -> Self {
+        T::delay_reg().store(reg.index() as u8, Ordering::Relaxed);
+        rtc.enable_counter();
+        RtcDelay { rtc, reg }
+    }
+
+    /// Releases the RTC.
+    pub fn free(mut self) -> Rtc<T> {
+        self.rtc.disable_interrupt(self.reg.into(), None);
+        self.rtc
+    }
+
+    /// Wakes the task waiting on the delay.
+    ///
+    /// Call this from the RTC interrupt handler.
+    pub fn on_interrupt() {
+        let rtc = unsafe { &*T::PTR };
+        // Only the delay's own compare register is touched, the others may be in use elsewhere.
+        let i = T::delay_reg().load(Ordering::Relaxed) as usize;
+        // The COMPARE[i] interrupt enable bits start at bit 16.
+        let bit = 1 << (16 + i);
+        if rtc.events_compare[i].read().bits() != 0 && rtc.intenset.read().bits() & bit != 0 {
+            rtc.intenclr.write(|w| unsafe { w.bits(bit) });
+            T::waker().wake();
+        }
+    }
+
+    async fn delay_us_u64(&mut self, us: u64) {
+        let freq = self.rtc.frequency().0 as u64;
+        let mut ticks = (us * freq).div_ceil(1_000_000);
+
+        while ticks > 0 {
+            // Stay well within the 24-bit counter range, and at least two ticks ahead of the
+            // counter to be sure the compare event is generated.
+            let chunk = ticks.min(HALF_RANGE as u64) as u32;
+            ticks -= chunk as u64;
+            let target = (self.rtc.get_counter() + chunk.max(2)) & COUNTER_MASK;
+
+            self.rtc.reset_event(self.reg.into());
+            self.rtc.set_compare(self.reg, target).unwrap();
+            self.rtc.enable_interrupt(self.reg.into(), None);
+
+            let rtc = &self.rtc;
+            let reg = self.reg;
+            let stop = StopOnDrop(rtc, reg);
+            poll_fn(|cx| {
+                T::waker().register(cx.waker());
+                if rtc.is_event_triggered(reg.into()) {
+                    Poll::Ready(())
+                } else {
+                    Poll::Pending
+                }
+            })
+            .await;
+            drop(stop);
+        }
+    }
+}
+
+impl<T> embedded_hal_async::delay::DelayNs for RtcDelay<T>
+where
+    T: Instance,
+{
+    async fn delay_ns(&mut self, ns: u32) {
+        self.delay_us_u64(ns.div_ceil(1_000) as u64).await;
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        self.delay_us_u64(us as u64).await;
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.delay_us_u64(ms as u64 * 1_000).await;
+    }
+}
+
+/// Disarms the compare register when an async delay has elapsed or is dropped.
+struct StopOnDrop<'a, T: Instance>(&'a Rtc<T>, RtcCompareReg);
+
+impl<T: Instance> Drop for StopOnDrop<'_, T> {
+    fn drop(&mut self) {
+        let bit = 1 << (16 + self.1.index());
+        self.0.periph.intenclr.write(|w| unsafe { w.bits(bit) });
+        self.0.reset_event(self.1.into());
+    }
+}
+
+/// Frequency of the LFCLK driving the RTC.
+const LFCLK_FREQ: u32 = 32_768;
+
+/// Half of the 24-bit counter range, used to advance the extended counter twice per wrap.
+const HALF_RANGE: u32 = 1 << 23;
+
+/// Mask of the 24-bit counter and compare registers.
+pub(crate) const COUNTER_MASK: u32 = (1 << 24) - 1;
+
+/// Combines the software period count with the 24-bit hardware counter.
+///
+/// An even period expects the counter in the lower half of its range and an odd period in the
+/// upper half. A counter found in the other half means an event is pending but not yet handled,
+/// which the XOR folds back into the right period.
+pub(crate) fn extend_counter(period: u32, counter: u32) -> u64 {
+    ((period as u64) << 23) + ((counter ^ ((period & 1) * HALF_RANGE)) as u64)
+}
+
+/// Returns `period` advanced by the pending `OVRFLW` and half-range `COMPARE[half_cc]` events,
+/// clearing them.
+pub(crate) fn advance_period(rtc: &rtc0::RegisterBlock, half_cc: usize, mut period: u32) -> u32 {
+    // The half-range compare is handled first: if both events are pending, they belong to the
+    // same wrap of the counter and the compare match happened first.
+    if rtc.events_compare[half_cc].read().bits() != 0 {
+        rtc.events_compare[half_cc].write(|w| unsafe { w.bits(0) });
+        period |= 1;
+    }
+    if rtc.events_ovrflw.read().bits() != 0 {
+        rtc.events_ovrflw.write(|w| unsafe { w.bits(0) });
+        // Rounding up to the next even period keeps the count in step with the counter even if
+        // the compare match was skipped, e.g. by `Rtc::trigger_overflow`.
+        period = (period | 1).wrapping_add(1);
+    }
+    period
 }
 
 /// Error types associated with the RTC peripheral interface.
@@ -216,6 +546,8 @@ where
 pub enum Error {
     PrescalerOutOfRange,
     CompareOutOfRange,
+    /// The requested frequency can't be derived from the 32.768 kHz clock.
+    InvalidFrequency,
 }
 
 /// Implemented by all RTC instances.
@@ -225,7 +557,19 @@ pub trait Instance: Deref<Target = rtc0::RegisterBlock> + sealed::Sealed {
 }
 
 mod sealed {
-    pub trait Sealed {}
+    use super::{rtc0, AtomicU8};
+    use crate::waker::AtomicWaker;
+
+    pub trait Sealed {
+        /// Pointer to the register block of the instance.
+        const PTR: *const rtc0::RegisterBlock;
+
+        /// The waker of the task waiting in an async delay.
+        fn waker() -> &'static AtomicWaker;
+
+        /// The index of the compare register used by the async delay.
+        fn delay_reg() -> &'static AtomicU8;
+    }
 }
 
 macro_rules! impl_instance {
@@ -234,7 +578,19 @@ macro_rules! impl_instance {
             impl Instance for $name {
                 const INTERRUPT: Interrupt = Interrupt::$name;
             }
-            impl sealed::Sealed for $name {}
+            impl sealed::Sealed for $name {
+                const PTR: *const rtc0::RegisterBlock = $name::PTR as *const rtc0::RegisterBlock;
+
+                fn waker() -> &'static crate::waker::AtomicWaker {
+                    static WAKER: crate::waker::AtomicWaker = crate::waker::AtomicWaker::new();
+                    &WAKER
+                }
+
+                fn delay_reg() -> &'static core::sync::atomic::AtomicU8 {
+                    static REG: core::sync::atomic::AtomicU8 = core::sync::atomic::AtomicU8::new(0);
+                    &REG
+                }
+            }
         )*
     }
 }
@@ -24,7 +24,24 @@
 //!
 //! // blocking read from saadc for `saadc_config.time` microseconds
 //! let _saadc_result = saadc.read_channel(&mut saadc_pin);
+//!
+//! // non-blocking read, polling until the conversion is done
+//! let _saadc_result = nb::block!(saadc.read_channel_nb(&mut saadc_pin));
 //! ```
+//!
+//! # Migrating from embedded-hal 0.2
+//!
+//! embedded-hal 1.0 has no ADC trait, so the `embedded_hal_02::adc::OneShot` implementation is
+//! only kept behind the `embedded-hal-02` feature for existing drivers. New code should use the
+//! inherent methods instead:
+//!
+//! - `OneShot::read(&mut saadc, &mut pin)` becomes [`Saadc::read_channel_nb`], which has the same
+//!   `nb` signature but actually returns `WouldBlock` while converting, or
+//!   [`Saadc::read_channel`] for a blocking read.
+//! - The error type is [`SaadcError`] in both cases.
+//!
+//! Drivers that need to abstract over ADCs can take a closure such as
+//! `FnMut() -> nb::Result<i16, E>` and be handed `|| saadc.read_channel_nb(&mut pin)`.
 
 #[cfg(any(feature = "9160", feature = "5340-app"))]
 use crate::pac::{saadc_ns as saadc, SAADC_NS as SAADC};
@@ -32,11 +49,19 @@ use crate::pac::{saadc_ns as saadc, SAADC_NS as SAADC};
 #[cfg(not(any(feature = "9160", feature = "5340-app")))]
 use crate::pac::{saadc, SAADC};
 
+use crate::clocks::HFCLK_FREQ;
+#[cfg(not(any(feature = "9160", feature = "5340-app")))]
+use crate::ppi::{ConfigurablePpi, Ppi};
+use core::convert::TryFrom;
 use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+use void::Void;
 
-use nrf52840_pac::gpiote::config;
+use saadc::ch::{pseln::PSELN_A, pselp::PSELP_A};
 pub use saadc::{
-    ch::config::{GAIN_A as Gain, REFSEL_A as Reference, RESP_A as Resistor, TACQ_A as Time},
+    ch::config::{
+        GAIN_A as Gain, REFSEL_A as Reference, RESN_A as NegativeResistor, RESP_A as Resistor,
+        TACQ_A as Time,
+    },
     oversample::OVERSAMPLE_A as Oversample,
     resolution::VAL_A as Resolution,
 };
@@ -49,101 +74,228 @@ pub trait Channel {
     fn channel() -> u8;
 }
 
-// Only 1 channel is allowed right now, a discussion needs to be had as to how
-// multiple channels should work (See "scan mode" in the datasheet).
-// Issue: https://github.com/nrf-rs/nrf-hal/issues/82
-
 /// Interface for the SAADC peripheral.
 ///
 /// External analog channels supported by the SAADC implement the `Channel` trait.
-/// Currently, use of only one channel is allowed.
-pub struct Saadc(SAADC);
+/// Single inputs are sampled with [`Saadc::read_channel`], several inputs at once in scan mode
+/// with [`Saadc::read_channels`].
+pub struct Saadc {
+    saadc: SAADC,
+    /// Whether a conversion started by [`Saadc::read_channel_nb`] is in flight.
+    converting: bool,
+    /// Whether a calibration started by [`Saadc::calibrate_nb`] is in progress.
+    calibrating: bool,
+}
 
-pub struct SaadcTask<const CHANNELS: usize> {
-    buffer: [u16; CHANNELS],
+/// Result buffer of [`Saadc::read_channel_nb`].
+///
+/// The conversion outlives the borrow of the `Saadc`, which may be moved in between, so EasyDMA
+/// can't target a field of it. There is only one SAADC, so a single buffer suffices.
+static mut NB_RESULT: i16 = 0;
+
+/// Multi-channel SAADC sampling task.
+///
+/// The samples are written by EasyDMA into the first `CHANNELS` entries of the buffer `B`, which is
+/// either owned by the task (see [`SaadcTask::new`]) or borrowed from the caller (see
+/// [`SaadcTask::with_buffer`]).
+pub struct SaadcTask<const CHANNELS: usize, B = [u16; CHANNELS]> {
+    saadc: SAADC,
+    buffer: B,
 }
 
 impl<const CHANNELS: usize> SaadcTask<CHANNELS> {
-    #[inline(always)]
-    fn ptr<'a>() -> &'a mut crate::pac::saadc::RegisterBlock {
-        unsafe { &mut *SAADC::PTR.cast_mut() }
-    }
+    /// Configures the SAADC to sample `channels` into an owned buffer.
+    ///
+    /// `channels` holds one channel ID per SAADC channel, as used by the [`Channel`] trait. Any
+    /// [`AnalogInput`] can be converted to such an ID, e.g. `AnalogInput::Ain5.into()`.
+    ///
+    /// # Panics
+    ///
+    /// Oversampling is not supported in scan mode, so this panics if more than one channel is
+    /// given and `config` has an oversample other than [`Oversample::BYPASS`].
     pub fn new(
         saadc: SAADC,
         config: SaadcConfig,
         channels: &[u8; CHANNELS],
         buffer: [u16; CHANNELS],
     ) -> Self {
-        // The write enums do not implement clone/copy/debug, only the
-        // read ones, hence the need to pull out and move the values.
-        let SaadcConfig {
-            resolution,
-            oversample,
-            reference,
-            gain,
-            resistor,
-            time,
-        } = config;
-        saadc.resolution.write(|w| w.val().variant(resolution));
-        saadc
-            .oversample
-            .write(|w| w.oversample().variant(oversample));
-        saadc.samplerate.write(|w| w.mode().task());
-        for (idx, ch) in channels.iter().enumerate() {
-            saadc.ch[idx].config.write(|w| {
-                w.refsel().variant(reference);
-                w.gain().variant(gain);
-                w.tacq().variant(time);
-                w.mode().se();
-                w.resp().variant(resistor);
-                w.resn().bypass();
-                w.burst().enabled();
-                w
-            });
-
-            match ch {
-                0 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input0()),
-                1 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input1()),
-                2 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input2()),
-                3 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input3()),
-                4 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input4()),
-                5 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input5()),
-                6 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input6()),
-                7 => saadc.ch[idx].pselp.write(|w| w.pselp().analog_input7()),
-                #[cfg(not(feature = "9160"))]
-                8 => saadc.ch[idx].pselp.write(|w| w.pselp().vdd()),
-                #[cfg(any(feature = "52833", feature = "52840"))]
-                13 => saadc.ch[idx].pselp.write(|w| w.pselp().vddhdiv5()),
-                // This can never happen with the `Channel` implementations provided, as the only analog
-                // pins have already been covered.
-                _ => panic!(),
-            }
-            saadc.ch[idx].pseln.write(|w| w.pseln().nc());
-        }
+        configure_task(&saadc, config, channels, None);
+        SaadcTask { saadc, buffer }
+    }
 
-        saadc.enable.write(|w| w.enable().set_bit());
-        // Calibrate
-        saadc.events_calibratedone.reset();
-        saadc.tasks_calibrateoffset.write(|w| unsafe { w.bits(1) });
-        //while saadc.events_calibratedone.read().bits() == 0 {}
-        saadc
-            .inten
-            .write(|w| w.end().set_bit().done().disabled().resultdone().clear_bit());
-        saadc.intenset.write(|w| {
-            w.end()
-                .set_bit()
-                .resultdone()
-                .clear_bit()
-                .done()
-                .clear_bit()
+    /// Like [`SaadcTask::new`], but with separate reference, gain, resistor and acquisition time
+    /// settings for each channel.
+    ///
+    /// `configs[i]` applies to the channel `channels[i]` instead of the per-channel part of
+    /// `config`. This allows a single scan to mix, e.g., a high-gain input with a low-gain battery
+    /// divider. The settings are applied before the offset calibration at the end of the
+    /// configuration. The resolution and oversample remain global, and burst mode stays enabled
+    /// on every channel.
+    ///
+    /// A measurement cycle takes the sum of the per-channel acquisition and conversion times.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`SaadcTask::new`]. Burst mode is enabled on every
+    /// channel, so a single oversampled channel takes all 2^`oversample` samples in one go, but a
+    /// scan over more than one channel only supports [`Oversample::BYPASS`].
+    ///
+    #[cfg_attr(feature = "52840", doc = "```no_run")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common as hal;
+    /// use hal::saadc::{AnalogInput, ChannelConfig, Gain, Oversample, SaadcConfig, SaadcTask};
+    ///
+    /// # let saadc = unsafe { hal::pac::Peripherals::steal() }.SAADC;
+    /// // Scan mode does not support oversampling.
+    /// let config = SaadcConfig::builder().oversample(Oversample::BYPASS).build();
+    /// let channels = [AnalogInput::Ain0.into(), AnalogInput::Ain1.into()];
+    /// let configs = [
+    ///     ChannelConfig {
+    ///         gain: Gain::GAIN4,
+    ///         ..ChannelConfig::default()
+    ///     },
+    ///     ChannelConfig::default(),
+    /// ];
+    /// let task = SaadcTask::with_channel_configs(saadc, config, &channels, configs, [0; 2]);
+    /// ```
+    pub fn with_channel_configs(
+        saadc: SAADC,
+        config: SaadcConfig,
+        channels: &[u8; CHANNELS],
+        configs: [ChannelConfig; CHANNELS],
+        buffer: [u16; CHANNELS],
+    ) -> Self {
+        configure_task(&saadc, config, channels, Some(&configs));
+        SaadcTask { saadc, buffer }
+    }
+}
+
+impl<'a, const CHANNELS: usize> SaadcTask<CHANNELS, &'a mut [u16]> {
+    /// Configures the SAADC to sample `channels` into caller-provided memory.
+    ///
+    /// Only the first `CHANNELS` entries of `buffer` are written, so it may be a subslice of a
+    /// larger buffer, e.g. a slot in a ring buffer or a region placed by the linker.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than `CHANNELS`, or under the same conditions as
+    /// [`SaadcTask::new`].
+    pub fn with_buffer(
+        saadc: SAADC,
+        config: SaadcConfig,
+        channels: &[u8; CHANNELS],
+        buffer: &'a mut [u16],
+    ) -> Self {
+        assert!(buffer.len() >= CHANNELS);
+        configure_task(&saadc, config, channels, None);
+        SaadcTask { saadc, buffer }
+    }
+}
+
+/// Checks that oversampling is only used with a single channel, which is all the SAADC supports.
+fn check_oversample(channels: usize, bypass: bool) -> Result<(), SaadcError> {
+    if channels > 1 && !bypass {
+        return Err(SaadcError::OversampleInScan);
+    }
+    Ok(())
+}
+
+/// Rejects an empty scan in [`Saadc::read_channels`] at compile time, as it would start EasyDMA
+/// with a MAXCNT of zero.
+struct ScanLen<const N: usize>;
+
+impl<const N: usize> ScanLen<N> {
+    const NOT_EMPTY: () = assert!(N > 0, "a SAADC scan needs at least one input");
+}
+
+/// Applies `config` to every channel in `channels` and enables the interrupts used by `SaadcTask`.
+///
+/// `configs`, if given, replaces the per-channel part of `config` for each channel.
+fn configure_task(
+    saadc: &SAADC,
+    config: SaadcConfig,
+    channels: &[u8],
+    configs: Option<&[ChannelConfig]>,
+) {
+    assert!(
+        check_oversample(channels.len(), config.oversample == Oversample::BYPASS).is_ok(),
+        "oversampling is not supported with more than one SAADC channel"
+    );
+
+    let default_config = config.channel_config();
+    // The write enums do not implement clone/copy/debug, only the
+    // read ones, hence the need to pull out and move the values.
+    let SaadcConfig {
+        resolution,
+        oversample,
+        negative_resistor,
+        ..
+    } = config;
+    saadc.resolution.write(|w| w.val().variant(resolution));
+    saadc
+        .oversample
+        .write(|w| w.oversample().variant(oversample));
+    saadc.samplerate.write(|w| w.mode().task());
+    for (idx, ch) in channels.iter().enumerate() {
+        let ch_config = configs.map_or(default_config, |configs| configs[idx]);
+        saadc.ch[idx].config.write(|w| {
+            w.refsel().variant(ch_config.reference);
+            w.gain().variant(ch_config.gain);
+            w.tacq().variant(ch_config.time);
+            w.mode().se();
+            w.resp().variant(ch_config.resistor);
+            w.resn().variant(negative_resistor);
+            w.burst().enabled();
+            w
         });
-        SaadcTask { buffer }
+
+        // This can only fail for IDs that no `Channel` implementation provides.
+        let input = AnalogInput::try_from(*ch).unwrap();
+        saadc.ch[idx]
+            .pselp
+            .write(|w| w.pselp().variant(input.into()));
+        saadc.ch[idx].pseln.write(|w| w.pseln().nc());
+    }
+
+    saadc.enable.write(|w| w.enable().set_bit());
+    // Calibrate
+    saadc.events_calibratedone.reset();
+    saadc.tasks_calibrateoffset.write(|w| unsafe { w.bits(1) });
+    // A calibration that doesn't finish in time only leaves the offset uncalibrated, the
+    // measurements themselves still work.
+    let _ = wait_for_calibration(saadc);
+    saadc
+        .inten
+        .write(|w| w.end().set_bit().done().disabled().resultdone().clear_bit());
+    saadc.intenset.write(|w| {
+        w.end()
+            .set_bit()
+            .resultdone()
+            .clear_bit()
+            .done()
+            .clear_bit()
+    });
+}
+
+impl<const CHANNELS: usize, B> SaadcTask<CHANNELS, B>
+where
+    B: AsRef<[u16]> + AsMut<[u16]>,
+{
+    #[inline(always)]
+    fn ptr<'a>() -> &'a mut saadc::RegisterBlock {
+        unsafe { &mut *SAADC::PTR.cast_mut() }
+    }
+
+    /// The samples of the last completed measurement cycle, one per channel.
+    #[inline(always)]
+    fn samples(&self) -> &[u16] {
+        &self.buffer.as_ref()[..CHANNELS]
     }
 
     /// Starts a new measurements cycle.
     #[inline(always)]
     pub fn start_sample(&mut self) {
-        let ptr = self.buffer.as_mut_ptr();
+        let ptr = self.buffer.as_mut().as_mut_ptr();
         let saadc = Self::ptr();
         saadc.events_end.reset();
         saadc
@@ -160,15 +312,15 @@ impl<const CHANNELS: usize> SaadcTask<CHANNELS> {
         // pointer and maxcount have been set.
         compiler_fence(SeqCst);
 
-        saadc.tasks_start.write(|w| w.tasks_start().set_bit());
-        saadc.tasks_sample.write(|w| w.tasks_sample().set_bit());
+        saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+        saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
         saadc.inten.write(|w| w.end().set_bit());
         saadc.intenset.write(|w| w.end().set_bit());
     }
 
     /// Prepares a sample. This is simply here to allow
     pub fn prepare_sample(&mut self) {
-        let ptr = self.buffer.as_mut_ptr();
+        let ptr = self.buffer.as_mut().as_mut_ptr();
         let saadc = Self::ptr();
         saadc.events_end.reset();
         saadc
@@ -185,7 +337,7 @@ impl<const CHANNELS: usize> SaadcTask<CHANNELS> {
         // pointer and maxcount have been set.
         compiler_fence(SeqCst);
 
-        saadc.tasks_start.write(|w| w.tasks_start().set_bit());
+        saadc.tasks_start.write(|w| unsafe { w.bits(1) });
     }
 
     /// Reads the buffer returning the converted values.
@@ -194,12 +346,33 @@ impl<const CHANNELS: usize> SaadcTask<CHANNELS> {
         mut callback: Callback,
     ) -> [T; CHANNELS] {
         let mut res = [T::default(); CHANNELS];
-        for (idx, val) in self.buffer.iter().enumerate() {
+        for (idx, val) in self.samples().iter().enumerate() {
             res[idx] = callback(*val);
         }
         res
     }
 
+    /// Lazily walks the buffer, yielding one [`Sample`] per channel.
+    ///
+    /// Unlike [`SaadcTask::read_buffer`], this does not build a `[T; CHANNELS]`, which is handy
+    /// when the results are only folded or filtered.
+    pub fn iter_samples<'a, T, F>(&'a self, convert: F) -> impl Iterator<Item = Sample<T>> + 'a
+    where
+        F: Fn(i16) -> T + 'a,
+    {
+        self.samples()
+            .iter()
+            .enumerate()
+            .map(move |(channel, val)| {
+                let raw = *val as i16;
+                Sample {
+                    channel,
+                    raw,
+                    value: convert(raw),
+                }
+            })
+    }
+
     /// Completes the previous measurement cycle and returns the values.
     ///
     /// This function takes a callback that allows for easy conversions.
@@ -223,18 +396,39 @@ impl<const CHANNELS: usize> SaadcTask<CHANNELS> {
         // Second fence to prevent optimizations creating issues with the EasyDMA-modified `val`.
         //compiler_fence(SeqCst);
         let mut res = [T::default(); CHANNELS];
-        for (idx, val) in self.buffer.iter().enumerate() {
+        for (idx, val) in self.samples().iter().enumerate() {
             res[idx] = callback(*val);
         }
 
         res
     }
 
+    /// Runs a measurement cycle and waits for it to complete, returning the converted values.
+    ///
+    /// Gives up and returns [`SaadcError::SampleTimeout`] after 1 ms, or after twice the time the
+    /// cycle should take with the configured acquisition time and oversampling if that is longer.
+    /// Use [`SaadcTask::sample_blocking_timeout`] for a tighter bound.
     pub fn sample_blocking<T: Default + Copy, Callback: FnMut(u16) -> T>(
         &mut self,
+        callback: Callback,
+    ) -> Result<[T; CHANNELS], SaadcError> {
+        let timeout_cycles = Self::expected_cycles()
+            .saturating_mul(2)
+            .max(SAMPLE_TIMEOUT_CYCLES);
+        self.sample_blocking_timeout(timeout_cycles, callback)
+    }
+
+    /// Runs a measurement cycle and waits up to `timeout_cycles` CPU cycles for it to complete.
+    ///
+    /// On timeout, the measurement is stopped and [`SaadcError::SampleTimeout`] is returned, so
+    /// that the next cycle starts from a clean state. The timeout is a lower bound, as the END
+    /// event is only polled every few cycles.
+    pub fn sample_blocking_timeout<T: Default + Copy, Callback: FnMut(u16) -> T>(
+        &mut self,
+        timeout_cycles: u32,
         mut callback: Callback,
-    ) -> Option<[T; CHANNELS]> {
-        let ptr = self.buffer.as_mut_ptr();
+    ) -> Result<[T; CHANNELS], SaadcError> {
+        let ptr = self.buffer.as_mut().as_mut_ptr();
         let saadc = Self::ptr();
         saadc.events_end.reset();
         saadc
@@ -246,15 +440,7 @@ impl<const CHANNELS: usize> SaadcTask<CHANNELS> {
             .maxcnt
             .write(|w| unsafe { w.maxcnt().bits(CHANNELS as u16) });
         saadc.enable.write(|w| w.enable().set_bit());
-
-        // Conservative compiler fence to prevent starting the ADC before the
-        // pointer and maxcount have been set.
-        compiler_fence(SeqCst);
-
-        saadc.tasks_start.write(|w| w.tasks_start().set_bit());
-        saadc.tasks_sample.write(|w| w.tasks_sample().set_bit());
         saadc.inten.write(|w| w.end().clear_bit());
-        saadc.intenset.write(|w| w.end().clear_bit());
 
         // Conservative compiler fence to prevent starting the ADC before the
         // pointer and maxcount have been set.
@@ -262,26 +448,356 @@ impl<const CHANNELS: usize> SaadcTask<CHANNELS> {
 
         saadc.tasks_start.write(|w| unsafe { w.bits(1) });
         saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
-        let mut count = 0;
+
+        let mut waited: u32 = 0;
         while saadc.events_end.read().bits() == 0 {
-            count += 1;
-            if count > 10_000 {
-                return None;
+            if waited >= timeout_cycles {
+                Self::abort();
+                return Err(SaadcError::SampleTimeout);
             }
+            cortex_m::asm::delay(POLL_CYCLES);
+            waited = waited.saturating_add(POLL_CYCLES);
         }
         saadc.events_end.reset();
 
         // Second fence to prevent optimizations creating issues with the EasyDMA-modified `val`.
         compiler_fence(SeqCst);
         let mut res = [T::default(); CHANNELS];
-        for (idx, val) in self.buffer.iter().enumerate() {
+        for (idx, val) in self.samples().iter().enumerate() {
             res[idx] = callback(*val);
         }
 
-        Some(res)
+        Ok(res)
+    }
+
+    /// Starts sampling continuously, paced by the internal timer of the SAADC.
+    ///
+    /// A sample is taken every `sample_rate_cc` cycles of the 16 MHz clock, i.e. at
+    /// `16 MHz / sample_rate_cc`, and written by EasyDMA to the next entry of the buffer without
+    /// any CPU involvement. Unlike the other methods, this uses the whole buffer rather than just
+    /// its first `CHANNELS` entries, so pass a larger one to [`SaadcTask::with_buffer`]. Once the
+    /// buffer is full, the END event fires; read the samples with
+    /// [`SaadcTask::continuous_samples`] and call [`SaadcTask::restart_continuous`] from the
+    /// interrupt handler to refill it.
+    ///
+    /// The internal timer can only be used with a single channel. To sample several channels at a
+    /// fixed rate, trigger the SAMPLE task from a TIMER through the PPI instead.
+    ///
+    /// Returns [`SaadcError::BufferTooLong`] without starting if the buffer holds more than
+    /// [`MAX_SAMPLES`] samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `CHANNELS` is not 1, or if `sample_rate_cc` is outside of `80..=2047`.
+    pub fn start_continuous(&mut self, sample_rate_cc: u16) -> Result<(), SaadcError> {
+        assert!(CHANNELS == 1);
+        assert!((80..=2047).contains(&sample_rate_cc));
+        if self.buffer.as_mut().len() > MAX_SAMPLES {
+            return Err(SaadcError::BufferTooLong);
+        }
+
+        let saadc = Self::ptr();
+        saadc
+            .samplerate
+            .write(|w| unsafe { w.cc().bits(sample_rate_cc).mode().timers() });
+        saadc.intenset.write(|w| w.end().set_bit());
+        self.restart_continuous()?;
+
+        // In timer mode, a single SAMPLE task starts the internal timer.
+        saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
+        Ok(())
     }
+
+    /// Points EasyDMA at the start of the buffer again after an END event in continuous mode.
+    ///
+    /// The samples taken between the END event and this call are lost. Returns
+    /// [`SaadcError::BufferTooLong`] if the buffer holds more than [`MAX_SAMPLES`] samples.
+    pub fn restart_continuous(&mut self) -> Result<(), SaadcError> {
+        let buffer = self.buffer.as_mut();
+        let (ptr, len) = (buffer.as_mut_ptr(), buffer.len());
+        if len > MAX_SAMPLES {
+            return Err(SaadcError::BufferTooLong);
+        }
+        let saadc = Self::ptr();
+        saadc.events_end.reset();
+        saadc
+            .result
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(ptr as u32) });
+        saadc
+            .result
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(len as u16) });
+        saadc.enable.write(|w| w.enable().set_bit());
+
+        // Conservative compiler fence to prevent starting the ADC before the
+        // pointer and maxcount have been set.
+        compiler_fence(SeqCst);
+
+        saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+        Ok(())
+    }
+
+    /// The samples written in continuous mode, valid after the END event.
+    pub fn continuous_samples(&self) -> &[u16] {
+        // Prevent optimizations creating issues with the EasyDMA-modified buffer.
+        compiler_fence(SeqCst);
+        self.buffer.as_ref()
+    }
+
+    /// Stops continuous sampling and returns to sampling on the SAMPLE task.
+    pub fn stop_continuous(&mut self) {
+        let saadc = Self::ptr();
+        saadc.intenclr.write(|w| w.end().set_bit());
+        saadc.samplerate.write(|w| w.mode().task());
+        Self::abort();
+    }
+
+    /// Stops any measurement, disables the SAADC and disconnects all channels, and returns the
+    /// low-level peripheral handle.
+    pub fn free(self) -> SAADC {
+        release(&self.saadc);
+        self.saadc
+    }
+
+    /// CPU cycles one measurement cycle takes with the configuration in the registers.
+    fn expected_cycles() -> u32 {
+        let saadc = Self::ptr();
+        let oversample = 1 << saadc.oversample.read().oversample().bits();
+        let us: u32 = saadc.ch[..CHANNELS]
+            .iter()
+            .map(|ch| {
+                let tacq = ACQUISITION_TIMES_US[ch.config.read().tacq().bits() as usize];
+                (tacq + CONVERSION_TIME_US) * oversample
+            })
+            .sum();
+        us.saturating_mul(HFCLK_FREQ / 1_000_000)
+    }
+
+    /// Stops a measurement cycle in progress and clears its events.
+    fn abort() {
+        let saadc = Self::ptr();
+        saadc.events_stopped.reset();
+        saadc.tasks_stop.write(|w| unsafe { w.bits(1) });
+        while saadc.events_stopped.read().bits() == 0 {}
+        saadc.events_stopped.reset();
+        saadc.events_started.reset();
+        saadc.events_end.reset();
+    }
+}
+
+/// Double-buffered SAADC sampling task for gap-free continuous acquisition.
+///
+/// EasyDMA fills one buffer while the CPU processes the other. The RESULT.PTR register is
+/// double-buffered: once a measurement cycle has STARTED, the pointer for the next one can be set,
+/// so [`DoubleBufferedSaadcTask::on_interrupt`] programs the other buffer on every STARTED event.
+/// The SAADC has no END to START shortcut, so a PPI channel restarts it in hardware on every END
+/// event, and the switch to the next buffer doesn't depend on the interrupt latency.
+///
+/// The measurements have to be triggered by the SAMPLE task, e.g. from a TIMER through the PPI.
+#[cfg(not(any(feature = "9160", feature = "5340-app")))]
+pub struct DoubleBufferedSaadcTask<const CHANNELS: usize, P> {
+    saadc: SAADC,
+    buffers: [&'static mut [u16; CHANNELS]; 2],
+    ppi: P,
+    /// Index of the buffer EasyDMA is currently writing to.
+    filling: usize,
+    /// Index of the last completed buffer, if it hasn't been fetched yet.
+    ready: Option<usize>,
+}
+
+#[cfg(not(any(feature = "9160", feature = "5340-app")))]
+impl<const CHANNELS: usize, P: ConfigurablePpi> DoubleBufferedSaadcTask<CHANNELS, P> {
+    /// Configures the SAADC to sample `channels` alternately into `first` and `second`, using
+    /// `ppi` to restart the SAADC on every END event.
+    ///
+    /// See [`SaadcTask::new`] for the format of `channels`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`SaadcTask::new`].
+    pub fn new(
+        saadc: SAADC,
+        config: SaadcConfig,
+        channels: &[u8; CHANNELS],
+        first: &'static mut [u16; CHANNELS],
+        second: &'static mut [u16; CHANNELS],
+        mut ppi: P,
+    ) -> Self {
+        configure_task(&saadc, config, channels, None);
+        ppi.set_event_endpoint(&saadc.events_end)
+            .set_task_endpoint(&saadc.tasks_start);
+        DoubleBufferedSaadcTask {
+            saadc,
+            buffers: [first, second],
+            ppi,
+            filling: 0,
+            ready: None,
+        }
+    }
+
+    #[inline(always)]
+    fn ptr<'a>() -> &'a saadc::RegisterBlock {
+        unsafe { &*SAADC::PTR }
+    }
+
+    /// Starts filling the first buffer.
+    ///
+    /// Note that the interrupt also has to be unmasked in the NVIC, or
+    /// [`DoubleBufferedSaadcTask::on_interrupt`] won't get called.
+    pub fn start(&mut self) {
+        let saadc = Self::ptr();
+        self.filling = 0;
+        self.ready = None;
+
+        saadc.events_started.reset();
+        saadc.events_end.reset();
+        self.set_ptr(0);
+        saadc
+            .result
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(CHANNELS as u16) });
+        saadc.enable.write(|w| w.enable().set_bit());
+        saadc
+            .intenset
+            .write(|w| w.started().set_bit().end().set_bit());
+        self.ppi.enable();
+
+        // Conservative compiler fence to prevent starting the ADC before the
+        // pointer and maxcount have been set.
+        compiler_fence(SeqCst);
+
+        saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Stops the acquisition. Samples of an incomplete measurement cycle are lost.
+    pub fn stop(&mut self) {
+        let saadc = Self::ptr();
+        self.ppi.disable();
+        saadc
+            .intenclr
+            .write(|w| w.started().set_bit().end().set_bit());
+        saadc.events_stopped.reset();
+        saadc.tasks_stop.write(|w| unsafe { w.bits(1) });
+        while saadc.events_stopped.read().bits() == 0 {}
+        saadc.events_stopped.reset();
+        saadc.events_started.reset();
+        saadc.events_end.reset();
+    }
+
+    /// Tracks the buffer switches. Call this from the interrupt handler of the SAADC.
+    ///
+    /// This has to run within one measurement cycle of the STARTED event, otherwise the PPI
+    /// restarts the SAADC into the same buffer again.
+    pub fn on_interrupt(&mut self) {
+        let saadc = Self::ptr();
+
+        // END of one cycle precedes the STARTED of the next, which the PPI triggers right away,
+        // so both can be pending at once and have to be handled in this order.
+        if saadc.events_end.read().bits() != 0 {
+            saadc.events_end.reset();
+            // The PPI has already restarted EasyDMA into the buffer programmed on STARTED.
+            self.ready = Some(self.filling);
+            self.filling ^= 1;
+        }
+
+        if saadc.events_started.read().bits() != 0 {
+            saadc.events_started.reset();
+            // RESULT.PTR has been latched, queue the other buffer for the next cycle.
+            self.set_ptr(self.filling ^ 1);
+        }
+    }
+
+    /// Returns the buffer completed last, once.
+    ///
+    /// The buffer stays valid until the other one is complete, i.e. for one measurement cycle.
+    pub fn get_ready_buffer(&mut self) -> Option<&[u16; CHANNELS]> {
+        let ready = self.ready.take()?;
+        // Prevent optimizations creating issues with the EasyDMA-modified buffer.
+        compiler_fence(SeqCst);
+        Some(&*self.buffers[ready])
+    }
+
+    /// Stops the acquisition, disables the SAADC and disconnects all channels, and returns the
+    /// low-level peripheral handle and the PPI channel.
+    pub fn free(mut self) -> (SAADC, P) {
+        self.ppi.disable();
+        release(&self.saadc);
+        (self.saadc, self.ppi)
+    }
+
+    fn set_ptr(&mut self, index: usize) {
+        let ptr = self.buffers[index].as_mut_ptr();
+        Self::ptr()
+            .result
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(ptr as u32) });
+    }
+}
+
+/// Stops a conversion in progress, waiting at most [`STOP_TIMEOUT_CYCLES`] for STOPPED.
+fn stop_conversion(saadc: &SAADC) {
+    saadc.events_stopped.reset();
+    saadc.tasks_stop.write(|w| unsafe { w.bits(1) });
+    // Wait for a conversion in progress to end, so EasyDMA doesn't write to a buffer that has
+    // already been handed back.
+    let mut waited: u32 = 0;
+    while saadc.events_stopped.read().bits() == 0 && waited < STOP_TIMEOUT_CYCLES {
+        cortex_m::asm::delay(POLL_CYCLES);
+        waited += POLL_CYCLES;
+    }
+    saadc.events_stopped.reset();
+}
+
+/// Stops the SAADC and returns it to its reset state, with all channels disconnected.
+fn release(saadc: &SAADC) {
+    saadc.intenclr.write(|w| unsafe { w.bits(0xFFFF_FFFF) });
+    stop_conversion(saadc);
+    saadc.events_started.reset();
+    saadc.events_end.reset();
+    saadc.enable.write(|w| w.enable().disabled());
+    saadc.samplerate.write(|w| w.mode().task());
+    for ch in saadc.ch.iter() {
+        ch.pselp.write(|w| w.pselp().nc());
+        ch.pseln.write(|w| w.pseln().nc());
+    }
+}
+
+/// Largest number of samples EasyDMA transfers in one go, the RESULT.MAXCNT field is 15 bits wide.
+pub const MAX_SAMPLES: usize = (1 << 15) - 1;
+
+/// CPU cycles between two polls of an event in the blocking operations with a timeout.
+const POLL_CYCLES: u32 = 64;
+
+/// CPU cycles after which [`Saadc::calibrate`] gives up.
+const CALIBRATION_TIMEOUT_CYCLES: u32 = HFCLK_FREQ / 1_000;
+
+/// CPU cycles after which stopping the SAADC gives up waiting for STOPPED.
+const STOP_TIMEOUT_CYCLES: u32 = HFCLK_FREQ / 1_000;
+
+/// Shortest timeout of [`SaadcTask::sample_blocking`] in CPU cycles.
+const SAMPLE_TIMEOUT_CYCLES: u32 = HFCLK_FREQ / 1_000;
+
+/// Waits for a calibration started by the CALIBRATEOFFSET task to finish and clears its event.
+fn wait_for_calibration(saadc: &SAADC) -> Result<(), SaadcError> {
+    let mut waited: u32 = 0;
+    while saadc.events_calibratedone.read().bits() == 0 {
+        if waited >= CALIBRATION_TIMEOUT_CYCLES {
+            return Err(SaadcError::CalibrationTimeout);
+        }
+        cortex_m::asm::delay(POLL_CYCLES);
+        waited += POLL_CYCLES;
+    }
+    saadc.events_calibratedone.reset();
+    Ok(())
 }
 
+/// Acquisition times in microseconds, indexed by the value of the TACQ field.
+const ACQUISITION_TIMES_US: [u32; 8] = [3, 5, 10, 15, 20, 40, 40, 40];
+
+/// Upper bound of the conversion time in microseconds, after the acquisition.
+const CONVERSION_TIME_US: u32 = 2;
+
 impl Saadc {
     pub fn new(saadc: SAADC, config: SaadcConfig) -> Self {
         // The write enums do not implement clone/copy/debug, only the
@@ -292,6 +808,7 @@ impl Saadc {
             reference,
             gain,
             resistor,
+            negative_resistor,
             time,
         } = config;
         saadc.resolution.write(|w| w.val().variant(resolution));
@@ -306,53 +823,199 @@ impl Saadc {
             w.tacq().variant(time);
             w.mode().se();
             w.resp().variant(resistor);
-            w.resn().bypass();
+            w.resn().variant(negative_resistor);
             w.burst().enabled();
             w
         });
         saadc.ch[0].pseln.write(|w| w.pseln().nc());
 
-        // Calibrate
-        saadc.events_calibratedone.reset();
-        saadc.tasks_calibrateoffset.write(|w| unsafe { w.bits(1) });
-        while saadc.events_calibratedone.read().bits() == 0 {}
+        let mut saadc = Saadc {
+            saadc,
+            converting: false,
+            calibrating: false,
+        };
+        // An uncalibrated SAADC still works, just with a larger offset error.
+        saadc.calibrate().ok();
+        saadc
+    }
+
+    /// Calibrates the offset of the SAADC.
+    ///
+    /// This is done once by [`Saadc::new`], but the offset drifts with temperature and supply
+    /// voltage, so long-running applications should recalibrate periodically, e.g. whenever the
+    /// temperature has changed by more than 10 °C.
+    ///
+    /// Returns [`SaadcError::CalibrationTimeout`] if the calibration does not finish within 1 ms.
+    /// Note that this is a blocking operation.
+    pub fn calibrate(&mut self) -> Result<(), SaadcError> {
+        self.finish_nb();
+        self.saadc.events_calibratedone.reset();
+        self.saadc
+            .tasks_calibrateoffset
+            .write(|w| unsafe { w.bits(1) });
+
+        wait_for_calibration(&self.saadc)
+    }
+
+    /// Calibrates the offset of the SAADC without blocking.
+    ///
+    /// The first call starts the calibration, and further calls return `WouldBlock` until it has
+    /// finished. Any other operation on the `Saadc` in the meantime waits for the calibration to
+    /// finish first.
+    pub fn calibrate_nb(&mut self) -> nb::Result<(), Void> {
+        if !self.calibrating {
+            self.finish_nb();
+            self.saadc.events_calibratedone.reset();
+            self.saadc
+                .tasks_calibrateoffset
+                .write(|w| unsafe { w.bits(1) });
+            self.calibrating = true;
+        }
 
-        Saadc(saadc)
+        if self.saadc.events_calibratedone.read().bits() == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+        self.saadc.events_calibratedone.reset();
+        self.calibrating = false;
+        Ok(())
     }
 
     /// Disable SAADC and return the low-level peripheral handle
-    pub fn free(self) -> SAADC {
-        self.0.enable.write(|w| w.enable().disabled());
-        self.0
+    pub fn free(mut self) -> SAADC {
+        self.finish_nb();
+        self.saadc.enable.write(|w| w.enable().disabled());
+        self.saadc
     }
 
-    /// Sample channel `PIN` for the configured ADC acquisition time in differential input mode.
+    /// Sample channel `PIN` for the configured ADC acquisition time in single-ended input mode.
     /// Note that this is a blocking operation.
-    pub fn read_channel<PIN: Channel>(&mut self, _pin: &mut PIN) -> Result<i16, ()> {
-        match PIN::channel() {
-            0 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input0()),
-            1 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input1()),
-            2 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input2()),
-            3 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input3()),
-            4 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input4()),
-            5 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input5()),
-            6 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input6()),
-            7 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input7()),
-            #[cfg(not(feature = "9160"))]
-            8 => self.0.ch[0].pselp.write(|w| w.pselp().vdd()),
-            #[cfg(any(feature = "52833", feature = "52840"))]
-            13 => self.0.ch[0].pselp.write(|w| w.pselp().vddhdiv5()),
-            // This can never happen with the `Channel` implementations provided, as the only analog
-            // pins have already been covered.
-            _ => return Err(()),
+    pub fn read_channel<PIN: Channel>(&mut self, _pin: &mut PIN) -> Result<i16, SaadcError> {
+        // This can never fail with the `Channel` implementations provided, as the only analog
+        // pins have already been covered.
+        let input = AnalogInput::try_from(PIN::channel())?;
+        self.read_input(input)
+    }
+
+    /// Sample the analog input `input` for the configured ADC acquisition time.
+    ///
+    /// Unlike [`Saadc::read_channel`], the input is not derived from a GPIO pin, which allows
+    /// boards to route their signals to any AINx input.
+    /// Note that this is a blocking operation.
+    pub fn read_input(&mut self, input: AnalogInput) -> Result<i16, SaadcError> {
+        self.finish_nb();
+        self.saadc.ch[0]
+            .pselp
+            .write(|w| w.pselp().variant(input.into()));
+        self.sample()
+    }
+
+    /// Sample the voltage between the channels `P` and `N` for the configured ADC acquisition
+    /// time in differential input mode.
+    ///
+    /// The result is positive if `P` is at a higher voltage than `N`. The resistor ladder of the
+    /// negative input is set by [`SaadcConfig::negative_resistor`].
+    /// Note that this is a blocking operation.
+    pub fn read_channel_differential<P: Channel, N: Channel>(
+        &mut self,
+        _pos: &mut P,
+        _neg: &mut N,
+    ) -> Result<i16, SaadcError> {
+        // This can never fail with the `Channel` implementations provided, as the only analog
+        // pins have already been covered.
+        let pos = AnalogInput::try_from(P::channel())?;
+        let neg = AnalogInput::try_from(N::channel())?;
+
+        self.finish_nb();
+
+        let ch = &self.saadc.ch[0];
+        ch.pselp.write(|w| w.pselp().variant(pos.into()));
+        ch.pseln.write(|w| w.pseln().variant(neg.into()));
+        ch.config.modify(|_, w| w.mode().diff());
+
+        let result = self.sample();
+
+        let ch = &self.saadc.ch[0];
+        ch.config.modify(|_, w| w.mode().se());
+        ch.pseln.write(|w| w.pseln().nc());
+
+        result
+    }
+
+    /// Sample channel `PIN` for the configured ADC acquisition time in single-ended input mode,
+    /// without blocking.
+    ///
+    /// The first call starts the conversion, and further calls return `WouldBlock` until it has
+    /// finished. Keep calling with the same pin until the result is returned; a conversion in
+    /// flight is not restarted for a different pin.
+    pub fn read_channel_nb<PIN: Channel>(&mut self, _pin: &mut PIN) -> nb::Result<i16, SaadcError> {
+        if !self.converting {
+            self.finish_nb();
+            // This can never fail with the `Channel` implementations provided, as the only
+            // analog pins have already been covered.
+            let input = AnalogInput::try_from(PIN::channel())?;
+            self.saadc.ch[0]
+                .pselp
+                .write(|w| w.pselp().variant(input.into()));
+            self.saadc
+                .result
+                .ptr
+                .write(|w| unsafe { w.ptr().bits(core::ptr::addr_of_mut!(NB_RESULT) as u32) });
+            self.saadc
+                .result
+                .maxcnt
+                .write(|w| unsafe { w.maxcnt().bits(1) });
+
+            // Conservative compiler fence to prevent starting the ADC before the
+            // pointer and maxcount have been set.
+            compiler_fence(SeqCst);
+
+            self.saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+            self.saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
+            self.converting = true;
+        }
+
+        if self.saadc.events_end.read().bits() == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+        self.saadc.events_end.reset();
+        self.converting = false;
+
+        // Will only occur if more than one channel has been enabled.
+        if self.saadc.result.amount.read().bits() != 1 {
+            return Err(nb::Error::Other(SaadcError::TooManyResults));
+        }
+
+        // Second fence to prevent optimizations creating issues with the EasyDMA-modified result.
+        compiler_fence(SeqCst);
+
+        Ok(unsafe { core::ptr::addr_of!(NB_RESULT).read_volatile() })
+    }
+
+    /// Stops a conversion started by [`Saadc::read_channel_nb`], discarding its result, and waits
+    /// for a calibration started by [`Saadc::calibrate_nb`].
+    ///
+    /// Both waits are bounded, like those of the blocking operations.
+    fn finish_nb(&mut self) {
+        if self.calibrating {
+            // A calibration that doesn't finish in time only leaves the offset uncalibrated.
+            let _ = wait_for_calibration(&self.saadc);
+            self.calibrating = false;
+        }
+        if self.converting {
+            stop_conversion(&self.saadc);
+            self.saadc.events_end.reset();
+            self.converting = false;
         }
+    }
 
+    /// Runs a single conversion of channel 0.
+    fn sample(&mut self) -> Result<i16, SaadcError> {
         let mut val: i16 = 0;
-        self.0
+        self.saadc
             .result
             .ptr
             .write(|w| unsafe { w.ptr().bits(((&mut val) as *mut _) as u32) });
-        self.0
+        self.saadc
             .result
             .maxcnt
             .write(|w| unsafe { w.maxcnt().bits(1) });
@@ -361,15 +1024,15 @@ impl Saadc {
         // pointer and maxcount have been set.
         compiler_fence(SeqCst);
 
-        self.0.tasks_start.write(|w| unsafe { w.bits(1) });
-        self.0.tasks_sample.write(|w| unsafe { w.bits(1) });
+        self.saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+        self.saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
 
-        while self.0.events_end.read().bits() == 0 {}
-        self.0.events_end.reset();
+        while self.saadc.events_end.read().bits() == 0 {}
+        self.saadc.events_end.reset();
 
         // Will only occur if more than one channel has been enabled.
-        if self.0.result.amount.read().bits() != 1 {
-            return Err(());
+        if self.saadc.result.amount.read().bits() != 1 {
+            return Err(SaadcError::TooManyResults);
         }
 
         // Second fence to prevent optimizations creating issues with the EasyDMA-modified `val`.
@@ -377,6 +1040,321 @@ impl Saadc {
 
         Ok(val)
     }
+
+    /// Sample channel `PIN` twice and only accept the result if both samples agree.
+    ///
+    /// Returns the first sample if the two samples differ by at most `tolerance` codes, and
+    /// [`SaadcError::Inconsistent`] otherwise. This guards safety-relevant measurements against
+    /// transient glitches, at the cost of a second conversion: the call takes twice the configured
+    /// acquisition and conversion time (including oversampling) of [`Saadc::read_channel`].
+    /// Note that this is a blocking operation.
+    pub fn read_channel_verified<PIN: Channel>(
+        &mut self,
+        pin: &mut PIN,
+        tolerance: u16,
+    ) -> Result<i16, SaadcError> {
+        let first = self.read_channel(pin)?;
+        let second = self.read_channel(pin)?;
+
+        if (i32::from(first) - i32::from(second)).unsigned_abs() > u32::from(tolerance) {
+            return Err(SaadcError::Inconsistent { first, second });
+        }
+
+        Ok(first)
+    }
+
+    /// Sample all `inputs` in a single scan, using the configured ADC acquisition time for each.
+    ///
+    /// The inputs are assigned to SAADC channels 0 to `N - 1` in the order given, and the results
+    /// are returned in the same order. Use [`AnalogInput::of`] to get the input of a GPIO pin.
+    ///
+    /// Oversampling is not supported in scan mode, so this returns
+    /// [`SaadcError::OversampleInScan`] if more than one input is given and the `Saadc` was
+    /// configured with an oversample other than [`Oversample::BYPASS`].
+    /// Note that this is a blocking operation.
+    ///
+    /// Passing no inputs at all is rejected at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than 8 inputs are given, the number of SAADC channels.
+    pub fn read_channels<const N: usize>(
+        &mut self,
+        inputs: [AnalogInput; N],
+    ) -> Result<[i16; N], SaadcError> {
+        let () = ScanLen::<N>::NOT_EMPTY;
+        assert!(N <= self.saadc.ch.len());
+
+        self.finish_nb();
+
+        check_oversample(N, self.saadc.oversample.read().oversample().is_bypass())?;
+
+        // All channels share the configuration of channel 0.
+        let config = self.saadc.ch[0].config.read().bits();
+        for (ch, input) in self.saadc.ch.iter().zip(inputs.iter()) {
+            ch.config.write(|w| unsafe { w.bits(config) });
+            ch.pseln.write(|w| w.pseln().nc());
+            ch.pselp.write(|w| w.pselp().variant((*input).into()));
+        }
+
+        let mut val = [0i16; N];
+        self.saadc
+            .result
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(val.as_mut_ptr() as u32) });
+        self.saadc
+            .result
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(N as _) });
+
+        // Conservative compiler fence to prevent starting the ADC before the
+        // pointer and maxcount have been set.
+        compiler_fence(SeqCst);
+
+        self.saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+        self.saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
+
+        while self.saadc.events_end.read().bits() == 0 {}
+        self.saadc.events_end.reset();
+
+        // Disconnect all but channel 0 again, so single reads keep working.
+        for ch in self.saadc.ch.iter().take(N).skip(1) {
+            ch.pselp.write(|w| w.pselp().nc());
+        }
+
+        if self.saadc.result.amount.read().bits() != N as u32 {
+            return Err(SaadcError::TooManyResults);
+        }
+
+        // Second fence to prevent optimizations creating issues with the EasyDMA-modified `val`.
+        compiler_fence(SeqCst);
+
+        Ok(val)
+    }
+
+    /// Sets the limits of SAADC channel `channel` for event monitoring.
+    ///
+    /// After each conversion of the channel, the [`Limit::High`] event is triggered if the result
+    /// is equal to or above `high`, and the [`Limit::Low`] event if it is equal to or below `low`.
+    /// [`Saadc::read_channel`] and the other single reads use channel 0, [`Saadc::read_channels`]
+    /// uses channels 0 to `N - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not below 8, or if `low` is above `high`.
+    pub fn set_limits(&mut self, channel: u8, low: i16, high: i16) {
+        assert!(low <= high);
+        let bits = u32::from(high as u16) << 16 | u32::from(low as u16);
+        self.saadc.ch[usize::from(channel)]
+            .limit
+            .write(|w| unsafe { w.bits(bits) });
+    }
+
+    /// Enables the interrupt for the `limit` event of SAADC channel `channel`.
+    pub fn enable_limit_interrupt(&mut self, channel: u8, limit: Limit) {
+        self.saadc
+            .intenset
+            .write(|w| unsafe { w.bits(limit_mask(channel, limit)) });
+    }
+
+    /// Disables the interrupt for the `limit` event of SAADC channel `channel`.
+    pub fn disable_limit_interrupt(&mut self, channel: u8, limit: Limit) {
+        self.saadc
+            .intenclr
+            .write(|w| unsafe { w.bits(limit_mask(channel, limit)) });
+    }
+
+    /// Checks if the `limit` event of SAADC channel `channel` has been triggered.
+    pub fn is_limit_triggered(&self, channel: u8, limit: Limit) -> bool {
+        let event = &self.saadc.events_ch[usize::from(channel)];
+        match limit {
+            Limit::High => event.limith.read().bits() != 0,
+            Limit::Low => event.limitl.read().bits() != 0,
+        }
+    }
+
+    /// Resets the `limit` event of SAADC channel `channel`.
+    pub fn reset_limit_event(&mut self, channel: u8, limit: Limit) {
+        let event = &self.saadc.events_ch[usize::from(channel)];
+        match limit {
+            Limit::High => event.limith.reset(),
+            Limit::Low => event.limitl.reset(),
+        }
+    }
+}
+
+/// Limit events of a SAADC channel, see [`Saadc::set_limits`].
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Limit {
+    /// The last result was equal to or above the high limit.
+    High,
+    /// The last result was equal to or below the low limit.
+    Low,
+}
+
+/// The INTEN bit of the `limit` event of SAADC channel `channel`.
+fn limit_mask(channel: u8, limit: Limit) -> u32 {
+    assert!(channel < 8);
+    let bit = 6 + 2 * u32::from(channel);
+    match limit {
+        Limit::High => 1 << bit,
+        Limit::Low => 1 << (bit + 1),
+    }
+}
+
+/// A single result of a [`SaadcTask`] measurement cycle.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Sample<T> {
+    /// Index of the channel in the list the task was created with.
+    pub channel: usize,
+    /// The raw two's complement result as written by the SAADC.
+    pub raw: i16,
+    /// The result of the conversion callback.
+    pub value: T,
+}
+
+/// Errors reported by the SAADC.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SaadcError {
+    /// The SAADC wrote a different number of results than expected, e.g. because more channels
+    /// were enabled than the result buffer has room for.
+    TooManyResults,
+    /// The offset calibration did not finish in time.
+    CalibrationTimeout,
+    /// The channel ID does not belong to an analog input of the SAADC.
+    InvalidChannel,
+    /// The measurement did not finish in time.
+    SampleTimeout,
+    /// Two samples of the same input differ by more than the accepted tolerance.
+    Inconsistent { first: i16, second: i16 },
+    /// Oversampling was requested with more than one channel, which the SAADC does not support.
+    OversampleInScan,
+    /// The buffer holds more samples than EasyDMA can transfer in one go, see [`MAX_SAMPLES`].
+    BufferTooLong,
+}
+
+/// Analog inputs of the SAADC, independent of the GPIO pins they are bonded to.
+///
+/// Converts from and to the `u8` IDs used by the [`Channel`] trait and the channel lists of
+/// [`SaadcTask`].
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AnalogInput {
+    Ain0,
+    Ain1,
+    Ain2,
+    Ain3,
+    Ain4,
+    Ain5,
+    Ain6,
+    Ain7,
+    /// The internal VDD voltage.
+    #[cfg(not(feature = "9160"))]
+    Vdd,
+    /// The GPIO supply voltage, VDD_GPIO. Only the nRF9160 has this input in place of VDD.
+    #[cfg(feature = "9160")]
+    VddGpio,
+    /// The voltage on the VDDH pin, divided by 5.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    VddhDiv5,
+}
+
+impl AnalogInput {
+    /// Returns the analog input the GPIO pin `PIN` is bonded to.
+    pub fn of<PIN: Channel>(_pin: &PIN) -> Self {
+        // The `Channel` implementations provided only cover valid analog inputs.
+        AnalogInput::try_from(PIN::channel()).unwrap()
+    }
+}
+
+impl From<AnalogInput> for u8 {
+    fn from(variant: AnalogInput) -> Self {
+        match variant {
+            AnalogInput::Ain0 => 0,
+            AnalogInput::Ain1 => 1,
+            AnalogInput::Ain2 => 2,
+            AnalogInput::Ain3 => 3,
+            AnalogInput::Ain4 => 4,
+            AnalogInput::Ain5 => 5,
+            AnalogInput::Ain6 => 6,
+            AnalogInput::Ain7 => 7,
+            #[cfg(not(feature = "9160"))]
+            AnalogInput::Vdd => 8,
+            #[cfg(feature = "9160")]
+            AnalogInput::VddGpio => 8,
+            #[cfg(any(feature = "52833", feature = "52840"))]
+            AnalogInput::VddhDiv5 => 13,
+        }
+    }
+}
+
+impl TryFrom<u8> for AnalogInput {
+    type Error = SaadcError;
+
+    fn try_from(channel: u8) -> Result<Self, Self::Error> {
+        match channel {
+            0 => Ok(AnalogInput::Ain0),
+            1 => Ok(AnalogInput::Ain1),
+            2 => Ok(AnalogInput::Ain2),
+            3 => Ok(AnalogInput::Ain3),
+            4 => Ok(AnalogInput::Ain4),
+            5 => Ok(AnalogInput::Ain5),
+            6 => Ok(AnalogInput::Ain6),
+            7 => Ok(AnalogInput::Ain7),
+            #[cfg(not(feature = "9160"))]
+            8 => Ok(AnalogInput::Vdd),
+            #[cfg(feature = "9160")]
+            8 => Ok(AnalogInput::VddGpio),
+            #[cfg(any(feature = "52833", feature = "52840"))]
+            13 => Ok(AnalogInput::VddhDiv5),
+            _ => Err(SaadcError::InvalidChannel),
+        }
+    }
+}
+
+impl From<AnalogInput> for PSELN_A {
+    fn from(variant: AnalogInput) -> Self {
+        match variant {
+            AnalogInput::Ain0 => PSELN_A::ANALOG_INPUT0,
+            AnalogInput::Ain1 => PSELN_A::ANALOG_INPUT1,
+            AnalogInput::Ain2 => PSELN_A::ANALOG_INPUT2,
+            AnalogInput::Ain3 => PSELN_A::ANALOG_INPUT3,
+            AnalogInput::Ain4 => PSELN_A::ANALOG_INPUT4,
+            AnalogInput::Ain5 => PSELN_A::ANALOG_INPUT5,
+            AnalogInput::Ain6 => PSELN_A::ANALOG_INPUT6,
+            AnalogInput::Ain7 => PSELN_A::ANALOG_INPUT7,
+            #[cfg(not(feature = "9160"))]
+            AnalogInput::Vdd => PSELN_A::VDD,
+            #[cfg(feature = "9160")]
+            AnalogInput::VddGpio => PSELN_A::VDD_GPIO,
+            #[cfg(any(feature = "52833", feature = "52840"))]
+            AnalogInput::VddhDiv5 => PSELN_A::VDDHDIV5,
+        }
+    }
+}
+
+impl From<AnalogInput> for PSELP_A {
+    fn from(variant: AnalogInput) -> Self {
+        match variant {
+            AnalogInput::Ain0 => PSELP_A::ANALOG_INPUT0,
+            AnalogInput::Ain1 => PSELP_A::ANALOG_INPUT1,
+            AnalogInput::Ain2 => PSELP_A::ANALOG_INPUT2,
+            AnalogInput::Ain3 => PSELP_A::ANALOG_INPUT3,
+            AnalogInput::Ain4 => PSELP_A::ANALOG_INPUT4,
+            AnalogInput::Ain5 => PSELP_A::ANALOG_INPUT5,
+            AnalogInput::Ain6 => PSELP_A::ANALOG_INPUT6,
+            AnalogInput::Ain7 => PSELP_A::ANALOG_INPUT7,
+            #[cfg(not(feature = "9160"))]
+            AnalogInput::Vdd => PSELP_A::VDD,
+            #[cfg(feature = "9160")]
+            AnalogInput::VddGpio => PSELP_A::VDD_GPIO,
+            #[cfg(any(feature = "52833", feature = "52840"))]
+            AnalogInput::VddhDiv5 => PSELP_A::VDDHDIV5,
+        }
+    }
 }
 
 /// Used to configure the SAADC peripheral.
@@ -393,10 +1371,32 @@ pub struct SaadcConfig {
     pub gain: Gain,
     /// Positive channel resistor control.
     pub resistor: Resistor,
+    /// Negative channel resistor control, only used in differential mode.
+    pub negative_resistor: NegativeResistor,
     /// Acquisition time in microseconds.
     pub time: Time,
 }
 
+/// Per-channel settings of a [`SaadcTask`], see [`SaadcTask::with_channel_configs`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ChannelConfig {
+    /// Reference voltage of the channel input.
+    pub reference: Reference,
+    /// Gain used to control the effective input range of the channel.
+    pub gain: Gain,
+    /// Positive channel resistor control.
+    pub resistor: Resistor,
+    /// Acquisition time in microseconds.
+    pub time: Time,
+}
+
+/// The channel settings of [`SaadcConfig::default`].
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        SaadcConfig::default().channel_config()
+    }
+}
+
 /// Default SAADC configuration. 0 volts reads as 0, VDD volts reads as `u16::MAX`.
 /// The returned SaadcConfig is configured with the following values:
 ///
@@ -405,7 +1405,10 @@ pub struct SaadcConfig {
 /// # use nrf_hal_common::saadc::SaadcConfig;
 /// # use nrf_hal_common::pac::{saadc, SAADC};
 /// # use saadc::{
-/// #    ch::config::{GAIN_A as Gain, REFSEL_A as Reference, RESP_A as Resistor, TACQ_A as Time},
+/// #    ch::config::{
+/// #        GAIN_A as Gain, REFSEL_A as Reference, RESN_A as NegativeResistor, RESP_A as Resistor,
+/// #        TACQ_A as Time,
+/// #    },
 /// #    oversample::OVERSAMPLE_A as Oversample,
 /// #    resolution::VAL_A as Resolution,
 /// # };
@@ -416,6 +1419,7 @@ pub struct SaadcConfig {
 ///     reference: Reference::VDD1_4,
 ///     gain: Gain::GAIN1_4,
 ///     resistor: Resistor::BYPASS,
+///     negative_resistor: NegativeResistor::BYPASS,
 ///     time: Time::_20US,
 /// };
 /// #
@@ -426,6 +1430,7 @@ pub struct SaadcConfig {
 /// # assert_eq!(saadc.reference, test_saadc.reference);
 /// # assert_eq!(saadc.gain, test_saadc.gain);
 /// # assert_eq!(saadc.resistor, test_saadc.resistor);
+/// # assert_eq!(saadc.negative_resistor, test_saadc.negative_resistor);
 /// # assert_eq!(saadc.time, test_saadc.time);
 /// # ()
 /// ```
@@ -438,20 +1443,382 @@ impl Default for SaadcConfig {
             reference: Reference::VDD1_4,
             gain: Gain::GAIN1_4,
             resistor: Resistor::BYPASS,
+            negative_resistor: NegativeResistor::BYPASS,
             time: Time::_20US,
         }
     }
 }
 
+/// Gains in order of decreasing input range, with their full-scale input voltage when used with
+/// the internal 0.6 V reference.
+const INTERNAL_REFERENCE_RANGES: [(Gain, f32); 8] = [
+    (Gain::GAIN1_6, 3.6),
+    (Gain::GAIN1_5, 3.0),
+    (Gain::GAIN1_4, 2.4),
+    (Gain::GAIN1_3, 1.8),
+    (Gain::GAIN1_2, 1.2),
+    (Gain::GAIN1, 0.6),
+    (Gain::GAIN2, 0.3),
+    (Gain::GAIN4, 0.15),
+];
+
+impl SaadcConfig {
+    /// Returns a configuration whose input range just covers `0..=max_volts`.
+    ///
+    /// Selects the internal 0.6 V reference and the highest gain whose full-scale input is at
+    /// least `max_volts`, so that the signal does not clip while using as much of the range as
+    /// possible. Inputs above 3.6 V cannot be covered and get the widest range, 1/6 gain. All
+    /// other settings are taken from [`SaadcConfig::default`].
+    ///
+    #[cfg_attr(feature = "52840", doc = "```")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common::saadc::{Gain, Reference, SaadcConfig};
+    /// let config = SaadcConfig::for_input_range(3.3);
+    /// assert_eq!(config.reference, Reference::INTERNAL);
+    /// assert_eq!(config.gain, Gain::GAIN1_6);
+    /// #
+    /// # // the chosen range covers the input without excessive attenuation
+    /// # for &max in &[
+    /// #     0.01, 0.1, 0.15, 0.2, 0.3, 0.5, 0.6, 1.0, 1.2, 1.5, 1.8, 2.0, 2.4, 3.0, 3.3, 3.6,
+    /// # ] {
+    /// #     let range = SaadcConfig::for_input_range(max).input_range().unwrap();
+    /// #     assert!(range >= max);
+    /// #     assert!(max <= 0.15 || range < 2.0 * max);
+    /// # }
+    /// # assert_eq!(SaadcConfig::for_input_range(0.3).gain, Gain::GAIN2);
+    /// # assert_eq!(SaadcConfig::for_input_range(0.31).gain, Gain::GAIN1);
+    /// # assert_eq!(SaadcConfig::for_input_range(5.0).gain, Gain::GAIN1_6);
+    /// ```
+    pub fn for_input_range(max_volts: f32) -> SaadcConfig {
+        let gain = INTERNAL_REFERENCE_RANGES
+            .iter()
+            .rev()
+            .find(|(_, range)| *range >= max_volts)
+            .map_or(Gain::GAIN1_6, |(gain, _)| *gain);
+
+        SaadcConfig {
+            reference: Reference::INTERNAL,
+            gain,
+            ..SaadcConfig::default()
+        }
+    }
+
+    /// Returns the full-scale input voltage of this configuration.
+    ///
+    /// Only known for the internal reference; returns `None` for [`Reference::VDD1_4`], whose range
+    /// depends on the supply voltage.
+    pub fn input_range(&self) -> Option<f32> {
+        if self.reference != Reference::INTERNAL {
+            return None;
+        }
+        INTERNAL_REFERENCE_RANGES
+            .iter()
+            .find(|(gain, _)| *gain == self.gain)
+            .map(|(_, range)| *range)
+    }
+
+    /// Returns the worst-case time in microseconds needed to produce one result with this
+    /// configuration.
+    ///
+    /// Each input sample takes the acquisition time plus up to 2 µs of conversion time, and with
+    /// oversampling one result takes 2^`oversample` input samples. When sampling continuously from
+    /// the internal timer, the sample period (`CC` / 16 MHz) has to be at least this long to avoid
+    /// overruns.
+    ///
+    #[cfg_attr(feature = "52840", doc = "```")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common::saadc::{Oversample, SaadcConfig, Time};
+    /// let config = SaadcConfig::builder()
+    ///     .time(Time::_10US)
+    ///     .oversample(Oversample::OVER4X)
+    ///     .build();
+    /// assert_eq!(config.conversion_time_us(), 48);
+    /// #
+    /// # assert_eq!(SaadcConfig::default().conversion_time_us(), 176);
+    /// # let config = SaadcConfig::builder()
+    /// #     .time(Time::_3US)
+    /// #     .oversample(Oversample::BYPASS)
+    /// #     .build();
+    /// # assert_eq!(config.conversion_time_us(), 5);
+    /// ```
+    pub fn conversion_time_us(&self) -> u32 {
+        let tacq = ACQUISITION_TIMES_US[u8::from(self.time) as usize];
+        (tacq + CONVERSION_TIME_US) << u8::from(self.oversample)
+    }
+
+    /// Returns a builder starting from [`SaadcConfig::default`], to override individual settings.
+    ///
+    #[cfg_attr(feature = "52840", doc = "```")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common::saadc::{Gain, Oversample, Reference, SaadcConfig};
+    /// let config = SaadcConfig::builder()
+    ///     .reference(Reference::INTERNAL)
+    ///     .gain(Gain::GAIN1_6)
+    ///     .build();
+    /// #
+    /// # assert_eq!(config.reference, Reference::INTERNAL);
+    /// # assert_eq!(config.gain, Gain::GAIN1_6);
+    /// # assert_eq!(config.oversample, SaadcConfig::default().oversample);
+    /// # let config = SaadcConfig::builder().oversample(Oversample::BYPASS).build();
+    /// # assert_eq!(config.oversample, Oversample::BYPASS);
+    /// # assert_eq!(config.reference, SaadcConfig::default().reference);
+    /// ```
+    pub fn builder() -> SaadcConfigBuilder {
+        SaadcConfigBuilder(SaadcConfig::default())
+    }
+
+    /// Returns the per-channel part of this configuration.
+    pub fn channel_config(&self) -> ChannelConfig {
+        ChannelConfig {
+            reference: self.reference,
+            gain: self.gain,
+            resistor: self.resistor,
+            time: self.time,
+        }
+    }
+
+    /// Converts a raw single-ended result into millivolts.
+    ///
+    /// Accounts for the reference, gain and resolution of this configuration. The
+    /// [`Reference::VDD1_4`] reference is assumed to be at its nominal 3.0 V supply; use
+    /// [`SaadcConfig::raw_to_millivolts_with_vdd`] if the supply voltage is known. The result is
+    /// truncated towards zero.
+    ///
+    #[cfg_attr(feature = "52840", doc = "```")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common::saadc::{Gain, Reference, Resolution, SaadcConfig};
+    /// // VDD/4 reference with 1/4 gain: the full scale is VDD
+    /// let config = SaadcConfig::default();
+    /// assert_eq!(config.raw_to_millivolts(8192), 1500);
+    /// #
+    /// # assert_eq!(config.raw_to_millivolts(0), 0);
+    /// # assert_eq!(config.raw_to_millivolts(16384), 3000);
+    /// # assert_eq!(config.raw_to_millivolts(-16), -2);
+    /// # assert_eq!(config.raw_to_millivolts_with_vdd(16384, 1800), 1800);
+    /// # let internal = |gain, resolution| SaadcConfig {
+    /// #     reference: Reference::INTERNAL,
+    /// #     gain,
+    /// #     resolution,
+    /// #     ..SaadcConfig::default()
+    /// # };
+    /// # assert_eq!(internal(Gain::GAIN1_6, Resolution::_12BIT).raw_to_millivolts(4095), 3599);
+    /// # assert_eq!(internal(Gain::GAIN1_6, Resolution::_12BIT).raw_to_millivolts(2048), 1800);
+    /// # assert_eq!(internal(Gain::GAIN1, Resolution::_10BIT).raw_to_millivolts(512), 300);
+    /// # assert_eq!(internal(Gain::GAIN4, Resolution::_8BIT).raw_to_millivolts(128), 75);
+    /// # assert_eq!(internal(Gain::GAIN1_3, Resolution::_14BIT).raw_to_millivolts(8192), 900);
+    /// ```
+    pub fn raw_to_millivolts(&self, raw: i16) -> i32 {
+        self.raw_to_millivolts_with_vdd(raw, NOMINAL_VDD_MV)
+    }
+
+    /// Converts a raw single-ended result into millivolts, given a supply voltage of `vdd_mv`.
+    ///
+    /// The supply voltage only matters for the [`Reference::VDD1_4`] reference, see
+    /// [`SaadcConfig::raw_to_millivolts`].
+    pub fn raw_to_millivolts_with_vdd(&self, raw: i16, vdd_mv: u32) -> i32 {
+        let reference_mv: i64 = match self.reference {
+            Reference::INTERNAL => 600,
+            Reference::VDD1_4 => i64::from(vdd_mv) / 4,
+        };
+        // Gain as a fraction `(numerator, denominator)`.
+        let (num, den): (i64, i64) = match self.gain {
+            Gain::GAIN1_6 => (1, 6),
+            Gain::GAIN1_5 => (1, 5),
+            Gain::GAIN1_4 => (1, 4),
+            Gain::GAIN1_3 => (1, 3),
+            Gain::GAIN1_2 => (1, 2),
+            Gain::GAIN1 => (1, 1),
+            Gain::GAIN2 => (2, 1),
+            Gain::GAIN4 => (4, 1),
+        };
+        let bits = match self.resolution {
+            Resolution::_8BIT => 8,
+            Resolution::_10BIT => 10,
+            Resolution::_12BIT => 12,
+            Resolution::_14BIT => 14,
+        };
+
+        (i64::from(raw) * reference_mv * den / (num << bits)) as i32
+    }
+}
+
+/// Builder for a [`SaadcConfig`], see [`SaadcConfig::builder`].
+pub struct SaadcConfigBuilder(SaadcConfig);
+
+impl SaadcConfigBuilder {
+    /// Sets the output resolution.
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        self.0.resolution = resolution;
+        self
+    }
+
+    /// Sets the oversampling.
+    pub fn oversample(mut self, oversample: Oversample) -> Self {
+        self.0.oversample = oversample;
+        self
+    }
+
+    /// Sets the reference voltage.
+    pub fn reference(mut self, reference: Reference) -> Self {
+        self.0.reference = reference;
+        self
+    }
+
+    /// Sets the gain.
+    pub fn gain(mut self, gain: Gain) -> Self {
+        self.0.gain = gain;
+        self
+    }
+
+    /// Sets the positive channel resistor control.
+    pub fn resistor(mut self, resistor: Resistor) -> Self {
+        self.0.resistor = resistor;
+        self
+    }
+
+    /// Sets the negative channel resistor control.
+    pub fn negative_resistor(mut self, negative_resistor: NegativeResistor) -> Self {
+        self.0.negative_resistor = negative_resistor;
+        self
+    }
+
+    /// Sets the acquisition time.
+    pub fn time(mut self, time: Time) -> Self {
+        self.0.time = time;
+        self
+    }
+
+    /// Returns the configuration.
+    pub fn build(self) -> SaadcConfig {
+        self.0
+    }
+}
+
+/// Nominal supply voltage assumed by [`SaadcConfig::raw_to_millivolts`].
+const NOMINAL_VDD_MV: u32 = 3000;
+
+// The PAC enums don't implement `defmt::Format`, so the configurations are logged with the names
+// of their variants.
+#[cfg(feature = "defmt")]
+impl defmt::Format for SaadcConfig {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "SaadcConfig {{ resolution: {=str}, oversample: {=str}, reference: {=str}, gain: {=str}, resistor: {=str}, negative_resistor: {=str}, time: {=str} }}",
+            resolution_name(self.resolution),
+            oversample_name(self.oversample),
+            reference_name(self.reference),
+            gain_name(self.gain),
+            resistor_name(self.resistor),
+            negative_resistor_name(self.negative_resistor),
+            time_name(self.time),
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ChannelConfig {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "ChannelConfig {{ reference: {=str}, gain: {=str}, resistor: {=str}, time: {=str} }}",
+            reference_name(self.reference),
+            gain_name(self.gain),
+            resistor_name(self.resistor),
+            time_name(self.time),
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn resolution_name(resolution: Resolution) -> &'static str {
+    match resolution {
+        Resolution::_8BIT => "8 bit",
+        Resolution::_10BIT => "10 bit",
+        Resolution::_12BIT => "12 bit",
+        Resolution::_14BIT => "14 bit",
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn oversample_name(oversample: Oversample) -> &'static str {
+    match oversample {
+        Oversample::BYPASS => "bypass",
+        Oversample::OVER2X => "2x",
+        Oversample::OVER4X => "4x",
+        Oversample::OVER8X => "8x",
+        Oversample::OVER16X => "16x",
+        Oversample::OVER32X => "32x",
+        Oversample::OVER64X => "64x",
+        Oversample::OVER128X => "128x",
+        Oversample::OVER256X => "256x",
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn reference_name(reference: Reference) -> &'static str {
+    match reference {
+        Reference::INTERNAL => "internal",
+        Reference::VDD1_4 => "VDD/4",
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn gain_name(gain: Gain) -> &'static str {
+    match gain {
+        Gain::GAIN1_6 => "1/6",
+        Gain::GAIN1_5 => "1/5",
+        Gain::GAIN1_4 => "1/4",
+        Gain::GAIN1_3 => "1/3",
+        Gain::GAIN1_2 => "1/2",
+        Gain::GAIN1 => "1",
+        Gain::GAIN2 => "2",
+        Gain::GAIN4 => "4",
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn resistor_name(resistor: Resistor) -> &'static str {
+    match resistor {
+        Resistor::BYPASS => "bypass",
+        Resistor::PULLDOWN => "pull-down",
+        Resistor::PULLUP => "pull-up",
+        Resistor::VDD1_2 => "VDD/2",
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn negative_resistor_name(resistor: NegativeResistor) -> &'static str {
+    match resistor {
+        NegativeResistor::BYPASS => "bypass",
+        NegativeResistor::PULLDOWN => "pull-down",
+        NegativeResistor::PULLUP => "pull-up",
+        NegativeResistor::VDD1_2 => "VDD/2",
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn time_name(time: Time) -> &'static str {
+    match time {
+        Time::_3US => "3 us",
+        Time::_5US => "5 us",
+        Time::_10US => "10 us",
+        Time::_15US => "15 us",
+        Time::_20US => "20 us",
+        Time::_40US => "40 us",
+    }
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<PIN> embedded_hal_02::adc::OneShot<Saadc, i16, PIN> for Saadc
 where
     PIN: Channel,
 {
-    type Error = ();
+    type Error = SaadcError;
 
-    /// Sample channel `PIN` for the configured ADC acquisition time in differential input mode.
-    /// Note that this is a blocking operation.
+    /// Sample channel `PIN` for the configured ADC acquisition time in single-ended input mode.
+    /// Note that this is a blocking operation, unlike [`Saadc::read_channel_nb`] which should be
+    /// preferred in new code.
     fn read(&mut self, pin: &mut PIN) -> nb::Result<i16, Self::Error> {
         Ok(self.read_channel(pin)?)
     }
@@ -524,6 +1891,29 @@ impl Channel for InternalVdd {
 /// Channel that doesn't sample a pin, but the internal VDD voltage.
 pub struct InternalVdd;
 
+#[cfg(all(feature = "9160", feature = "embedded-hal-02"))]
+impl embedded_hal_02::adc::Channel<Saadc> for InternalVddGpio {
+    type ID = u8;
+
+    fn channel() -> u8 {
+        8
+    }
+}
+
+#[cfg(feature = "9160")]
+impl Channel for InternalVddGpio {
+    #[cfg(not(feature = "embedded-hal-02"))]
+    fn channel() -> u8 {
+        8
+    }
+}
+
+#[cfg(feature = "9160")]
+/// Channel that doesn't sample a pin, but the GPIO supply voltage VDD_GPIO.
+///
+/// The nRF9160 has no VDDH input; this is the supply rail it can monitor internally.
+pub struct InternalVddGpio;
+
 #[cfg(all(any(feature = "52833", feature = "52840"), feature = "embedded-hal-02"))]
 impl embedded_hal_02::adc::Channel<Saadc> for InternalVddHdiv5 {
     type ID = u8;
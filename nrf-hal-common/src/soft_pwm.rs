@@ -0,0 +1,199 @@
+//! Software PWM driven by a TIMER peripheral.
+//!
+//! This is meant for pins that cannot be routed to a PWM instance, e.g. on pin-constrained
+//! designs where all PWM outputs are already in use. The TIMER counts the period in CC\[0\] and
+//! each channel compares its duty cycle in CC\[1\] to CC\[3\], so up to three pins can be driven
+//! from a single TIMER. The pins are toggled by [`SoftPwm::on_interrupt`], which has to be called
+//! from the interrupt handler of the TIMER.
+//!
+//! The edges are produced in software, so they are subject to the interrupt latency: any
+//! higher-priority interrupt or critical section delays them, and the jitter is much higher than
+//! with the hardware PWM. Keep the frequency modest (a few kHz at most) and prefer the
+//! [`Pwm`](crate::pwm::Pwm) peripheral wherever the pin can reach it.
+
+use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    gpio::{Output, Pin, PushPull},
+    time::Hertz,
+    timer::Instance,
+};
+
+/// Number of channels a single TIMER can drive, one per compare register besides CC\[0\].
+pub const MAX_CHANNELS: usize = 3;
+
+/// A timer-based software PWM driving up to [`MAX_CHANNELS`] pins.
+pub struct SoftPwm<T, const CHANNELS: usize> {
+    timer: T,
+    pins: [Pin<Output<PushPull>>; CHANNELS],
+    duty: [u32; CHANNELS],
+    period: u32,
+}
+
+impl<T, const CHANNELS: usize> SoftPwm<T, CHANNELS>
+where
+    T: Instance,
+{
+    /// The TIMER runs at 1 MHz, so periods and duty cycles are given in microseconds.
+    pub const TICKS_PER_SECOND: u32 = 1_000_000;
+
+    /// Takes ownership of the TIMER and the output pins and configures a period of `freq`.
+    ///
+    /// All channels start with a duty cycle of 0, and the PWM is stopped until
+    /// [`SoftPwm::start`] is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`MAX_CHANNELS`] pins are given, or if `freq` is zero or above 1 MHz.
+    pub fn new(timer: T, mut pins: [Pin<Output<PushPull>>; CHANNELS], freq: Hertz) -> Self {
+        assert!(CHANNELS <= MAX_CHANNELS);
+
+        let regs = timer.as_timer0();
+        regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+        regs.prescaler.write(
+            |w| unsafe { w.prescaler().bits(4) }, // 1 MHz
+        );
+        regs.bitmode.write(|w| w.bitmode()._32bit());
+        regs.shorts
+            .write(|w| w.compare0_clear().enabled().compare0_stop().disabled());
+
+        for pin in pins.iter_mut() {
+            pin.set_low().unwrap();
+        }
+
+        let mut pwm = SoftPwm {
+            timer,
+            pins,
+            duty: [0; CHANNELS],
+            period: 0,
+        };
+        pwm.set_frequency(freq);
+        pwm
+    }
+
+    /// Sets the PWM frequency.
+    ///
+    /// Duty cycles set before are clamped to the new period, but not rescaled.
+    pub fn set_frequency(&mut self, freq: Hertz) {
+        assert!(freq.0 > 0 && freq.0 <= Self::TICKS_PER_SECOND);
+
+        self.period = Self::TICKS_PER_SECOND / freq.0;
+        self.timer.as_timer0().cc[0].write(|w| unsafe { w.bits(self.period) });
+        for channel in 0..CHANNELS {
+            self.set_duty(channel, self.duty[channel]);
+        }
+    }
+
+    /// Returns the PWM frequency.
+    pub fn frequency(&self) -> Hertz {
+        Hertz(Self::TICKS_PER_SECOND / self.period)
+    }
+
+    /// Returns the maximum duty cycle value, the length of the period in ticks.
+    pub fn max_duty(&self) -> u32 {
+        self.period
+    }
+
+    /// Sets the duty cycle of `channel`, i.e. the number of ticks the pin is high per period.
+    ///
+    /// Values above [`SoftPwm::max_duty`] are clamped. The new value takes effect from the next
+    /// period at the latest; an update in the middle of a period may stretch that period's pulse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not below `CHANNELS`.
+    pub fn set_duty(&mut self, channel: usize, duty: u32) {
+        let duty = duty.min(self.period);
+        self.duty[channel] = duty;
+
+        let regs = self.timer.as_timer0();
+        regs.cc[channel + 1].write(|w| unsafe { w.bits(duty) });
+
+        // A channel that never goes low, or never goes high, doesn't need its compare
+        // interrupt.
+        let mask = 1 << (16 + channel + 1);
+        if duty > 0 && duty < self.period {
+            regs.intenset.write(|w| unsafe { w.bits(mask) });
+        } else {
+            regs.intenclr.write(|w| unsafe { w.bits(mask) });
+        }
+
+        // Without a compare interrupt nothing else would pull the pin down.
+        if duty == 0 {
+            self.pins[channel].set_low().unwrap();
+        }
+    }
+
+    /// Returns the duty cycle of `channel`.
+    pub fn duty(&self, channel: usize) -> u32 {
+        self.duty[channel]
+    }
+
+    /// Starts generating the PWM signals.
+    ///
+    /// Note that the interrupt also has to be unmasked in the NVIC, or
+    /// [`SoftPwm::on_interrupt`] won't get called.
+    pub fn start(&mut self) {
+        self.start_period();
+
+        // Make sure the pins are set before the first compare event can fire.
+        compiler_fence(SeqCst);
+
+        let regs = self.timer.as_timer0();
+        for event in regs.events_compare.iter() {
+            event.reset();
+        }
+        regs.intenset.modify(|_, w| w.compare0().set());
+        regs.tasks_clear.write(|w| unsafe { w.bits(1) });
+        regs.tasks_start.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Stops generating the PWM signals and drives all pins low.
+    pub fn stop(&mut self) {
+        let regs = self.timer.as_timer0();
+        regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+        regs.intenclr.write(|w| unsafe { w.bits(0xFFFF_FFFF) });
+
+        for pin in self.pins.iter_mut() {
+            pin.set_low().unwrap();
+        }
+    }
+
+    /// Toggles the pins. Call this from the interrupt handler of the TIMER.
+    pub fn on_interrupt(&mut self) {
+        let regs = self.timer.as_timer0();
+        for (channel, pin) in self.pins.iter_mut().enumerate() {
+            let event = &regs.events_compare[channel + 1];
+            if event.read().bits() != 0 {
+                event.reset();
+                if self.duty[channel] < self.period {
+                    pin.set_low().unwrap();
+                }
+            }
+        }
+
+        if regs.events_compare[0].read().bits() != 0 {
+            regs.events_compare[0].reset();
+            self.start_period();
+        }
+    }
+
+    /// Stops the PWM and returns the TIMER and the pins.
+    pub fn free(mut self) -> (T, [Pin<Output<PushPull>>; CHANNELS]) {
+        self.stop();
+        (self.timer, self.pins)
+    }
+
+    /// Drives the pins of all channels with a non-zero duty cycle high, and all others low.
+    fn start_period(&mut self) {
+        for (pin, duty) in self.pins.iter_mut().zip(self.duty.iter()) {
+            if *duty > 0 {
+                pin.set_high().unwrap();
+            } else {
+                pin.set_low().unwrap();
+            }
+        }
+    }
+}
@@ -2,8 +2,11 @@
 //!
 //! See product specification, chapter 31.
 
+use core::future::poll_fn;
+use core::mem;
 use core::ops::Deref;
 use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+use core::task::Poll;
 use embedded_hal::digital::OutputPin;
 use embedded_hal::spi::{self, ErrorKind, ErrorType, SpiBus};
 
@@ -17,6 +20,8 @@ use crate::pac::{SPIM1_NS as SPIM1, SPIM2_NS as SPIM2, SPIM3_NS as SPIM3};
 use crate::pac::{spim0, SPIM0};
 
 pub use embedded_hal::spi::{Mode, Phase, Polarity, MODE_0, MODE_1, MODE_2, MODE_3};
+#[cfg(any(feature = "52833", feature = "52840"))]
+pub use spim0::csnpol::CSNPOL_A as CsnPolarity;
 pub use spim0::frequency::FREQUENCY_A as Frequency;
 
 use core::iter::repeat_with;
@@ -40,7 +45,7 @@ use crate::{slice_in_ram, slice_in_ram_or, DmaSlice};
 /// - The SPIM instances share the same address space with instances of SPIS,
 ///   SPI, TWIM, TWIS, and TWI. You need to make sure that conflicting instances
 ///   are disabled before using `Spim`. See product specification, section 15.2.
-pub struct Spim<T>(T);
+pub struct Spim<T>(T, SpimConfig);
 
 impl<T> ErrorType for Spim<T> {
     type Error = Error;
@@ -50,6 +55,7 @@ impl<T: Instance> SpiBus for Spim<T> {
     fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
         // A mutable slice can only be built from data in RAM.
         assert!(slice_in_ram(words));
+        self.check_hardware_csn(words.len(), EASY_DMA_SIZE)?;
 
         for chunk in words.chunks(EASY_DMA_SIZE) {
             self.do_spi_dma_transfer(DmaSlice::null(), DmaSlice::from_slice(chunk))?;
@@ -59,10 +65,12 @@ impl<T: Instance> SpiBus for Spim<T> {
 
     fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
         if slice_in_ram(words) {
+            self.check_hardware_csn(words.len(), EASY_DMA_SIZE)?;
             for chunk in words.chunks(EASY_DMA_SIZE) {
                 self.do_spi_dma_transfer(DmaSlice::from_slice(chunk), DmaSlice::null())?;
             }
         } else {
+            self.check_hardware_csn(words.len(), FORCE_COPY_BUFFER_SIZE)?;
             let mut buf = [0u8; FORCE_COPY_BUFFER_SIZE];
             for chunk in words.chunks(FORCE_COPY_BUFFER_SIZE) {
                 buf[..chunk.len()].copy_from_slice(chunk);
@@ -83,6 +91,7 @@ impl<T: Instance> SpiBus for Spim<T> {
     fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
         // A mutable slice can only be built from data in RAM.
         assert!(slice_in_ram(words));
+        self.check_hardware_csn(words.len(), EASY_DMA_SIZE)?;
 
         words.chunks(EASY_DMA_SIZE).try_for_each(|chunk| {
             self.do_spi_dma_transfer(DmaSlice::from_slice(chunk), DmaSlice::from_slice(chunk))
@@ -97,6 +106,87 @@ impl<T: Instance> SpiBus for Spim<T> {
     }
 }
 
+/// Works like the blocking [`SpiBus`] implementation, but yields to the executor while the DMA
+/// runs. [`Spim::on_interrupt`] has to be called from the interrupt handler of the SPIM instance,
+/// and the interrupt has to be unmasked in the NVIC.
+impl<T: Instance> embedded_hal_async::spi::SpiBus for Spim<T> {
+    async fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        // A mutable slice can only be built from data in RAM.
+        assert!(slice_in_ram(words));
+        self.check_hardware_csn(words.len(), EASY_DMA_SIZE)?;
+
+        for chunk in words.chunks_mut(EASY_DMA_SIZE) {
+            self.do_spi_dma_transfer_async(DmaSlice::null(), DmaSlice::from_slice(chunk))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        if slice_in_ram(words) {
+            self.check_hardware_csn(words.len(), EASY_DMA_SIZE)?;
+            for chunk in words.chunks(EASY_DMA_SIZE) {
+                self.do_spi_dma_transfer_async(DmaSlice::from_slice(chunk), DmaSlice::null())
+                    .await?;
+            }
+        } else {
+            self.check_hardware_csn(words.len(), FORCE_COPY_BUFFER_SIZE)?;
+            let mut buf = [0u8; FORCE_COPY_BUFFER_SIZE];
+            for chunk in words.chunks(FORCE_COPY_BUFFER_SIZE) {
+                buf[..chunk.len()].copy_from_slice(chunk);
+                self.do_spi_dma_transfer_async(
+                    DmaSlice::from_slice(&buf[..chunk.len()]),
+                    DmaSlice::null(),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let len = read.len().max(write.len());
+        let needs_copy = !slice_in_ram(write);
+        let chunk_size = if needs_copy {
+            FORCE_COPY_BUFFER_SIZE
+        } else {
+            EASY_DMA_SIZE
+        };
+        self.check_hardware_csn(len, chunk_size)?;
+
+        // Like `transfer_split_uneven_internal`, a null DmaSlice is used once a buffer runs out.
+        let mut buf = [0u8; FORCE_COPY_BUFFER_SIZE];
+        let mut rx_chunks = read.chunks_mut(chunk_size);
+        let mut tx_chunks = write.chunks(chunk_size);
+        for _ in 0..len.div_ceil(chunk_size) {
+            let r = rx_chunks
+                .next()
+                .map_or_else(DmaSlice::null, |chunk| DmaSlice::from_slice(chunk));
+            let t = match tx_chunks.next() {
+                Some(chunk) if needs_copy => {
+                    buf[..chunk.len()].copy_from_slice(chunk);
+                    DmaSlice::from_slice(&buf[..chunk.len()])
+                }
+                Some(chunk) => DmaSlice::from_slice(chunk),
+                None => DmaSlice::null(),
+            };
+            self.do_spi_dma_transfer_async(t, r).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.transfer_async(words).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        // This implementation doesn't buffer operations, so there is nothing to flush.
+        Ok(())
+    }
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<T> embedded_hal_02::blocking::spi::Transfer<u8> for Spim<T>
 where
@@ -107,6 +197,7 @@ where
     fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Error> {
         // If the slice isn't in RAM, we can't write back to it at all
         slice_in_ram_or(words, Error::DMABufferNotInDataMemory)?;
+        self.check_hardware_csn(words.len(), EASY_DMA_SIZE)?;
 
         words.chunks(EASY_DMA_SIZE).try_for_each(|chunk| {
             self.do_spi_dma_transfer(DmaSlice::from_slice(chunk), DmaSlice::from_slice(chunk))
@@ -133,6 +224,7 @@ where
         } else {
             EASY_DMA_SIZE
         };
+        self.check_hardware_csn(words.len(), chunk_sz)?;
 
         let step = if needs_copy {
             Self::spi_dma_copy
@@ -161,6 +253,14 @@ where
         self.do_spi_dma_transfer(DmaSlice::from_slice(&buf[..chunk.len()]), DmaSlice::null())
     }
 
+    /// The maximum number of bytes a single DMA transfer can move on this chip.
+    ///
+    /// Longer buffers are transparently split into back-to-back DMA transfers of at most this
+    /// size, with a software chip select held asserted across all of them. Pre-chunk the data
+    /// to this size to control the chip select between the chunks yourself. With the hardware
+    /// chip select, longer buffers are rejected, see [`Spim::new_with_cs`].
+    pub const MAX_TRANSFER_SIZE: usize = EASY_DMA_SIZE;
+
     pub fn new(spim: T, pins: Pins, frequency: Frequency, mode: Mode, orc: u8) -> Self {
         // Select pins.
         match pins.sck {
@@ -189,42 +289,121 @@ where
         spim.enable.write(|w| w.enable().enabled());
 
         // Configure mode.
-        spim.config.write(|w| {
-            // Can't match on `mode` due to embedded-hal, see https://github.com/rust-embedded/embedded-hal/pull/126
-            if mode == MODE_0 {
-                w.order().msb_first();
-                w.cpol().active_high();
-                w.cpha().leading();
-            } else if mode == MODE_1 {
-                w.order().msb_first();
-                w.cpol().active_high();
-                w.cpha().trailing();
-            } else if mode == MODE_2 {
-                w.order().msb_first();
-                w.cpol().active_low();
-                w.cpha().leading();
-            } else {
-                w.order().msb_first();
-                w.cpol().active_low();
-                w.cpha().trailing();
-            }
-            w
-        });
+        write_mode(&spim, mode);
 
         // Configure frequency.
         spim.frequency.write(|w| w.frequency().variant(frequency));
 
-        // Set over-read character to `0`.
+        // Set over-read character.
         spim.orc.write(|w|
-            // The ORC field is 8 bits long, so `0` is a valid value to write
+            // The ORC field is 8 bits long, so any `u8` is a valid value to write
             // there.
             unsafe { w.orc().bits(orc) });
 
-        Spim(spim)
+        Spim(
+            spim,
+            SpimConfig {
+                orc,
+                ..SpimConfig::default()
+            },
+        )
+    }
+
+    /// Changes the SPI mode used by subsequent transfers.
+    ///
+    /// Transfers borrow the `Spim` mutably, so the peripheral is always idle here. This allows a
+    /// single `Spim` to talk to devices using different modes on the same bus, e.g. one that takes
+    /// commands in mode 0 and data in mode 3.
+    pub fn set_mode(&mut self, mode: Mode) {
+        write_mode(&self.0, mode);
+    }
+
+    /// Sets the chip select timing and the over-read character.
+    ///
+    /// The delays are applied around the software chip select of the methods taking a
+    /// `chip_select` pin, by busy-waiting. SPIM3 on the nRF52833 and nRF52840 can also drive the
+    /// chip select in hardware, see [`Spim::new_with_cs`]; there, the longer of the two delays is
+    /// programmed into the IFTIMING.CSNDUR register, which applies to both.
+    ///
+    /// Every field is applied, including the over-read character given to [`Spim::new`]. To change
+    /// only some of them, start from the current configuration rather than from
+    /// `SpimConfig::default()`, which would reset the over-read character to 0:
+    ///
+    #[cfg_attr(feature = "52840", doc = "```no_run")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common::spim::{Spim, SpimConfig};
+    /// # fn f(spim: &mut Spim<nrf_hal_common::pac::SPIM0>) {
+    /// spim.set_config(SpimConfig {
+    ///     cs_to_clock_delay: 4,
+    ///     ..spim.config()
+    /// });
+    /// # }
+    /// ```
+    pub fn set_config(&mut self, config: SpimConfig) {
+        self.1 = config;
+        self.set_orc(config.orc);
+
+        #[cfg(any(feature = "52833", feature = "52840"))]
+        if T::PTR == SPIM3::PTR {
+            write_csndur(&self.0, config);
+        }
+    }
+
+    /// Returns the chip select timing and the over-read character.
+    pub fn config(&self) -> SpimConfig {
+        self.1
+    }
+
+    /// Sets the over-read character, the byte clocked out once `tx` is exhausted while `rx` is
+    /// still being filled.
+    ///
+    /// SD cards and many flash chips expect `0xFF` during read-only phases.
+    pub fn set_orc(&mut self, orc: u8) {
+        self.1.orc = orc;
+        self.0.orc.write(|w| unsafe { w.orc().bits(orc) });
+    }
+
+    /// Asserts the software chip select and waits for the configured delay.
+    fn select(&self, chip_select: &mut Pin<Output<PushPull>>) {
+        chip_select.set_low().unwrap();
+        delay_cycles(self.1.cs_to_clock_delay);
+    }
+
+    /// Waits for the configured delay and deasserts the software chip select.
+    fn deselect(&self, chip_select: &mut Pin<Output<PushPull>>) {
+        delay_cycles(self.1.cs_to_clock_delay);
+        chip_select.set_high().unwrap();
+        delay_cycles(self.1.inter_frame_delay);
+    }
+
+    /// Rejects a transfer of `len` bytes that is split into DMA transfers of at most `chunk_size`
+    /// bytes while the hardware chip select is in use, as it would be deasserted between them.
+    fn check_hardware_csn(&self, len: usize, chunk_size: usize) -> Result<(), Error> {
+        if len <= chunk_size {
+            return Ok(());
+        }
+        #[cfg(any(feature = "52833", feature = "52840"))]
+        if T::PTR == SPIM3::PTR && self.0.psel.csn.read().connect().is_connected() {
+            return Err(Error::TransferTooLong);
+        }
+        Ok(())
     }
 
     /// Internal helper function to setup and execute SPIM DMA transfer.
     fn do_spi_dma_transfer(&mut self, tx: DmaSlice, rx: DmaSlice) -> Result<(), Error> {
+        self.start_spi_dma_transfer(&tx, &rx);
+
+        // Wait for END event.
+        //
+        // This event is triggered once both transmitting and receiving are
+        // done.
+        while self.0.events_end.read().bits() == 0 {}
+
+        self.finish_spi_dma_transfer(&tx, &rx)
+    }
+
+    /// Internal helper function to setup and start a SPIM DMA transfer.
+    fn start_spi_dma_transfer(&mut self, tx: &DmaSlice, rx: &DmaSlice) {
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
         // before any DMA action has started.
@@ -259,13 +438,10 @@ where
         // take in to account actions by DMA. The fence has been placed here,
         // after all possible DMA actions have completed.
         compiler_fence(SeqCst);
+    }
 
-        // Wait for END event.
-        //
-        // This event is triggered once both transmitting and receiving are
-        // done.
-        while self.0.events_end.read().bits() == 0 {}
-
+    /// Internal helper function to complete a SPIM DMA transfer after the END event.
+    fn finish_spi_dma_transfer(&mut self, tx: &DmaSlice, rx: &DmaSlice) -> Result<(), Error> {
         // Reset the event, otherwise it will always read `1` from now on.
         self.0.events_end.write(|w| w);
 
@@ -283,6 +459,62 @@ where
         Ok(())
     }
 
+    /// Read and write from a SPI slave using a single buffer, without blocking.
+    ///
+    /// Transmits all bytes in `buffer` and replaces them with the received bytes, like
+    /// [`SpiBus::transfer_in_place`], but yields to the executor while the DMA runs instead of
+    /// spinning. [`Spim::on_interrupt`] has to be called from the interrupt handler of the SPIM
+    /// instance, and the interrupt has to be unmasked in the NVIC.
+    ///
+    /// No chip select is driven, so either bracket the call with a GPIO or use the hardware chip
+    /// select where available. Dropping the future stops the transfer.
+    pub async fn transfer_async(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        slice_in_ram_or(buffer, Error::DMABufferNotInDataMemory)?;
+        self.check_hardware_csn(buffer.len(), EASY_DMA_SIZE)?;
+
+        for chunk in buffer.chunks_mut(EASY_DMA_SIZE) {
+            self.do_spi_dma_transfer_async(
+                DmaSlice::from_slice(chunk),
+                DmaSlice::from_slice(chunk),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Internal helper function to execute a SPIM DMA transfer, yielding until the END event.
+    async fn do_spi_dma_transfer_async(&mut self, tx: DmaSlice, rx: DmaSlice) -> Result<(), Error> {
+        self.0.intenset.write(|w| w.end().set());
+        self.start_spi_dma_transfer(&tx, &rx);
+
+        let stop = StopOnDrop(&self.0);
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+            if self.0.events_end.read().bits() != 0 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        mem::forget(stop);
+
+        self.finish_spi_dma_transfer(&tx, &rx)
+    }
+
+    /// Wakes the task waiting for a transfer of this SPIM instance to end.
+    ///
+    /// Call this from the interrupt handler of the SPIM instance when using
+    /// [`Spim::transfer_async`].
+    pub fn on_interrupt() {
+        let spim = unsafe { &*T::PTR };
+        if spim.events_end.read().bits() != 0 {
+            spim.intenclr.write(|w| w.end().clear());
+            T::waker().wake();
+        }
+    }
+
     /// Read and write from a SPI slave, using a single buffer.
     ///
     /// This method implements a complete read transaction, which consists of
@@ -291,21 +523,24 @@ where
     ///
     /// Uses the provided chip select pin to initiate the transaction. Transmits
     /// all bytes in `buffer`, then receives an equal number of bytes.
+    ///
+    /// Buffers of any length are supported, see [`Spim::MAX_TRANSFER_SIZE`].
     pub fn transfer(
         &mut self,
         chip_select: &mut Pin<Output<PushPull>>,
         buffer: &mut [u8],
     ) -> Result<(), Error> {
         slice_in_ram_or(buffer, Error::DMABufferNotInDataMemory)?;
+        self.check_hardware_csn(buffer.len(), EASY_DMA_SIZE)?;
 
-        chip_select.set_low().unwrap();
+        self.select(chip_select);
 
         // Don't return early, as we must reset the CS pin.
         let res = buffer.chunks(EASY_DMA_SIZE).try_for_each(|chunk| {
             self.do_spi_dma_transfer(DmaSlice::from_slice(chunk), DmaSlice::from_slice(chunk))
         });
 
-        chip_select.set_high().unwrap();
+        self.deselect(chip_select);
 
         res
     }
@@ -321,6 +556,8 @@ where
     ///
     /// If `tx_buffer.len() != rx_buffer.len()`, the transaction will stop at the
     /// smaller of either buffer.
+    ///
+    /// Buffers of any length are supported, see [`Spim::MAX_TRANSFER_SIZE`].
     pub fn transfer_split_even(
         &mut self,
         chip_select: &mut Pin<Output<PushPull>>,
@@ -330,18 +567,19 @@ where
         // NOTE: RAM slice check for `rx_buffer` is not necessary, as a mutable
         // slice can only be built from data located in RAM.
         slice_in_ram_or(tx_buffer, Error::DMABufferNotInDataMemory)?;
+        self.check_hardware_csn(tx_buffer.len().min(rx_buffer.len()), EASY_DMA_SIZE)?;
 
         let txi = tx_buffer.chunks(EASY_DMA_SIZE);
         let rxi = rx_buffer.chunks_mut(EASY_DMA_SIZE);
 
-        chip_select.set_low().unwrap();
+        self.select(chip_select);
 
         // Don't return early, as we must reset the CS pin
         let res = txi.zip(rxi).try_for_each(|(t, r)| {
             self.do_spi_dma_transfer(DmaSlice::from_slice(t), DmaSlice::from_slice(r))
         });
 
-        chip_select.set_high().unwrap();
+        self.deselect(chip_select);
 
         res
     }
@@ -359,6 +597,8 @@ where
     /// it is allowed to perform transactions where `tx_buffer.len() != rx_buffer.len()`.
     /// If this occurs, extra incoming bytes will be discarded, OR extra outgoing bytes
     /// will be filled with the `orc` value.
+    ///
+    /// Buffers of any length are supported, see [`Spim::MAX_TRANSFER_SIZE`].
     pub fn transfer_split_uneven(
         &mut self,
         chip_select: &mut Pin<Output<PushPull>>,
@@ -367,13 +607,29 @@ where
     ) -> Result<(), Error> {
         slice_in_ram_or(tx_buffer, Error::DMABufferNotInDataMemory)?;
 
-        chip_select.set_low().unwrap();
+        self.select(chip_select);
         // Don't return early, as we must reset the CS pin.
         let res = self.transfer_split_uneven_internal(tx_buffer, rx_buffer);
-        chip_select.set_high().unwrap();
+        self.deselect(chip_select);
         res
     }
 
+    /// Transmits `tx` while receiving into `rx`, without driving a chip select.
+    ///
+    /// The TX and RX lengths are independent: while `tx` is shorter, the `orc` byte is clocked
+    /// out, and while `rx` is shorter, the incoming bytes are discarded. This suits devices that
+    /// take a short command followed by a longer response, e.g. `tx = [CMD]` and `rx` of
+    /// `1 + response length` bytes, with the response starting at `rx[1]`.
+    ///
+    /// If both buffers are in RAM and fit into [`Spim::MAX_TRANSFER_SIZE`], this is a single DMA
+    /// transfer, so a hardware chip select (see [`Spim::new_with_cs`]) stays asserted throughout.
+    /// Otherwise, the buffers are split into several transfers, which fails with
+    /// [`Error::TransferTooLong`] while the hardware chip select is in use. `tx` may be located in
+    /// flash.
+    pub fn transfer_split(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<(), Error> {
+        self.transfer_split_uneven_internal(tx, rx)
+    }
+
     pub fn transfer_split_uneven_internal(
         &mut self,
         tx_buffer: &[u8],
@@ -381,7 +637,10 @@ where
     ) -> Result<(), Error> {
         // NOTE: RAM slice check for `rx_buffer` is not necessary, as a mutable
         // slice can only be built from data located in RAM.
+        let len = tx_buffer.len().max(rx_buffer.len());
         if slice_in_ram(tx_buffer) {
+            self.check_hardware_csn(len, EASY_DMA_SIZE)?;
+
             // For the tx and rx, we want to return a DmaSlice with a chunk as long
             // as there is data to send. We then chain a repeat to the end so once
             // all chunks have been exhausted, we will keep DmaSlice::null() out of
@@ -401,6 +660,8 @@ where
                 self.do_spi_dma_transfer(t, r)?;
             }
         } else {
+            self.check_hardware_csn(len, FORCE_COPY_BUFFER_SIZE)?;
+
             let mut buf = [0u8; FORCE_COPY_BUFFER_SIZE];
             let txi = tx_buffer
                 .chunks(FORCE_COPY_BUFFER_SIZE)
@@ -429,15 +690,70 @@ where
     /// This method uses the provided chip select pin to initiate the
     /// transaction, then transmits all bytes in `tx_buffer`. All incoming
     /// bytes are discarded.
+    ///
+    /// Buffers of any length are supported, see [`Spim::MAX_TRANSFER_SIZE`].
+    ///
+    /// Returns [`Error::DMABufferNotInDataMemory`] if `tx_buffer` is not in RAM, see
+    /// [`Spim::write_from_ram`].
     pub fn write(
         &mut self,
         chip_select: &mut Pin<Output<PushPull>>,
         tx_buffer: &[u8],
+    ) -> Result<(), Error> {
+        self.write_from_ram(chip_select, tx_buffer)
+    }
+
+    /// Write to an SPI slave from a buffer in RAM.
+    ///
+    /// EasyDMA can only read from RAM, so this returns [`Error::DMABufferNotInDataMemory`]
+    /// without starting a transaction if `tx_buffer` is located elsewhere, e.g. a constant in
+    /// flash. Use [`Spim::write_copy`] for such data.
+    pub fn write_from_ram(
+        &mut self,
+        chip_select: &mut Pin<Output<PushPull>>,
+        tx_buffer: &[u8],
     ) -> Result<(), Error> {
         slice_in_ram_or(tx_buffer, Error::DMABufferNotInDataMemory)?;
         self.transfer_split_uneven(chip_select, tx_buffer, &mut [0u8; 0])
     }
 
+    /// Write to an SPI slave from a buffer located anywhere, e.g. a constant in flash.
+    ///
+    /// Data that is not in RAM is copied through `scratch` in chunks of its size, with the chip
+    /// select held asserted throughout. A larger scratch buffer means fewer DMA transfers, and
+    /// thus shorter pauses on the bus. Data in RAM is written directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scratch` is empty.
+    pub fn write_copy(
+        &mut self,
+        chip_select: &mut Pin<Output<PushPull>>,
+        tx_buffer: &[u8],
+        scratch: &mut [u8],
+    ) -> Result<(), Error> {
+        if slice_in_ram(tx_buffer) {
+            return self.write_from_ram(chip_select, tx_buffer);
+        }
+        assert!(!scratch.is_empty());
+
+        let chunk_size = scratch.len().min(EASY_DMA_SIZE);
+        self.check_hardware_csn(tx_buffer.len(), chunk_size)?;
+
+        self.select(chip_select);
+
+        // Don't return early, as we must reset the CS pin.
+        let res = tx_buffer.chunks(chunk_size).try_for_each(|chunk| {
+            let buf = &mut scratch[..chunk.len()];
+            buf.copy_from_slice(chunk);
+            self.do_spi_dma_transfer(DmaSlice::from_slice(buf), DmaSlice::null())
+        });
+
+        self.deselect(chip_select);
+
+        res
+    }
+
     /// Return the raw interface to the underlying SPIM peripheral.
     pub fn free(self) -> (T, Pins) {
         let sck = self.0.psel.sck.read();
@@ -469,6 +785,126 @@ where
     }
 }
 
+/// Hardware chip select.
+///
+/// Only SPIM3 on the nRF52833 and nRF52840 has a dedicated `CSN` pin, which the peripheral
+/// asserts before and deasserts after every DMA transfer with precise timing. On all other
+/// instances and parts, chip select has to be driven in software, e.g. by passing the pin to
+/// [`Spim::transfer`].
+#[cfg(any(feature = "52833", feature = "52840"))]
+impl Spim<SPIM3> {
+    /// Like [`Spim::new`], but lets the peripheral drive `cs` as an active-low chip select.
+    ///
+    /// The chip select is asserted for the duration of each DMA transfer, so the methods of
+    /// [`SpiBus`] can be used without any manual chip select handling. A transfer that doesn't fit
+    /// into a single DMA transfer can't keep the chip select asserted throughout, so while the
+    /// hardware chip select is in use, transfers larger than [`Spim::MAX_TRANSFER_SIZE`] (or
+    /// `FORCE_COPY_BUFFER_SIZE` for data written from flash) are rejected with
+    /// [`Error::TransferTooLong`] instead of being split. Use [`Spim::set_config`] to adjust the
+    /// timing.
+    pub fn new_with_cs(
+        spim: SPIM3,
+        pins: Pins,
+        frequency: Frequency,
+        mode: Mode,
+        orc: u8,
+        cs: Pin<Output<PushPull>>,
+    ) -> Self {
+        let mut spim = Spim::new(spim, pins, frequency, mode, orc);
+        spim.set_hardware_csn(cs, CsnPolarity::LOW);
+        spim
+    }
+
+    /// Lets the peripheral drive `csn` as the chip select pin.
+    ///
+    /// The minimum time between the `CSN` edges and the `SCK` edges is taken from the
+    /// [`SpimConfig`], see [`Spim::set_config`].
+    ///
+    /// The chip select is asserted around each DMA transfer, so transfers that would have to be
+    /// split into several of them fail with [`Error::TransferTooLong`] while it is in use, see
+    /// [`Spim::new_with_cs`]. Use the methods of [`SpiBus`], which do not take a software chip
+    /// select, while the hardware chip select is in use.
+    pub fn set_hardware_csn(&mut self, csn: Pin<Output<PushPull>>, polarity: CsnPolarity) {
+        write_csndur(&self.0, self.1);
+        self.0.csnpol.write(|w| w.csnpol().variant(polarity));
+        self.0.psel.csn.write(|w| {
+            unsafe { w.bits(csn.psel_bits()) };
+            w.connect().connected()
+        });
+    }
+
+    /// Stops driving the hardware chip select and returns its pin, if one was set.
+    pub fn clear_hardware_csn(&mut self) -> Option<Pin<Output<PushPull>>> {
+        let csn = self.0.psel.csn.read();
+        self.0.psel.csn.reset();
+        if csn.connect().is_connected() {
+            Some(unsafe { Pin::from_psel_bits(csn.bits()) })
+        } else {
+            None
+        }
+    }
+}
+
+/// Programs the hardware chip select timing of SPIM3 from the delays in `config`.
+///
+/// IFTIMING.CSNDUR applies both before the first and after the last clock edge, so it is set to
+/// the longer of the two delays.
+#[cfg(any(feature = "52833", feature = "52840"))]
+fn write_csndur(spim: &spim0::RegisterBlock, config: SpimConfig) {
+    let csndur = config.cs_to_clock_delay.max(config.inter_frame_delay);
+    spim.iftiming
+        .csndur
+        .write(|w| unsafe { w.csndur().bits(csndur) });
+}
+
+/// Writes the clock polarity and phase of `mode` into the CONFIG register.
+fn write_mode(spim: &spim0::RegisterBlock, mode: Mode) {
+    spim.config.write(|w| {
+        // Can't match on `mode` due to embedded-hal, see https://github.com/rust-embedded/embedded-hal/pull/126
+        if mode == MODE_0 {
+            w.order().msb_first();
+            w.cpol().active_high();
+            w.cpha().leading();
+        } else if mode == MODE_1 {
+            w.order().msb_first();
+            w.cpol().active_high();
+            w.cpha().trailing();
+        } else if mode == MODE_2 {
+            w.order().msb_first();
+            w.cpol().active_low();
+            w.cpha().leading();
+        } else {
+            w.order().msb_first();
+            w.cpol().active_low();
+            w.cpha().trailing();
+        }
+        w
+    });
+}
+
+/// Busy-waits for at least `cycles` periods of the 64 MHz clock.
+fn delay_cycles(cycles: u8) {
+    if cycles > 0 {
+        cortex_m::asm::delay(u32::from(cycles));
+    }
+}
+
+/// Chip select timing and over-read character of a [`Spim`], see [`Spim::set_config`].
+///
+/// The delays are given in units of 15.625 ns, one period of the 64 MHz clock. Update a
+/// configuration with `..spim.config()` rather than `..Default::default()`, so that the over-read
+/// character is kept.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SpimConfig {
+    /// Minimum time between asserting the chip select and the first clock edge, and between the
+    /// last clock edge and deasserting the chip select.
+    pub cs_to_clock_delay: u8,
+    /// Minimum time the chip select stays deasserted after a transaction.
+    pub inter_frame_delay: u8,
+    /// Over-read character, clocked out while `rx` is longer than `tx`, see [`Spim::set_orc`].
+    pub orc: u8,
+}
+
 /// GPIO pins for SPIM interface
 pub struct Pins {
     /// SPI clock.
@@ -491,6 +927,9 @@ pub enum Error {
     DMABufferNotInDataMemory,
     Transmit,
     Receive,
+    /// The transfer would have to be split into several DMA transfers while the hardware chip
+    /// select is in use, which would deassert it in between, see [`Spim::new_with_cs`].
+    TransferTooLong,
 }
 
 impl spi::Error for Error {
@@ -499,15 +938,49 @@ impl spi::Error for Error {
     }
 }
 
+/// Stops a DMA transfer that is still running when an async transfer is cancelled.
+struct StopOnDrop<'a>(&'a spim0::RegisterBlock);
+
+impl Drop for StopOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.intenclr.write(|w| w.end().clear());
+        self.0.tasks_stop.write(|w| unsafe { w.bits(1) });
+        while self.0.events_stopped.read().bits() == 0 {}
+        self.0.events_stopped.reset();
+        self.0.events_end.reset();
+    }
+}
+
 /// Implemented by all SPIM instances.
 pub trait Instance: Deref<Target = spim0::RegisterBlock> + sealed::Sealed {}
 
 mod sealed {
-    pub trait Sealed {}
+    use super::spim0;
+    use crate::waker::AtomicWaker;
+
+    pub trait Sealed {
+        const PTR: *const spim0::RegisterBlock;
+
+        /// The waker of the task waiting in `Spim::transfer_async`.
+        fn waker() -> &'static AtomicWaker;
+    }
+}
+
+macro_rules! impl_instance {
+    ($spim:ident) => {
+        impl sealed::Sealed for $spim {
+            const PTR: *const spim0::RegisterBlock = $spim::PTR;
+
+            fn waker() -> &'static crate::waker::AtomicWaker {
+                static WAKER: crate::waker::AtomicWaker = crate::waker::AtomicWaker::new();
+                &WAKER
+            }
+        }
+        impl Instance for $spim {}
+    };
 }
 
-impl sealed::Sealed for SPIM0 {}
-impl Instance for SPIM0 {}
+impl_instance!(SPIM0);
 
 #[cfg(any(
     feature = "52832",
@@ -518,8 +991,7 @@ impl Instance for SPIM0 {}
 ))]
 mod _spim1 {
     use super::*;
-    impl Instance for SPIM1 {}
-    impl sealed::Sealed for SPIM1 {}
+    impl_instance!(SPIM1);
 }
 
 #[cfg(any(
@@ -530,13 +1002,11 @@ mod _spim1 {
 ))]
 mod _spim2 {
     use super::*;
-    impl Instance for SPIM2 {}
-    impl sealed::Sealed for SPIM2 {}
+    impl_instance!(SPIM2);
 }
 
 #[cfg(any(feature = "52833", feature = "52840", feature = "9160"))]
 mod _spim3 {
     use super::*;
-    impl Instance for SPIM3 {}
-    impl sealed::Sealed for SPIM3 {}
+    impl_instance!(SPIM3);
 }
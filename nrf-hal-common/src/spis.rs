@@ -3,8 +3,11 @@
 //! A module for SPI communication in peripheral mode.
 
 use core::{
+    future::poll_fn,
+    mem,
     ops::Deref,
     sync::atomic::{compiler_fence, Ordering},
+    task::Poll,
 };
 
 #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
@@ -30,6 +33,7 @@ use crate::pac::{SPIS1, SPIS2};
 use crate::{
     gpio::{Floating, Input, Pin},
     pac::Interrupt,
+    slice_in_ram_or,
     target_constants::{EASY_DMA_SIZE, SRAM_LOWER, SRAM_UPPER},
 };
 use embedded_dma::*;
@@ -312,6 +316,126 @@ where
         &self.spis.tasks_release
     }
 
+    /// Blocking full duplex transfer.
+    ///
+    /// Prepares `tx` and `rx`, hands the semaphore over to the SPIS and waits until the
+    /// controller has clocked a transaction, i.e. until the END event. The semaphore is then taken
+    /// back, so that further transactions are answered with the default character until the next
+    /// call. Returns the number of bytes received, which may be less than `rx.len()`.
+    ///
+    /// `tx` must be located in RAM.
+    ///
+    #[cfg_attr(feature = "52840", doc = "```no_run")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common::{gpio::p0::Parts, pac::Peripherals};
+    /// # use nrf_hal_common::spis::{Pins, Spis};
+    /// let p = Peripherals::take().unwrap();
+    /// let port0 = Parts::new(p.P0);
+    /// let pins = Pins {
+    ///     sck: port0.p0_13.into_floating_input().degrade(),
+    ///     cs: port0.p0_14.into_floating_input().degrade(),
+    ///     copi: Some(port0.p0_15.into_floating_input().degrade()),
+    ///     cipo: Some(port0.p0_16.into_floating_input().degrade()),
+    /// };
+    /// let mut spis = Spis::new(p.SPIS1, pins);
+    /// spis.set_orc(0xff);
+    ///
+    /// let mut command = [0u8; 4];
+    /// let response = [0x5a; 4];
+    /// let len = spis.transfer_blocking(&response, &mut command).unwrap();
+    /// ```
+    pub fn transfer_blocking(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<usize, Error> {
+        self.start_transaction(tx, rx)?;
+        while self.spis.events_end.read().bits() == 0 {}
+        Ok(self.finish_transaction())
+    }
+
+    /// Async full duplex transfer.
+    ///
+    /// Like [`Spis::transfer_blocking`], but sleeps until the END event instead of busy-waiting.
+    /// [`Spis::on_interrupt`] has to be called from the interrupt handler of the SPIS instance,
+    /// and the interrupt has to be unmasked in the NVIC.
+    ///
+    /// If the future is dropped early, the CPU takes the semaphore back before the buffers are
+    /// released, waiting for a transaction in progress to end.
+    pub async fn wait_transaction_async(
+        &mut self,
+        tx: &[u8],
+        rx: &mut [u8],
+    ) -> Result<usize, Error> {
+        self.start_transaction(tx, rx)?;
+        let cancel = CancelOnDrop(&self.spis);
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+            if self.spis.events_end.read().bits() != 0 {
+                Poll::Ready(())
+            } else {
+                self.spis.intenset.write(|w| w.end().set_bit());
+                Poll::Pending
+            }
+        })
+        .await;
+        mem::forget(cancel);
+        Ok(self.finish_transaction())
+    }
+
+    /// Wakes the task waiting in [`Spis::wait_transaction_async`].
+    ///
+    /// Call this from the interrupt handler of the SPIS instance.
+    pub fn on_interrupt() {
+        let spis = unsafe { &*T::PTR };
+        if spis.events_end.read().bits() != 0 {
+            spis.intenclr.write(|w| w.end().set_bit());
+            T::waker().wake();
+        }
+    }
+
+    /// Sets up the buffers and hands the semaphore over to the SPIS.
+    fn start_transaction(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<(), Error> {
+        if tx.len().max(rx.len()) > EASY_DMA_SIZE {
+            return Err(Error::BufferTooLong);
+        }
+        slice_in_ram_or(tx, Error::DMABufferNotInDataMemory)?;
+
+        // The buffers may only be changed while the CPU holds the semaphore.
+        acquire_semaphore(&self.spis);
+        self.spis
+            .txd
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(tx.as_ptr() as u32) });
+        self.spis
+            .txd
+            .maxcnt
+            .write(|w| unsafe { w.bits(tx.len() as u32) });
+        self.spis
+            .rxd
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(rx.as_mut_ptr() as u32) });
+        self.spis
+            .rxd
+            .maxcnt
+            .write(|w| unsafe { w.bits(rx.len() as u32) });
+        self.spis.events_end.reset();
+        self.spis.events_endrx.reset();
+
+        // Conservative compiler fence to prevent handing over the buffers before the
+        // pointers and maxcounts have been set.
+        compiler_fence(Ordering::SeqCst);
+        self.release();
+        Ok(())
+    }
+
+    /// Takes the semaphore back after the END event and returns the number of bytes received.
+    fn finish_transaction(&mut self) -> usize {
+        self.spis.events_end.reset();
+        self.spis.events_endrx.reset();
+        acquire_semaphore(&self.spis);
+
+        // Prevent optimizations creating issues with the EasyDMA-modified buffer.
+        compiler_fence(Ordering::SeqCst);
+        self.spis.rxd.amount.read().bits() as usize
+    }
+
     /// Full duplex DMA transfer.
     /// Transmits the given buffer while simultaneously receiving data into the same buffer until it is filled.
     /// Buffer must be located in RAM.
@@ -433,6 +557,24 @@ where
     }
 }
 
+/// Acquires the semaphore for the CPU, waiting for an ongoing transaction to end.
+fn acquire_semaphore(spis: &spis0::RegisterBlock) {
+    spis.tasks_acquire.write(|w| unsafe { w.bits(1) });
+    // SEMSTAT reads 1 once the CPU holds the semaphore.
+    while spis.semstat.read().bits() != 1 {}
+    spis.events_acquired.reset();
+}
+
+/// Takes the buffers back from the SPIS when an async transaction is dropped before it ended.
+struct CancelOnDrop<'a>(&'a spis0::RegisterBlock);
+
+impl Drop for CancelOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.intenclr.write(|w| w.end().set_bit());
+        acquire_semaphore(self.0);
+    }
+}
+
 /// A DMA transfer
 pub struct Transfer<T: Instance, B> {
     // FIXME: Always `Some`, only using `Option` here to allow moving fields out of `inner`.
@@ -614,8 +756,36 @@ pub struct Pins {
 }
 
 mod sealed {
-    pub trait Sealed {}
-    impl Sealed for super::SPIS0 {}
+    use super::spis0;
+    use crate::waker::AtomicWaker;
+
+    pub trait Sealed {
+        /// Pointer to the register block of the instance.
+        const PTR: *const spis0::RegisterBlock;
+
+        /// The waker of the task waiting for a transaction.
+        fn waker() -> &'static AtomicWaker;
+    }
+}
+
+macro_rules! impl_sealed {
+    ($($(#[$attr:meta])* $spis:ident,)*) => {
+        $(
+            $(#[$attr])*
+            impl sealed::Sealed for $spis {
+                const PTR: *const spis0::RegisterBlock = $spis::PTR as *const _;
+
+                fn waker() -> &'static crate::waker::AtomicWaker {
+                    static WAKER: crate::waker::AtomicWaker = crate::waker::AtomicWaker::new();
+                    &WAKER
+                }
+            }
+        )*
+    };
+}
+
+impl_sealed!(
+    SPIS0,
     #[cfg(not(any(
         feature = "9160",
         feature = "5340-app",
@@ -623,7 +793,7 @@ mod sealed {
         feature = "52810",
         feature = "52805"
     )))]
-    impl Sealed for super::SPIS1 {}
+    SPIS1,
     #[cfg(not(any(
         feature = "9160",
         feature = "5340-app",
@@ -632,8 +802,8 @@ mod sealed {
         feature = "52810",
         feature = "52805"
     )))]
-    impl Sealed for super::SPIS2 {}
-}
+    SPIS2,
+);
 
 pub trait Instance: sealed::Sealed + Deref<Target = spis0::RegisterBlock> {
     const INTERRUPT: Interrupt;
@@ -1,20 +1,47 @@
 //! Temperature sensor interface.
+//!
+//! Temperatures are reported in °C as [`I30F2`] values, matching the 0.25 °C resolution of the
+//! sensor. The sensor is uncalibrated and only accurate to about ±5 °C, so a per-device
+//! calibration offset can be applied with [`Temp::set_offset`].
 
 #[cfg(not(feature = "5340-net"))]
 use crate::pac::TEMP;
 #[cfg(feature = "5340-net")]
 use crate::pac::TEMP_NS as TEMP;
 
+use core::future::poll_fn;
+use core::mem;
+use core::task::Poll;
+
 use fixed::types::I30F2;
 use void::Void;
 
+use crate::waker::AtomicWaker;
+
 /// Integrated temperature sensor.
-pub struct Temp(TEMP);
+pub struct Temp {
+    temp: TEMP,
+    /// Whether a measurement started by `measure_nb` is in progress.
+    measuring: bool,
+    offset: I30F2,
+}
+
+/// The waker of the task waiting in [`Temp::measure_async`].
+static WAKER: AtomicWaker = AtomicWaker::new();
 
 impl Temp {
     /// Creates a new `Temp`, taking ownership of the temperature sensor's register block.
     pub fn new(raw: TEMP) -> Self {
-        Temp(raw)
+        Temp {
+            temp: raw,
+            measuring: false,
+            offset: I30F2::ZERO,
+        }
+    }
+
+    /// Sets a calibration offset, in °C, that is added to every measured temperature.
+    pub fn set_offset(&mut self, offset: I30F2) {
+        self.offset = offset;
     }
 
     /// Starts a new measurement and blocks until completion.
@@ -40,20 +67,77 @@ impl Temp {
         nb::block!(self.read()).unwrap()
     }
 
+    /// Measures the temperature without blocking.
+    ///
+    /// The first call starts a measurement, and following calls return `WouldBlock` until it has
+    /// completed. Once the temperature has been returned, the next call starts a new measurement.
+    ///
+    /// Returns the measured temperature in °C.
+    pub fn measure_nb(&mut self) -> nb::Result<I30F2, Void> {
+        if !self.measuring {
+            self.stop_measurement();
+            self.start_measurement();
+            self.measuring = true;
+        }
+        let temp = self.read()?;
+        self.measuring = false;
+        Ok(temp)
+    }
+
+    /// Measures the temperature without blocking the executor.
+    ///
+    /// [`Temp::on_interrupt`] has to be called from the TEMP interrupt handler, and the interrupt
+    /// has to be unmasked in the NVIC. Dropping the future cancels the measurement.
+    ///
+    /// Returns the measured temperature in °C.
+    pub async fn measure_async(&mut self) -> I30F2 {
+        self.stop_measurement();
+        self.start_measurement();
+
+        let temp = &self.temp;
+        let stop = StopOnDrop(temp);
+        poll_fn(|cx| {
+            WAKER.register(cx.waker());
+            if temp.events_datardy.read().bits() != 0 {
+                Poll::Ready(())
+            } else {
+                temp.intenset.write(|w| w.datardy().set());
+                Poll::Pending
+            }
+        })
+        .await;
+        mem::forget(stop);
+
+        nb::block!(self.read()).unwrap()
+    }
+
+    /// Wakes the task waiting in [`Temp::measure_async`].
+    ///
+    /// Call this from the TEMP interrupt handler.
+    pub fn on_interrupt() {
+        let temp = unsafe { &*TEMP::ptr() };
+        if temp.events_datardy.read().bits() != 0 {
+            temp.intenclr.write(|w| w.datardy().clear());
+            WAKER.wake();
+        }
+    }
+
     /// Kicks off a temperature measurement.
     ///
     /// The measurement can be retrieved by calling `read`.
     pub fn start_measurement(&mut self) {
         unsafe {
-            self.0.tasks_start.write(|w| w.bits(1));
+            self.temp.tasks_start.write(|w| w.bits(1));
         }
     }
 
     /// Cancels an in-progress temperature measurement.
     pub fn stop_measurement(&mut self) {
+        self.measuring = false;
+        self.temp.intenclr.write(|w| w.datardy().clear());
         unsafe {
-            self.0.tasks_stop.write(|w| w.bits(1));
-            self.0.events_datardy.reset();
+            self.temp.tasks_stop.write(|w| w.bits(1));
+            self.temp.events_datardy.reset();
         }
     }
 
@@ -63,12 +147,43 @@ impl Temp {
     ///
     /// Returns the measured temperature in °C.
     pub fn read(&mut self) -> nb::Result<I30F2, Void> {
-        if self.0.events_datardy.read().bits() == 0 {
+        if self.temp.events_datardy.read().bits() == 0 {
             Err(nb::Error::WouldBlock)
         } else {
-            self.0.events_datardy.reset(); // clear event
-            let raw = self.0.temp.read().bits();
-            Ok(I30F2::from_bits(raw as i32))
+            self.temp.events_datardy.reset(); // clear event
+            let raw = self.temp.temp.read().bits();
+            Ok(I30F2::from_bits(raw as i32) + self.offset)
         }
     }
 }
+
+/// Conversions of a temperature in °C, as returned by [`Temp`].
+pub trait CelsiusExt {
+    /// Returns the temperature in thousandths of a degree Celsius.
+    fn to_millidegrees(self) -> i32;
+
+    /// Returns the temperature in degrees Celsius as a floating point value.
+    fn to_f32(self) -> f32;
+}
+
+impl CelsiusExt for I30F2 {
+    fn to_millidegrees(self) -> i32 {
+        // One LSB is 0.25 °C.
+        self.to_bits() * 250
+    }
+
+    fn to_f32(self) -> f32 {
+        self.to_num()
+    }
+}
+
+/// Cancels a measurement when an async measurement is dropped before completion.
+struct StopOnDrop<'a>(&'a TEMP);
+
+impl Drop for StopOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.intenclr.write(|w| w.datardy().clear());
+        self.0.tasks_stop.write(|w| unsafe { w.bits(1) });
+        self.0.events_datardy.reset();
+    }
+}
@@ -39,7 +39,21 @@ use crate::pac::timer0::{
     RegisterBlock as RegBlock3, EVENTS_COMPARE as EventsCompare3, TASKS_CAPTURE as TasksCapture3,
 };
 
-use core::{hint::spin_loop, marker::PhantomData};
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+use crate::{
+    gpio::Level,
+    gpiote::{config_channel_task_pin, GpioteOutputPin, TaskOutPolarity},
+    pac::GPIOTE,
+};
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+use crate::{gpiote::GpioteChannel, ppi::ConfigurablePpi};
+
+use core::{future::poll_fn, hint::spin_loop, marker::PhantomData, mem, task::Poll};
 
 pub struct OneShot;
 pub struct Periodic;
@@ -102,6 +116,24 @@ where
         self.0
     }
 
+    /// Turns the timer into a free-running 32-bit microsecond clock.
+    ///
+    /// The timer is started immediately and never stops or clears, see [`MicroCounter`].
+    pub fn as_microsecond_counter(self) -> MicroCounter<T> {
+        let regs = self.0.as_timer0();
+        regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+        regs.intenclr.write(|w| unsafe { w.bits(0xFFFF_FFFF) });
+        regs.shorts.reset();
+        regs.prescaler.write(
+            |w| unsafe { w.prescaler().bits(4) }, // 1 MHz
+        );
+        regs.bitmode.write(|w| w.bitmode()._32bit());
+        regs.tasks_clear.write(|w| unsafe { w.bits(1) });
+        regs.tasks_start.write(|w| unsafe { w.bits(1) });
+
+        MicroCounter(self.free())
+    }
+
     /// Return the current value of the counter, by capturing to CC\[1\].
     pub fn read(&self) -> u32 {
         self.0.read_counter()
@@ -186,6 +218,63 @@ where
         }
     }
 
+    /// Sets CC\[`cc`\] so that its COMPARE event fires when the counter reaches `ticks`.
+    ///
+    /// The four compare registers work independently, so a single timer can schedule several
+    /// events, e.g. by starting it with `start(u32::MAX)` and setting CC\[1\] to CC\[3\]
+    /// relative to [`Timer::read`]. Keep in mind that CC\[0\] holds the value given to
+    /// [`Timer::start`] and drives the one-shot/periodic shortcuts, and that [`Timer::read`]
+    /// overwrites CC\[1\].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cc` is not below 4.
+    pub fn set_compare(&mut self, cc: usize, ticks: u32) {
+        self.0.as_timer0().cc[cc].write(|w| unsafe { w.bits(ticks) });
+    }
+
+    /// Returns whether the COMPARE event of CC\[`cc`\] has fired since it was last reset.
+    pub fn is_compare_triggered(&self, cc: usize) -> bool {
+        self.0.as_timer0().events_compare[cc].read().bits() != 0
+    }
+
+    /// Resets the COMPARE event of CC\[`cc`\].
+    pub fn reset_compare_event(&self, cc: usize) {
+        self.0.as_timer0().events_compare[cc].reset();
+    }
+
+    /// Enables the interrupt for the COMPARE event of CC\[`cc`\].
+    ///
+    /// Note that the interrupt also has to be unmasked in the NVIC, or the
+    /// handler won't get called.
+    pub fn enable_compare_interrupt(&mut self, cc: usize) {
+        assert!(cc < 4);
+        self.0
+            .as_timer0()
+            .intenset
+            .write(|w| unsafe { w.bits(1 << (16 + cc)) });
+    }
+
+    /// Disables the interrupt for the COMPARE event of CC\[`cc`\].
+    pub fn disable_compare_interrupt(&mut self, cc: usize) {
+        assert!(cc < 4);
+        self.0
+            .as_timer0()
+            .intenclr
+            .write(|w| unsafe { w.bits(1 << (16 + cc)) });
+    }
+
+    /// Returns the value last captured into CC\[`cc`\].
+    ///
+    /// Together with [`Timer::capture_on_edge`], this gives the timestamp of the last edge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cc` is not below 4.
+    pub fn read_capture(&self, cc: usize) -> u32 {
+        self.0.as_timer0().cc[cc].read().bits()
+    }
+
     /// Returns reference to the `START` task endpoint for PPI.
     /// Starts timer.
     #[inline(always)]
@@ -275,6 +364,235 @@ where
     }
 }
 
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+impl<T, U> Timer<T, U>
+where
+    T: Instance,
+{
+    /// Turns the timer into a single-channel PWM that runs without CPU involvement.
+    ///
+    /// CC\[1\] holds the `period` and clears the counter, its COMPARE event sets the pin through
+    /// `ppi_period` and the GPIOTE channel. CC\[0\] holds the `duty` cycle, its COMPARE event
+    /// clears the pin through `ppi_duty`. Both values are given in ticks of 1 MHz.
+    ///
+    /// This is useful on devices with few PWM instances when a TIMER is spare. Use
+    /// [`TimerPwm::free`] to get the timer, the pin and the PPI channels back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn into_pwm_channel<P, C0, C1>(
+        self,
+        gpiote: &GpioteChannel<'_>,
+        pin: P,
+        mut ppi_period: C0,
+        mut ppi_duty: C1,
+        period: u32,
+        duty: u32,
+    ) -> TimerPwm<T, P, C0, C1>
+    where
+        P: GpioteOutputPin,
+        C0: ConfigurablePpi,
+        C1: ConfigurablePpi,
+    {
+        assert!(period > 0);
+
+        let timer = self.free();
+        let regs = timer.as_timer0();
+        regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+        regs.intenclr.write(|w| unsafe { w.bits(0xFFFF_FFFF) });
+        regs.shorts.write(|w| w.compare1_clear().enabled());
+        regs.prescaler.write(
+            |w| unsafe { w.prescaler().bits(4) }, // 1 MHz
+        );
+        regs.bitmode.write(|w| w.bitmode()._32bit());
+
+        config_channel_task_pin(
+            gpiote.gpiote,
+            gpiote.channel,
+            &pin,
+            &TaskOutPolarity::Toggle,
+            Level::Low,
+        );
+
+        ppi_period.set_event_endpoint(&regs.events_compare[1]);
+        ppi_period.set_task_endpoint(gpiote.task_set());
+        ppi_duty.set_event_endpoint(&regs.events_compare[0]);
+        ppi_duty.set_task_endpoint(gpiote.task_clr());
+
+        let mut pwm = TimerPwm {
+            timer,
+            pin,
+            ppi_period,
+            ppi_duty,
+            channel: gpiote.channel,
+            period,
+            duty: 0,
+        };
+        pwm.set_duty(duty);
+
+        let regs = pwm.timer.as_timer0();
+        regs.tasks_clear.write(|w| unsafe { w.bits(1) });
+        regs.tasks_start.write(|w| unsafe { w.bits(1) });
+        pwm
+    }
+}
+
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+impl<T, U> Timer<T, U>
+where
+    T: Instance,
+{
+    /// Captures the counter into CC\[`cc`\] in hardware whenever the GPIOTE channel fires.
+    ///
+    /// The GPIOTE channel has to be configured as an input with the wanted edge beforehand, e.g.
+    /// `gpiote.channel0().input_pin(&pin).lo_to_hi()`. Its event is connected to the `CAPTURE`
+    /// task through `ppi`, so the timestamp is free of interrupt latency. Read it with
+    /// [`Timer::read_capture`].
+    ///
+    /// CC\[0\] holds the timeout given to [`Timer::start`] and CC\[1\] is overwritten by
+    /// [`Timer::read`], so use CC\[2\] or CC\[3\] unless the timer is only used for capturing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cc` is not below 4.
+    pub fn capture_on_edge<C>(&mut self, cc: usize, gpiote: &GpioteChannel<'_>, ppi: &mut C)
+    where
+        C: ConfigurablePpi,
+    {
+        ppi.set_event_endpoint(gpiote.event());
+        ppi.set_task_endpoint(&self.0.as_timer0().tasks_capture[cc]);
+        ppi.enable();
+    }
+}
+
+/// A single-channel PWM built from a TIMER, a GPIOTE channel and two PPI channels.
+///
+/// Created by [`Timer::into_pwm_channel`].
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+pub struct TimerPwm<T, P, C0, C1> {
+    timer: T,
+    pin: P,
+    ppi_period: C0,
+    ppi_duty: C1,
+    channel: usize,
+    period: u32,
+    duty: u32,
+}
+
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+impl<T, P, C0, C1> TimerPwm<T, P, C0, C1>
+where
+    T: Instance,
+    P: GpioteOutputPin,
+    C0: ConfigurablePpi,
+    C1: ConfigurablePpi,
+{
+    /// Returns the period in ticks of 1 MHz, which is also the maximum duty cycle.
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// Returns the duty cycle in ticks of 1 MHz.
+    pub fn duty(&self) -> u32 {
+        self.duty
+    }
+
+    /// Sets the duty cycle, i.e. the number of ticks the pin is high per period.
+    ///
+    /// Values above [`TimerPwm::period`] are clamped. A duty cycle of 0 keeps the pin low and a
+    /// duty cycle equal to the period keeps it high. The new value is written immediately, so
+    /// the current period may get one stretched pulse.
+    pub fn set_duty(&mut self, duty: u32) {
+        let duty = duty.min(self.period);
+        self.duty = duty;
+
+        let regs = self.timer.as_timer0();
+        regs.cc[1].write(|w| unsafe { w.bits(self.period) });
+        regs.cc[0].write(|w| unsafe { w.bits(duty) });
+
+        // The compare events never fire at a count of 0, so the edge cases are handled by
+        // disabling the channel producing the unwanted edge.
+        let gpiote = unsafe { &*GPIOTE::ptr() };
+        if duty == 0 {
+            self.ppi_period.disable();
+            self.ppi_duty.enable();
+            gpiote.tasks_clr[self.channel].write(|w| unsafe { w.bits(1) });
+        } else if duty == self.period {
+            self.ppi_duty.disable();
+            self.ppi_period.enable();
+            gpiote.tasks_set[self.channel].write(|w| unsafe { w.bits(1) });
+        } else {
+            self.ppi_period.enable();
+            self.ppi_duty.enable();
+        }
+    }
+
+    /// Stops the PWM and returns the timer, the pin and the PPI channels.
+    ///
+    /// The GPIOTE channel is disabled, so the pin falls back to its GPIO configuration.
+    pub fn free(mut self) -> (T, P, C0, C1) {
+        let regs = self.timer.as_timer0();
+        regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+        regs.shorts.reset();
+
+        self.ppi_period.disable();
+        self.ppi_duty.disable();
+
+        let gpiote = unsafe { &*GPIOTE::ptr() };
+        gpiote.config[self.channel].reset();
+
+        (self.timer, self.pin, self.ppi_period, self.ppi_duty)
+    }
+}
+
+/// A free-running microsecond clock, created by [`Timer::as_microsecond_counter`].
+///
+/// The counter is 32 bits wide at 1 MHz, so it wraps around to 0 after 2^32 µs, about 71.6
+/// minutes. [`MicroCounter::elapsed_since`] handles a single wraparound, so it is correct for
+/// intervals shorter than that.
+pub struct MicroCounter<T>(T);
+
+impl<T> MicroCounter<T>
+where
+    T: Instance,
+{
+    /// Returns the current time in microseconds, by capturing to CC\[1\].
+    pub fn now_us(&self) -> u32 {
+        self.0.read_counter()
+    }
+
+    /// Returns the microseconds elapsed since `start`, a value returned by
+    /// [`MicroCounter::now_us`].
+    pub fn elapsed_since(&self, start: u32) -> u32 {
+        self.now_us().wrapping_sub(start)
+    }
+
+    /// Stops the counter and returns the raw timer peripheral.
+    pub fn free(self) -> T {
+        self.0
+            .as_timer0()
+            .tasks_stop
+            .write(|w| unsafe { w.bits(1) });
+        self.0
+    }
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<T, U> embedded_hal_02::timer::CountDown for Timer<T, U>
 where
@@ -392,6 +710,84 @@ impl<T: Instance, U> DelayNs for Timer<T, U> {
     }
 }
 
+/// A TIMER used as an async delay provider.
+///
+/// The timer runs at 1 MHz, and the task sleeps until the compare interrupt fires instead of
+/// busy-waiting. [`TimerDelay::on_interrupt`] has to be called from the interrupt handler of the
+/// TIMER instance, and the interrupt has to be unmasked in the NVIC.
+pub struct TimerDelay<T>(T);
+
+impl<T: Instance> TimerDelay<T> {
+    /// Configures the timer as an async delay provider.
+    pub fn new(timer: T) -> Self {
+        timer.timer_cancel();
+        timer.set_oneshot();
+        TimerDelay(timer)
+    }
+
+    /// Releases the timer.
+    pub fn free(self) -> T {
+        self.0
+    }
+
+    /// Wakes the task waiting on the delay.
+    ///
+    /// Call this from the interrupt handler of the TIMER instance.
+    pub fn on_interrupt() {
+        let timer = unsafe { &*T::PTR };
+        if timer.events_compare[0].read().bits() != 0 {
+            timer.intenclr.write(|w| w.compare0().clear());
+            T::waker().wake();
+        }
+    }
+
+    async fn delay_us_u64(&mut self, mut us: u64) {
+        while us > 0 {
+            let cycles = us.min(u32::MAX as u64) as u32;
+            us -= cycles as u64;
+
+            self.0.timer_start(cycles);
+            self.0.enable_interrupt();
+            let stop = StopOnDrop(&self.0);
+            poll_fn(|cx| {
+                T::waker().register(cx.waker());
+                if self.0.timer_running() {
+                    Poll::Pending
+                } else {
+                    Poll::Ready(())
+                }
+            })
+            .await;
+            mem::forget(stop);
+            self.0.timer_reset_event();
+        }
+    }
+}
+
+impl<T: Instance> embedded_hal_async::delay::DelayNs for TimerDelay<T> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.delay_us_u64(ns.div_ceil(1_000) as u64).await;
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        self.delay_us_u64(us as u64).await;
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.delay_us_u64(ms as u64 * 1_000).await;
+    }
+}
+
+/// Stops the timer when an async delay is dropped before it has elapsed.
+struct StopOnDrop<'a, T: Instance>(&'a T);
+
+impl<T: Instance> Drop for StopOnDrop<'_, T> {
+    fn drop(&mut self) {
+        self.0.disable_interrupt();
+        self.0.timer_cancel();
+    }
+}
+
 /// Implemented by all TIMER* instances.
 pub trait Instance: sealed::Sealed {
     /// The interrupt associated with this RTC instance.
@@ -633,14 +1029,40 @@ impl ExtendedCCTimer for Timer<TIMER4> {
 }
 
 mod sealed {
-    pub trait Sealed {}
-    impl Sealed for super::TIMER0 {}
-    impl Sealed for super::TIMER1 {}
-    impl Sealed for super::TIMER2 {}
+    use super::RegBlock0;
+    use crate::waker::AtomicWaker;
 
-    #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
-    impl Sealed for super::TIMER3 {}
+    pub trait Sealed {
+        /// Pointer to the register block of the instance.
+        const PTR: *const RegBlock0;
 
-    #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
-    impl Sealed for super::TIMER4 {}
+        /// The waker of the task waiting in an async delay.
+        fn waker() -> &'static AtomicWaker;
+    }
+}
+
+macro_rules! impl_sealed {
+    ($($(#[$attr:meta])* $timer:ident,)*) => {
+        $(
+            $(#[$attr])*
+            impl sealed::Sealed for $timer {
+                const PTR: *const RegBlock0 = $timer::PTR as *const RegBlock0;
+
+                fn waker() -> &'static crate::waker::AtomicWaker {
+                    static WAKER: crate::waker::AtomicWaker = crate::waker::AtomicWaker::new();
+                    &WAKER
+                }
+            }
+        )*
+    };
 }
+
+impl_sealed!(
+    TIMER0,
+    TIMER1,
+    TIMER2,
+    #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+    TIMER3,
+    #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+    TIMER4,
+);
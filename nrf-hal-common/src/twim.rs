@@ -4,8 +4,12 @@
 //!
 //! - nRF52832: Section 33
 //! - nRF52840: Section 6.31
-use core::ops::Deref;
+use core::future::poll_fn;
+use core::mem;
+use core::ops::{Deref, Range};
 use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+use core::task::Poll;
+use embedded_hal::digital::{InputPin, OutputPin};
 use embedded_hal::i2c::{self, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
 
 #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
@@ -21,13 +25,23 @@ use crate::pac::{twim0, TWIM0};
 use crate::pac::TWIM1;
 
 use crate::{
-    gpio::{Floating, Input, Pin},
+    clocks::HFCLK_FREQ,
+    gpio::{Disconnected, Floating, Input, Level, OpenDrainConfig, Pin},
     slice_in_ram_or,
     target_constants::{EASY_DMA_SIZE, FORCE_COPY_BUFFER_SIZE},
 };
 
 pub use twim0::frequency::FREQUENCY_A as Frequency;
 
+/// Highest `FREQUENCY` register value the chip supports.
+///
+/// Only the nRF5340 TWIM supports Fast-mode Plus (1000 kbps, [`Frequency::K1000`]); other chips
+/// are limited to Fast-mode (400 kbps).
+#[cfg(any(feature = "5340-app", feature = "5340-net"))]
+const MAX_FREQUENCY_RAW: u32 = Frequency::K1000 as u32;
+#[cfg(not(any(feature = "5340-app", feature = "5340-net")))]
+const MAX_FREQUENCY_RAW: u32 = Frequency::K400 as u32;
+
 /// Interface to a TWIM instance.
 ///
 /// This is a very basic interface that comes with the following limitation:
@@ -37,12 +51,23 @@ pub use twim0::frequency::FREQUENCY_A as Frequency;
 /// conflicting instances are disabled before using `Twim`. Please refer to the
 /// product specification for more information (section 15.2 for nRF52832,
 /// section 6.1.2 for nRF52840).
-pub struct Twim<T>(T);
+pub struct Twim<T> {
+    twim: T,
+    /// Transfer timeout in microseconds, see [`Twim::set_timeout`].
+    timeout_us: Option<u32>,
+}
 
 impl<T> Twim<T>
 where
     T: Instance,
 {
+    /// The largest number of bytes a single EasyDMA transfer can move, e.g. 255 on the nRF52832
+    /// and 65535 on the nRF52840.
+    ///
+    /// Longer buffers passed to [`Twim::write`], [`Twim::read`] and [`Twim::write_then_read`]
+    /// are transparently split into several transfers.
+    pub const MAX_TRANSFER_SIZE: usize = EASY_DMA_SIZE;
+
     pub fn new(twim: T, pins: Pins, frequency: Frequency) -> Self {
         // The TWIM peripheral requires the pins to be in a mode that is not
         // exposed through the GPIO API, and might it might not make sense to
@@ -53,18 +78,7 @@ where
         // safe, as we own the pins now and have exclusive access to their
         // registers.
         for &pin in &[&pins.scl, &pins.sda] {
-            pin.conf().write(|w| {
-                w.dir()
-                    .input()
-                    .input()
-                    .connect()
-                    .pull()
-                    .pullup()
-                    .drive()
-                    .s0d1()
-                    .sense()
-                    .disabled()
-            });
+            configure_pin(pin);
         }
 
         // Select pins.
@@ -83,7 +97,105 @@ where
         // Configure frequency.
         twim.frequency.write(|w| w.frequency().variant(frequency));
 
-        Twim(twim)
+        Twim {
+            twim,
+            timeout_us: None,
+        }
+    }
+
+    /// Changes the SCL frequency.
+    pub fn set_frequency(&mut self, frequency: Frequency) {
+        self.twim
+            .frequency
+            .write(|w| w.frequency().variant(frequency));
+    }
+
+    /// Changes the SCL frequency to a raw `FREQUENCY` register value.
+    ///
+    /// The register holds approximately `f_scl * 2^32 / 16 MHz`, which allows frequencies
+    /// between the presets of [`Frequency`]. Values below 100 kbps or above the fastest preset
+    /// of the chip are rejected with [`Error::InvalidFrequency`]. Fast-mode Plus (1000 kbps) is
+    /// only supported on the nRF5340; the nRF52 and nRF9160 series stop at 400 kbps.
+    pub fn set_frequency_raw(&mut self, raw: u32) -> Result<(), Error> {
+        if raw < Frequency::K100 as u32 || raw > MAX_FREQUENCY_RAW {
+            return Err(Error::InvalidFrequency);
+        }
+        self.twim.frequency.write(|w| unsafe { w.bits(raw) });
+        Ok(())
+    }
+
+    /// Sets a timeout for transfers, in microseconds, or disables it with `None`.
+    ///
+    /// A transfer that has not finished within the timeout, e.g. because a slave is holding SCL
+    /// low, is aborted by resetting the peripheral and fails with [`Error::Timeout`]. The bus may
+    /// then need to be freed with [`Twim::recover_bus`]. The timeout is approximate, as it is
+    /// counted in polling iterations. Transfers split into several parts apply it to each part.
+    pub fn set_timeout(&mut self, timeout_us: Option<u32>) {
+        self.timeout_us = timeout_us;
+    }
+
+    /// Frees a bus that is held by a slave stuck in the middle of a transfer.
+    ///
+    /// A slave that was interrupted (e.g. by a reset of the master) while sending a 0 bit keeps
+    /// SDA low indefinitely. This disables the peripheral, clocks SCL manually at about 100 kHz
+    /// until the slave releases SDA (at most 9 pulses), generates a STOP condition and then hands
+    /// the pins back to the peripheral.
+    ///
+    /// Returns [`Error::BusStuck`] if SDA is still held low afterwards.
+    pub fn recover_bus(&mut self) -> Result<(), Error> {
+        // Half a period of a 100 kHz clock.
+        const HALF_PERIOD: u32 = HFCLK_FREQ / 200_000;
+
+        self.twim.enable.write(|w| w.enable().disabled());
+
+        // Safe, as the pins are owned by this instance and the peripheral no longer drives them.
+        let (mut scl, mut sda) = unsafe {
+            (
+                Pin::<Disconnected>::from_psel_bits(self.twim.psel.scl.read().bits())
+                    .into_open_drain_input_output(
+                        OpenDrainConfig::Standard0Disconnect1,
+                        Level::High,
+                    ),
+                Pin::<Disconnected>::from_psel_bits(self.twim.psel.sda.read().bits())
+                    .into_open_drain_input_output(
+                        OpenDrainConfig::Standard0Disconnect1,
+                        Level::High,
+                    ),
+            )
+        };
+        cortex_m::asm::delay(HALF_PERIOD);
+
+        for _ in 0..9 {
+            if sda.is_high().unwrap() {
+                break;
+            }
+            scl.set_low().unwrap();
+            cortex_m::asm::delay(HALF_PERIOD);
+            scl.set_high().unwrap();
+            cortex_m::asm::delay(HALF_PERIOD);
+        }
+
+        // STOP condition: SDA rising while SCL is high.
+        scl.set_low().unwrap();
+        cortex_m::asm::delay(HALF_PERIOD);
+        sda.set_low().unwrap();
+        cortex_m::asm::delay(HALF_PERIOD);
+        scl.set_high().unwrap();
+        cortex_m::asm::delay(HALF_PERIOD);
+        sda.set_high().unwrap();
+        cortex_m::asm::delay(HALF_PERIOD);
+
+        let released = sda.is_high().unwrap();
+
+        configure_pin(&scl);
+        configure_pin(&sda);
+        self.twim.enable.write(|w| w.enable().enabled());
+
+        if released {
+            Ok(())
+        } else {
+            Err(Error::BusStuck)
+        }
     }
 
     /// Disable the instance.
@@ -93,12 +205,12 @@ where
     /// it is not possible to use it for communication. The configuration of
     /// the instance will be retained.
     pub fn disable(&mut self) {
-        self.0.enable.write(|w| w.enable().disabled());
+        self.twim.enable.write(|w| w.enable().disabled());
     }
 
     /// Re-enable the instance after it was previously disabled.
     pub fn enable(&mut self) {
-        self.0.enable.write(|w| w.enable().enabled());
+        self.twim.enable.write(|w| w.enable().enabled());
     }
 
     /// Set TX buffer, checking that it is in RAM and has suitable length.
@@ -112,7 +224,7 @@ where
             return Err(Error::TxBufferTooLong);
         }
 
-        self.0.txd.ptr.write(|w|
+        self.twim.txd.ptr.write(|w|
             // We're giving the register a pointer to the stack. Since we're
             // waiting for the I2C transaction to end before this stack pointer
             // becomes invalid, there's nothing wrong here.
@@ -120,7 +232,7 @@ where
             // The PTR field is a full 32 bits wide and accepts the full range
             // of values.
             w.ptr().bits(buffer.as_ptr() as u32));
-        self.0.txd.maxcnt.write(|w|
+        self.twim.txd.maxcnt.write(|w|
             // We're giving it the length of the buffer, so no danger of
             // accessing invalid memory. We have verified that the length of the
             // buffer fits in an `u8`, so the cast to `u8` is also fine.
@@ -144,7 +256,7 @@ where
             return Err(Error::RxBufferTooLong);
         }
 
-        self.0.rxd.ptr.write(|w|
+        self.twim.rxd.ptr.write(|w|
             // We're giving the register a pointer to the stack. Since we're
             // waiting for the I2C transaction to end before this stack pointer
             // becomes invalid, there's nothing wrong here.
@@ -152,7 +264,7 @@ where
             // The PTR field is a full 32 bits wide and accepts the full range
             // of values.
             w.ptr().bits(buffer.as_mut_ptr() as u32));
-        self.0.rxd.maxcnt.write(|w|
+        self.twim.rxd.maxcnt.write(|w|
             // We're giving it the length of the buffer, so no danger of
             // accessing invalid memory. We have verified that the length of the
             // buffer fits in an `u8`, so the cast to the type of maxcnt
@@ -168,14 +280,14 @@ where
     }
 
     fn clear_errorsrc(&mut self) {
-        self.0
+        self.twim
             .errorsrc
             .write(|w| w.anack().bit(true).dnack().bit(true).overrun().bit(true));
     }
 
     /// Get Error instance, if any occurred.
     fn read_errorsrc(&self) -> Result<(), Error> {
-        let err = self.0.errorsrc.read();
+        let err = self.twim.errorsrc.read();
         if err.anack().is_received() {
             return Err(Error::AddressNack);
         }
@@ -188,35 +300,51 @@ where
         Ok(())
     }
 
-    /// Wait for stop or error
-    fn wait(&mut self) {
+    /// Wait for stop or error, or until the timeout expires.
+    fn wait(&mut self) -> Result<(), Error> {
+        let mut remaining_us = self.timeout_us;
         loop {
-            if self.0.events_stopped.read().bits() != 0 {
-                self.0.events_stopped.reset();
-                break;
+            if self.twim.events_stopped.read().bits() != 0 {
+                self.twim.events_stopped.reset();
+                return Ok(());
             }
-            if self.0.events_suspended.read().bits() != 0 {
-                self.0.events_suspended.reset();
-                break;
+            if self.twim.events_suspended.read().bits() != 0 {
+                self.twim.events_suspended.reset();
+                return Ok(());
             }
-            if self.0.events_error.read().bits() != 0 {
-                self.0.events_error.reset();
-                self.0.tasks_stop.write(|w| unsafe { w.bits(1) });
+            if self.twim.events_error.read().bits() != 0 {
+                self.twim.events_error.reset();
+                self.twim.tasks_stop.write(|w| unsafe { w.bits(1) });
+            }
+            if let Some(us) = remaining_us.as_mut() {
+                if *us == 0 {
+                    // A STOP can't be generated while a slave holds SCL, so reset the
+                    // peripheral instead.
+                    self.twim.enable.write(|w| w.enable().disabled());
+                    self.twim.enable.write(|w| w.enable().enabled());
+                    return Err(Error::Timeout);
+                }
+                *us -= 1;
+                cortex_m::asm::delay(HFCLK_FREQ / 1_000_000);
             }
         }
     }
 
     /// Write to an I2C slave.
     ///
-    /// The buffer must have a length of at most 255 bytes on the nRF52832
-    /// and at most 65535 bytes on the nRF52840.
+    /// Buffers longer than [`Twim::MAX_TRANSFER_SIZE`] are sent in several chunks within a
+    /// single transaction, suspending the bus between chunks, so there is only one STOP.
     pub fn write(&mut self, address: u8, buffer: &[u8]) -> Result<(), Error> {
+        if buffer.len() > EASY_DMA_SIZE {
+            return self.write_read_chunked(address, buffer, &mut []);
+        }
+
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
         // before any DMA action has started.
         compiler_fence(SeqCst);
 
-        self.0
+        self.twim
             .address
             .write(|w| unsafe { w.address().bits(address) });
 
@@ -224,18 +352,18 @@ where
         unsafe { self.set_tx_buffer(buffer)? };
 
         // Clear events
-        self.0.events_stopped.reset();
-        self.0.events_error.reset();
-        self.0.events_lasttx.reset();
+        self.twim.events_stopped.reset();
+        self.twim.events_error.reset();
+        self.twim.events_lasttx.reset();
         self.clear_errorsrc();
 
         // Start write operation.
-        self.0.shorts.write(|w| w.lasttx_stop().enabled());
-        self.0.tasks_starttx.write(|w|
+        self.twim.shorts.write(|w| w.lasttx_stop().enabled());
+        self.twim.tasks_starttx.write(|w|
             // `1` is a valid value to write to task registers.
             unsafe { w.bits(1) });
 
-        self.wait();
+        self.wait()?;
 
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
@@ -244,7 +372,7 @@ where
 
         self.read_errorsrc()?;
 
-        if self.0.txd.amount.read().bits() != buffer.len() as u32 {
+        if self.twim.txd.amount.read().bits() != buffer.len() as u32 {
             return Err(Error::Transmit);
         }
 
@@ -253,15 +381,21 @@ where
 
     /// Read from an I2C slave.
     ///
-    /// The buffer must have a length of at most 255 bytes on the nRF52832
-    /// and at most 65535 bytes on the nRF52840.
+    /// Buffers longer than [`Twim::MAX_TRANSFER_SIZE`] are received in several chunks. The
+    /// peripheral can't suspend between reads, so each chunk is a separate read with its own
+    /// START and STOP. This suits devices that auto-increment their internal address, such as
+    /// EEPROMs.
     pub fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error> {
+        if buffer.len() > EASY_DMA_SIZE {
+            return self.write_read_chunked(address, &[], buffer);
+        }
+
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
         // before any DMA action has started.
         compiler_fence(SeqCst);
 
-        self.0
+        self.twim
             .address
             .write(|w| unsafe { w.address().bits(address) });
 
@@ -269,17 +403,17 @@ where
         unsafe { self.set_rx_buffer(buffer)? };
 
         // Clear events
-        self.0.events_stopped.reset();
-        self.0.events_error.reset();
+        self.twim.events_stopped.reset();
+        self.twim.events_error.reset();
         self.clear_errorsrc();
 
         // Start read operation.
-        self.0.shorts.write(|w| w.lastrx_stop().enabled());
-        self.0.tasks_startrx.write(|w|
+        self.twim.shorts.write(|w| w.lastrx_stop().enabled());
+        self.twim.tasks_startrx.write(|w|
             // `1` is a valid value to write to task registers.
             unsafe { w.bits(1) });
 
-        self.wait();
+        self.wait()?;
 
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
@@ -288,7 +422,7 @@ where
 
         self.read_errorsrc()?;
 
-        if self.0.rxd.amount.read().bits() != buffer.len() as u32 {
+        if self.twim.rxd.amount.read().bits() != buffer.len() as u32 {
             return Err(Error::Receive);
         }
 
@@ -298,20 +432,26 @@ where
     /// Write data to an I2C slave, then read data from the slave without
     /// triggering a stop condition between the two.
     ///
-    /// The buffers must have a length of at most 255 bytes on the nRF52832
-    /// and at most 65535 bytes on the nRF52840.
+    /// Buffers longer than [`Twim::MAX_TRANSFER_SIZE`] are split into chunks. The write chunks
+    /// are joined by suspending the bus, and the first read chunk follows them with a repeated
+    /// start. Each read chunk ends with its own STOP, so every further read chunk is a separate
+    /// read with its own START and STOP, as described for [`Twim::read`].
     pub fn write_then_read(
         &mut self,
         address: u8,
         wr_buffer: &[u8],
         rd_buffer: &mut [u8],
     ) -> Result<(), Error> {
+        if wr_buffer.len() > EASY_DMA_SIZE || rd_buffer.len() > EASY_DMA_SIZE {
+            return self.write_read_chunked(address, wr_buffer, rd_buffer);
+        }
+
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
         // before any DMA action has started.
         compiler_fence(SeqCst);
 
-        self.0
+        self.twim
             .address
             .write(|w| unsafe { w.address().bits(address) });
 
@@ -322,20 +462,20 @@ where
         }
 
         // Clear events
-        self.0.events_stopped.reset();
-        self.0.events_error.reset();
+        self.twim.events_stopped.reset();
+        self.twim.events_error.reset();
         self.clear_errorsrc();
 
         // Start write+read operation.
-        self.0.shorts.write(|w| {
+        self.twim.shorts.write(|w| {
             w.lasttx_startrx().enabled();
             w.lastrx_stop().enabled();
             w
         });
         // `1` is a valid value to write to task registers.
-        self.0.tasks_starttx.write(|w| unsafe { w.bits(1) });
+        self.twim.tasks_starttx.write(|w| unsafe { w.bits(1) });
 
-        self.wait();
+        self.wait()?;
 
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
@@ -344,8 +484,8 @@ where
 
         self.read_errorsrc()?;
 
-        let bad_write = self.0.txd.amount.read().bits() != wr_buffer.len() as u32;
-        let bad_read = self.0.rxd.amount.read().bits() != rd_buffer.len() as u32;
+        let bad_write = self.twim.txd.amount.read().bits() != wr_buffer.len() as u32;
+        let bad_read = self.twim.rxd.amount.read().bits() != rd_buffer.len() as u32;
 
         if bad_write {
             return Err(Error::Transmit);
@@ -358,14 +498,210 @@ where
         Ok(())
     }
 
+    /// Write data to an I2C slave, then read data from the slave after a repeated start.
+    ///
+    /// This is the usual register read: the register address is written, and the `LASTTX` to
+    /// `STARTRX` shortcut turns the end of the write into a repeated start, so the only STOP
+    /// condition is generated after the read. A write buffer located in flash is copied to RAM
+    /// first, like [`copy_write_then_read`](Self::copy_write_then_read).
+    ///
+    /// Buffers longer than [`Twim::MAX_TRANSFER_SIZE`] are split into chunks as described for
+    /// [`Twim::write_then_read`], so a read chunk after the first one gets its own START and
+    /// STOP.
+    pub fn write_read(
+        &mut self,
+        address: u8,
+        wr_buffer: &[u8],
+        rd_buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        if crate::slice_in_ram(wr_buffer) {
+            self.write_then_read(address, wr_buffer, rd_buffer)
+        } else {
+            self.copy_write_then_read(address, wr_buffer, rd_buffer)
+        }
+    }
+
+    /// Write to an I2C slave without blocking.
+    ///
+    /// Like [`Twim::write`], but yields to the executor while the transfer runs.
+    /// [`Twim::on_interrupt`] has to be called from the interrupt handler of the TWIM instance,
+    /// and the interrupt has to be unmasked in the NVIC. The buffer must be in RAM and at most
+    /// [`Twim::MAX_TRANSFER_SIZE`] bytes long, and the timeout set with [`Twim::set_timeout`]
+    /// does not apply. Dropping the future stops the transfer.
+    pub async fn write_async(&mut self, address: u8, buffer: &[u8]) -> Result<(), Error> {
+        self.transfer_async(address, Some(buffer), None).await
+    }
+
+    /// Read from an I2C slave without blocking.
+    ///
+    /// See [`Twim::write_async`] for the requirements.
+    pub async fn read_async(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error> {
+        self.transfer_async(address, None, Some(buffer)).await
+    }
+
+    /// Write data to an I2C slave, then read data from the slave after a repeated start,
+    /// without blocking.
+    ///
+    /// See [`Twim::write_async`] for the requirements.
+    pub async fn write_read_async(
+        &mut self,
+        address: u8,
+        wr_buffer: &[u8],
+        rd_buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        self.transfer_async(address, Some(wr_buffer), Some(rd_buffer))
+            .await
+    }
+
+    async fn transfer_async(
+        &mut self,
+        address: u8,
+        wr_buffer: Option<&[u8]>,
+        mut rd_buffer: Option<&mut [u8]>,
+    ) -> Result<(), Error> {
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // before any DMA action has started.
+        compiler_fence(SeqCst);
+
+        self.twim
+            .address
+            .write(|w| unsafe { w.address().bits(address) });
+
+        // Set up DMA buffers.
+        unsafe {
+            if let Some(buffer) = wr_buffer {
+                self.set_tx_buffer(buffer)?;
+            }
+            if let Some(buffer) = rd_buffer.as_deref_mut() {
+                self.set_rx_buffer(buffer)?;
+            }
+        }
+
+        // Clear events
+        self.twim.events_stopped.reset();
+        self.twim.events_error.reset();
+        self.twim.events_lasttx.reset();
+        self.twim.events_lastrx.reset();
+        self.clear_errorsrc();
+
+        self.twim
+            .shorts
+            .write(|w| match (wr_buffer.is_some(), rd_buffer.is_some()) {
+                (true, true) => w.lasttx_startrx().enabled().lastrx_stop().enabled(),
+                (true, false) => w.lasttx_stop().enabled(),
+                _ => w.lastrx_stop().enabled(),
+            });
+        self.twim
+            .intenset
+            .write(|w| w.stopped().set().error().set());
+        if wr_buffer.is_some() {
+            self.twim.tasks_starttx.write(|w| unsafe { w.bits(1) });
+        } else {
+            self.twim.tasks_startrx.write(|w| unsafe { w.bits(1) });
+        }
+
+        let stop = StopOnDrop(&self.twim);
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+            if self.twim.events_stopped.read().bits() != 0 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        mem::forget(stop);
+        self.twim.events_stopped.reset();
+
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // after all possible DMA actions have completed.
+        compiler_fence(SeqCst);
+
+        self.read_errorsrc()?;
+
+        if let Some(buffer) = wr_buffer {
+            if self.twim.txd.amount.read().bits() != buffer.len() as u32 {
+                return Err(Error::Transmit);
+            }
+        }
+        if let Some(buffer) = rd_buffer {
+            if self.twim.rxd.amount.read().bits() != buffer.len() as u32 {
+                return Err(Error::Receive);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Like `write_part`, but yields to the executor until the part is sent.
+    async fn write_part_async(
+        &mut self,
+        buffer: &[u8],
+        final_operation: bool,
+    ) -> Result<(), Error> {
+        self.start_write_part(buffer, final_operation)?;
+        self.wait_async().await;
+        self.finish_write_part(buffer)
+    }
+
+    /// Like `read_part`, but yields to the executor until the part is received.
+    async fn read_part_async(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        self.start_read_part(buffer)?;
+        self.wait_async().await;
+        self.finish_read_part(buffer)
+    }
+
+    /// Yields to the executor until the peripheral has stopped or suspended.
+    async fn wait_async(&mut self) {
+        self.twim
+            .intenset
+            .write(|w| w.stopped().set().suspended().set().error().set());
+
+        let stop = StopOnDrop(&self.twim);
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+            if self.twim.events_stopped.read().bits() != 0
+                || self.twim.events_suspended.read().bits() != 0
+            {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        mem::forget(stop);
+        self.twim.events_stopped.reset();
+        self.twim.events_suspended.reset();
+    }
+
+    /// Advances async transfers of this TWIM instance.
+    ///
+    /// Call this from the interrupt handler of the TWIM instance when using
+    /// [`Twim::write_async`], [`Twim::read_async`], [`Twim::write_read_async`] or the
+    /// [`embedded_hal_async::i2c::I2c`] implementation. A bus error stops the transfer, and the
+    /// waiting task is woken once the peripheral has stopped or suspended.
+    pub fn on_interrupt() {
+        let twim = unsafe { &*T::PTR };
+        if twim.events_error.read().bits() != 0 {
+            twim.events_error.reset();
+            twim.tasks_stop.write(|w| unsafe { w.bits(1) });
+        }
+        if twim.events_stopped.read().bits() != 0 || twim.events_suspended.read().bits() != 0 {
+            twim.intenclr
+                .write(|w| w.stopped().clear().suspended().clear().error().clear());
+            T::waker().wake();
+        }
+    }
+
     /// Copy data into RAM and write to an I2C slave, then read data from the slave without
     /// triggering a stop condition between the two.
     ///
-    /// The write buffer must have a length of at most 255 bytes on the nRF52832
-    /// and at most 1024 bytes on the nRF52840.
-    ///
-    /// The read buffer must have a length of at most 255 bytes on the nRF52832
-    /// and at most 65535 bytes on the nRF52840.
+    /// The write buffer must have a length of at most `FORCE_COPY_BUFFER_SIZE` bytes, 255 on
+    /// the nRF52832 and 1024 on the nRF52840. A read buffer longer than
+    /// [`Twim::MAX_TRANSFER_SIZE`] is split into chunks as described for
+    /// [`Twim::write_then_read`], each with its own STOP.
     pub fn copy_write_then_read(
         &mut self,
         address: u8,
@@ -385,12 +721,12 @@ where
 
     /// Return the raw interface to the underlying TWIM peripheral.
     pub fn free(self) -> (T, Pins) {
-        let scl = self.0.psel.scl.read();
-        let sda = self.0.psel.sda.read();
-        self.0.psel.scl.reset();
-        self.0.psel.sda.reset();
+        let scl = self.twim.psel.scl.read();
+        let sda = self.twim.psel.sda.read();
+        self.twim.psel.scl.reset();
+        self.twim.psel.sda.reset();
         (
-            self.0,
+            self.twim,
             Pins {
                 scl: unsafe { Pin::from_psel_bits(scl.bits()) },
                 sda: unsafe { Pin::from_psel_bits(sda.bits()) },
@@ -398,53 +734,110 @@ where
         )
     }
 
+    /// Writes `wr_buffer` and then reads `rd_buffer` in chunks of at most `EASY_DMA_SIZE` bytes.
+    ///
+    /// The write chunks are joined by suspending the bus, and the first read follows the
+    /// write with a repeated start. See [`Self::read_part`] for why reads end with a STOP.
+    fn write_read_chunked(
+        &mut self,
+        address: u8,
+        wr_buffer: &[u8],
+        rd_buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        compiler_fence(SeqCst);
+
+        self.twim
+            .address
+            .write(|w| unsafe { w.address().bits(address) });
+
+        self.reset_events();
+
+        let num_chunks = wr_buffer.len().div_ceil(EASY_DMA_SIZE);
+        for (chunk_index, chunk) in wr_buffer.chunks(EASY_DMA_SIZE).enumerate() {
+            self.write_part(chunk, rd_buffer.is_empty() && chunk_index == num_chunks - 1)?;
+        }
+        for chunk in rd_buffer.chunks_mut(EASY_DMA_SIZE) {
+            self.read_part(chunk)?;
+        }
+
+        Ok(())
+    }
+
     fn write_part(&mut self, buffer: &[u8], final_operation: bool) -> Result<(), Error> {
+        self.start_write_part(buffer, final_operation)?;
+        self.wait()?;
+        self.finish_write_part(buffer)
+    }
+
+    fn read_part(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        self.start_read_part(buffer)?;
+        self.wait()?;
+        self.finish_read_part(buffer)
+    }
+
+    fn start_write_part(&mut self, buffer: &[u8], final_operation: bool) -> Result<(), Error> {
         compiler_fence(SeqCst);
         unsafe { self.set_tx_buffer(buffer)? };
 
         // Set appropriate lasttx shortcut.
         if final_operation {
-            self.0.shorts.write(|w| w.lasttx_stop().enabled());
+            self.twim.shorts.write(|w| w.lasttx_stop().enabled());
         } else {
-            self.0.shorts.write(|w| w.lasttx_suspend().enabled());
+            self.twim.shorts.write(|w| w.lasttx_suspend().enabled());
         }
 
         // Start write.
-        self.0.tasks_starttx.write(|w| unsafe { w.bits(1) });
-        self.0.tasks_resume.write(|w| unsafe { w.bits(1) });
+        self.twim.tasks_starttx.write(|w| unsafe { w.bits(1) });
+        self.twim.tasks_resume.write(|w| unsafe { w.bits(1) });
 
-        self.wait();
+        Ok(())
+    }
+
+    fn finish_write_part(&mut self, buffer: &[u8]) -> Result<(), Error> {
         compiler_fence(SeqCst);
         self.read_errorsrc()?;
-        if self.0.txd.amount.read().bits() != buffer.len() as u32 {
+        if self.twim.txd.amount.read().bits() != buffer.len() as u32 {
             return Err(Error::Transmit);
         }
 
         Ok(())
     }
 
-    fn read_part(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+    fn start_read_part(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
         compiler_fence(SeqCst);
         unsafe { self.set_rx_buffer(buffer)? };
 
         // TODO: We should suspend rather than stopping if there are more operations to
         // follow, but for some reason that results in an overrun error and reading bad
         // data in the next read.
-        self.0.shorts.write(|w| w.lastrx_stop().enabled());
+        self.twim.shorts.write(|w| w.lastrx_stop().enabled());
 
         // Start read.
-        self.0.tasks_startrx.write(|w| unsafe { w.bits(1) });
-        self.0.tasks_resume.write(|w| unsafe { w.bits(1) });
+        self.twim.tasks_startrx.write(|w| unsafe { w.bits(1) });
+        self.twim.tasks_resume.write(|w| unsafe { w.bits(1) });
 
-        self.wait();
+        Ok(())
+    }
+
+    fn finish_read_part(&mut self, buffer: &[u8]) -> Result<(), Error> {
         compiler_fence(SeqCst);
         self.read_errorsrc()?;
-        if self.0.rxd.amount.read().bits() != buffer.len() as u32 {
+        if self.twim.rxd.amount.read().bits() != buffer.len() as u32 {
             return Err(Error::Receive);
         }
 
         Ok(())
     }
+
+    /// Clears the events of a previous transfer before starting the next part.
+    fn reset_events(&mut self) {
+        self.twim.events_stopped.reset();
+        self.twim.events_suspended.reset();
+        self.twim.events_error.reset();
+        self.twim.events_lasttx.reset();
+        self.twim.events_lastrx.reset();
+        self.clear_errorsrc();
+    }
 }
 
 impl<T> ErrorType for Twim<T> {
@@ -457,130 +850,286 @@ enum OperationType {
     Write,
 }
 
-impl<T: Instance> I2c for Twim<T> {
-    fn transaction(
-        &mut self,
-        address: u8,
-        operations: &mut [Operation],
-    ) -> Result<(), Self::Error> {
-        compiler_fence(SeqCst);
+/// A DMA transfer planned by [`Transaction::next_part`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Part {
+    /// Write [`Transaction::tx_buffer`], and generate a STOP afterwards if `last` is set.
+    Write { last: bool },
+    /// Read into [`Transaction::rx_buffer`], then call [`Transaction::finish_read`].
+    Read,
+}
 
-        // Buffer used when writing data from flash, or combining multiple consecutive write operations.
-        let mut tx_copy = [0; FORCE_COPY_BUFFER_SIZE];
-        // Number of bytes waiting in `tx_copy` to be sent.
-        let mut pending_tx_bytes = 0;
-        // Buffer used when combining multiple consecutive read operations.
-        let mut rx_copy = [0; FORCE_COPY_BUFFER_SIZE];
-        // Number of bytes from earlier read operations not yet actually read.
-        let mut pending_rx_bytes = 0;
+/// The buffers of the current [`Part`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Buffers {
+    /// The buffer of a single operation, used as it is.
+    Direct(usize),
+    /// The bytes `range` of the buffer of a single operation, sent through the copy buffer.
+    Chunk { index: usize, range: Range<usize> },
+    /// The buffers of the operations in `ops`, combined in the copy buffer.
+    Combined { ops: Range<usize>, len: usize },
+}
 
-        self.0
-            .address
-            .write(|w| unsafe { w.address().bits(address) });
+/// Splits the operations of an I2C transaction into DMA transfers.
+///
+/// Consecutive operations of the same type are combined into a single transfer in a copy buffer,
+/// and writes from flash or longer than the copy buffer are sent through it. Both the blocking
+/// and the async [`I2c`] implementation run the transfers planned here.
+struct Transaction {
+    /// Buffer used when writing data from flash, or combining multiple consecutive write
+    /// operations.
+    tx_copy: [u8; FORCE_COPY_BUFFER_SIZE],
+    /// Buffer used when combining multiple consecutive read operations.
+    rx_copy: [u8; FORCE_COPY_BUFFER_SIZE],
+    /// Index of the next operation to plan.
+    index: usize,
+    /// Offset of the next chunk in the buffer of the operation at `index`.
+    offset: usize,
+    /// First operation of the run being combined.
+    start: usize,
+    /// Number of bytes of the run being combined.
+    pending: usize,
+    /// The buffers of the last planned part.
+    current: Buffers,
+}
 
-        for i in 0..operations.len() {
+impl Transaction {
+    fn new() -> Self {
+        Self {
+            tx_copy: [0; FORCE_COPY_BUFFER_SIZE],
+            rx_copy: [0; FORCE_COPY_BUFFER_SIZE],
+            index: 0,
+            offset: 0,
+            start: 0,
+            pending: 0,
+            current: Buffers::Direct(0),
+        }
+    }
+
+    /// Plans the next transfer, or returns `None` once all operations are done.
+    fn next_part(&mut self, operations: &[Operation<'_>]) -> Result<Option<Part>, Error> {
+        loop {
+            let i = self.index;
+            let operation = match operations.get(i) {
+                Some(operation) => operation,
+                None => return Ok(None),
+            };
             let next_operation_type = match operations.get(i + 1) {
                 None => None,
                 Some(Operation::Write(_)) => Some(OperationType::Write),
                 Some(Operation::Read(_)) => Some(OperationType::Read),
             };
-            let operation = &mut operations[i];
-
-            // Clear events
-            self.0.events_stopped.reset();
-            self.0.events_error.reset();
-            self.0.events_lasttx.reset();
-            self.0.events_lastrx.reset();
-            self.clear_errorsrc();
 
             match operation {
                 Operation::Read(buffer) => {
-                    if buffer.len() > FORCE_COPY_BUFFER_SIZE - pending_rx_bytes {
+                    if buffer.len() > FORCE_COPY_BUFFER_SIZE - self.pending {
                         // Splitting into multiple reads isn't going to work, so just return an
                         // error.
                         return Err(Error::RxBufferTooLong);
-                    } else if pending_rx_bytes == 0
-                        && next_operation_type != Some(OperationType::Read)
-                    {
+                    }
+                    self.index += 1;
+
+                    if self.pending == 0 && next_operation_type != Some(OperationType::Read) {
                         // Simple case: there are no consecutive read operations, so receive
                         // directly.
-                        self.read_part(buffer)?;
-                    } else {
-                        pending_rx_bytes += buffer.len();
-
-                        // If the next operation is not a read (or these is no next operation),
-                        // receive into `rx_copy` now.
-                        if next_operation_type != Some(OperationType::Read) {
-                            self.read_part(&mut rx_copy[..pending_rx_bytes])?;
-
-                            // Copy the resulting data back to the various buffers.
-                            for j in (0..=i).rev() {
-                                if let Operation::Read(buffer) = &mut operations[j] {
-                                    buffer.copy_from_slice(
-                                        &rx_copy[pending_rx_bytes - buffer.len()..pending_rx_bytes],
-                                    );
-                                    pending_rx_bytes -= buffer.len();
-                                } else {
-                                    break;
-                                }
-                            }
-
-                            assert_eq!(pending_rx_bytes, 0);
-                        }
+                        self.current = Buffers::Direct(i);
+                        return Ok(Some(Part::Read));
+                    }
+
+                    if self.pending == 0 {
+                        self.start = i;
+                    }
+                    self.pending += buffer.len();
+
+                    // If the next operation is not a read (or there is no next operation),
+                    // receive into `rx_copy` now.
+                    if next_operation_type != Some(OperationType::Read) {
+                        self.current = Buffers::Combined {
+                            ops: self.start..i + 1,
+                            len: self.pending,
+                        };
+                        self.pending = 0;
+                        return Ok(Some(Part::Read));
                     }
                 }
                 Operation::Write(buffer) => {
                     // Will the current buffer fit in the remaining space in `tx_copy`? If not,
                     // send `tx_copy` immediately.
-                    if buffer.len() > FORCE_COPY_BUFFER_SIZE - pending_tx_bytes
-                        && pending_tx_bytes > 0
-                    {
-                        self.write_part(&tx_copy[..pending_tx_bytes], false)?;
-                        pending_tx_bytes = 0;
+                    if buffer.len() > FORCE_COPY_BUFFER_SIZE - self.pending && self.pending > 0 {
+                        self.current = Buffers::Combined {
+                            ops: self.start..i,
+                            len: self.pending,
+                        };
+                        self.pending = 0;
+                        return Ok(Some(Part::Write { last: false }));
                     }
 
                     if crate::slice_in_ram(buffer)
-                        && pending_tx_bytes == 0
+                        && self.pending == 0
                         && next_operation_type != Some(OperationType::Write)
                     {
                         // Simple case: the buffer is in RAM, and there are no consecutive write
                         // operations, so send it directly.
-                        self.write_part(buffer, next_operation_type.is_none())?;
+                        self.index += 1;
+                        self.current = Buffers::Direct(i);
+                        return Ok(Some(Part::Write {
+                            last: next_operation_type.is_none(),
+                        }));
                     } else if buffer.len() > FORCE_COPY_BUFFER_SIZE {
-                        // This must be true because if it wasn't we must have hit the case above to send
-                        // `tx_copy` immediately and reset `pending_tx_bytes` to 0.
-                        assert!(pending_tx_bytes == 0);
-
-                        // Send the buffer in chunks immediately.
-                        let num_chunks = buffer.len().div_ceil(FORCE_COPY_BUFFER_SIZE);
-                        for (chunk_index, chunk) in buffer
-                            .chunks(FORCE_COPY_BUFFER_SIZE)
-                            .into_iter()
-                            .enumerate()
-                        {
-                            tx_copy[..chunk.len()].copy_from_slice(chunk);
-                            self.write_part(
-                                &tx_copy[..chunk.len()],
-                                next_operation_type.is_none() && chunk_index == num_chunks - 1,
-                            )?;
-                        }
-                    } else {
-                        // Copy the current buffer to `tx_copy`. It must fit, as otherwise we
-                        // would have hit one of the cases above.
-                        tx_copy[pending_tx_bytes..pending_tx_bytes + buffer.len()]
-                            .copy_from_slice(&buffer);
-                        pending_tx_bytes += buffer.len();
-
-                        // If the next operation is not a write (or there is no next operation),
-                        // send `tx_copy` now.
-                        if next_operation_type != Some(OperationType::Write) {
-                            self.write_part(
-                                &tx_copy[..pending_tx_bytes],
-                                next_operation_type.is_none(),
-                            )?;
-                            pending_tx_bytes = 0;
+                        // Nothing is pending here, as it would have been sent above. Send the
+                        // buffer in chunks.
+                        let start = self.offset;
+                        self.offset = buffer.len().min(start + FORCE_COPY_BUFFER_SIZE);
+                        let final_chunk = self.offset == buffer.len();
+                        self.current = Buffers::Chunk {
+                            index: i,
+                            range: start..self.offset,
+                        };
+                        if final_chunk {
+                            self.index += 1;
+                            self.offset = 0;
                         }
+                        return Ok(Some(Part::Write {
+                            last: next_operation_type.is_none() && final_chunk,
+                        }));
+                    }
+
+                    // Copy the current buffer to `tx_copy` later. It must fit, as otherwise we
+                    // would have hit one of the cases above.
+                    self.index += 1;
+                    if self.pending == 0 {
+                        self.start = i;
                     }
+                    self.pending += buffer.len();
+
+                    // If the next operation is not a write (or there is no next operation),
+                    // send `tx_copy` now.
+                    if next_operation_type != Some(OperationType::Write) {
+                        self.current = Buffers::Combined {
+                            ops: self.start..i + 1,
+                            len: self.pending,
+                        };
+                        self.pending = 0;
+                        return Ok(Some(Part::Write {
+                            last: next_operation_type.is_none(),
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the data to send for the last [`Part::Write`].
+    fn tx_buffer<'a>(&'a mut self, operations: &'a [Operation<'_>]) -> &'a [u8] {
+        match self.current.clone() {
+            Buffers::Direct(index) => write_buffer(&operations[index]),
+            Buffers::Chunk { index, range } => {
+                let chunk = &write_buffer(&operations[index])[range];
+                self.tx_copy[..chunk.len()].copy_from_slice(chunk);
+                &self.tx_copy[..chunk.len()]
+            }
+            Buffers::Combined { ops, len } => {
+                let mut copied = 0;
+                for operation in &operations[ops] {
+                    let buffer = write_buffer(operation);
+                    self.tx_copy[copied..copied + buffer.len()].copy_from_slice(buffer);
+                    copied += buffer.len();
+                }
+                &self.tx_copy[..len]
+            }
+        }
+    }
+
+    /// Returns the buffer to receive the last [`Part::Read`] into.
+    fn rx_buffer<'a>(&'a mut self, operations: &'a mut [Operation<'_>]) -> &'a mut [u8] {
+        match self.current.clone() {
+            Buffers::Direct(index) => match &mut operations[index] {
+                Operation::Read(buffer) => buffer,
+                Operation::Write(_) => unreachable!(),
+            },
+            Buffers::Combined { len, .. } => &mut self.rx_copy[..len],
+            Buffers::Chunk { .. } => unreachable!(),
+        }
+    }
+
+    /// Copies the data received by the last [`Part::Read`] back to the read operations.
+    fn finish_read(&mut self, operations: &mut [Operation<'_>]) {
+        if let Buffers::Combined { ops, .. } = self.current.clone() {
+            let mut copied = 0;
+            for operation in &mut operations[ops] {
+                if let Operation::Read(buffer) = operation {
+                    buffer.copy_from_slice(&self.rx_copy[copied..copied + buffer.len()]);
+                    copied += buffer.len();
+                }
+            }
+        }
+    }
+}
+
+/// Returns the buffer of a write operation.
+fn write_buffer<'a>(operation: &'a Operation<'_>) -> &'a [u8] {
+    match operation {
+        Operation::Write(buffer) => buffer,
+        Operation::Read(_) => unreachable!(),
+    }
+}
+
+impl<T: Instance> I2c for Twim<T> {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation],
+    ) -> Result<(), Self::Error> {
+        compiler_fence(SeqCst);
+
+        self.twim
+            .address
+            .write(|w| unsafe { w.address().bits(address) });
+
+        let mut transaction = Transaction::new();
+        while let Some(part) = transaction.next_part(operations)? {
+            self.reset_events();
+            match part {
+                Part::Write { last } => self.write_part(transaction.tx_buffer(operations), last)?,
+                Part::Read => {
+                    self.read_part(transaction.rx_buffer(operations))?;
+                    transaction.finish_read(operations);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Works like the blocking [`I2c`] implementation, but yields to the executor while the transfers
+/// run. [`Twim::on_interrupt`] has to be called from the interrupt handler of the TWIM instance,
+/// and the interrupt has to be unmasked in the NVIC. The timeout set with [`Twim::set_timeout`]
+/// does not apply.
+impl<T: Instance> embedded_hal_async::i2c::I2c for Twim<T> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        compiler_fence(SeqCst);
+
+        self.twim
+            .address
+            .write(|w| unsafe { w.address().bits(address) });
+
+        let mut transaction = Transaction::new();
+        while let Some(part) = transaction.next_part(operations)? {
+            self.reset_events();
+            match part {
+                Part::Write { last } => {
+                    self.write_part_async(transaction.tx_buffer(operations), last)
+                        .await?
+                }
+                Part::Read => {
+                    self.read_part_async(transaction.rx_buffer(operations))
+                        .await?;
+                    transaction.finish_read(operations);
                 }
             }
         }
@@ -635,14 +1184,27 @@ where
         bytes: &'w [u8],
         buffer: &'w mut [u8],
     ) -> Result<(), Error> {
-        if crate::slice_in_ram(bytes) {
-            self.write_then_read(addr, bytes, buffer)
-        } else {
-            self.copy_write_then_read(addr, bytes, buffer)
-        }
+        Twim::write_read(self, addr, bytes, buffer)
     }
 }
 
+/// Configures a pin for use by the TWIM peripheral.
+fn configure_pin<MODE>(pin: &Pin<MODE>) {
+    // See `Twim::new` for why this is done through the raw registers.
+    pin.conf().write(|w| {
+        w.dir()
+            .input()
+            .input()
+            .connect()
+            .pull()
+            .pullup()
+            .drive()
+            .s0d1()
+            .sense()
+            .disabled()
+    });
+}
+
 /// The pins used by the TWIM peripheral.
 ///
 /// Currently, only P0 pins are supported.
@@ -666,6 +1228,12 @@ pub enum Error {
     AddressNack,
     DataNack,
     Overrun,
+    /// The transfer did not finish within the timeout set with [`Twim::set_timeout`].
+    Timeout,
+    /// SDA is held low even after [`Twim::recover_bus`].
+    BusStuck,
+    /// The value passed to [`Twim::set_frequency_raw`] is out of range for the chip.
+    InvalidFrequency,
 }
 
 impl i2c::Error for Error {
@@ -677,7 +1245,10 @@ impl i2c::Error for Error {
             | Self::RxBufferZeroLength
             | Self::Transmit
             | Self::Receive
-            | Self::DMABufferNotInDataMemory => ErrorKind::Other,
+            | Self::DMABufferNotInDataMemory
+            | Self::Timeout
+            | Self::InvalidFrequency => ErrorKind::Other,
+            Self::BusStuck => ErrorKind::Bus,
             Self::AddressNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
             Self::DataNack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
             Self::Overrun => ErrorKind::Overrun,
@@ -685,15 +1256,64 @@ impl i2c::Error for Error {
     }
 }
 
+/// Stops a transfer that is still running when an async transfer is cancelled.
+struct StopOnDrop<'a>(&'a twim0::RegisterBlock);
+
+impl Drop for StopOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0
+            .intenclr
+            .write(|w| w.stopped().clear().suspended().clear().error().clear());
+        self.0.tasks_stop.write(|w| unsafe { w.bits(1) });
+        let mut remaining_us = STOP_TIMEOUT_US;
+        while self.0.events_stopped.read().bits() == 0 {
+            if remaining_us == 0 {
+                // A STOP can't be generated while a slave holds SCL, so reset the
+                // peripheral instead.
+                self.0.enable.write(|w| w.enable().disabled());
+                self.0.enable.write(|w| w.enable().enabled());
+                break;
+            }
+            remaining_us -= 1;
+            cortex_m::asm::delay(HFCLK_FREQ / 1_000_000);
+        }
+        self.0.events_stopped.reset();
+    }
+}
+
+/// Time a cancelled async transfer waits for the STOP condition before resetting the peripheral.
+const STOP_TIMEOUT_US: u32 = 1_000;
+
 /// Implemented by all TWIM instances
 pub trait Instance: Deref<Target = twim0::RegisterBlock> + sealed::Sealed {}
 
 mod sealed {
-    pub trait Sealed {}
+    use super::twim0;
+    use crate::waker::AtomicWaker;
+
+    pub trait Sealed {
+        const PTR: *const twim0::RegisterBlock;
+
+        /// The waker of the task waiting in an async transfer.
+        fn waker() -> &'static AtomicWaker;
+    }
+}
+
+macro_rules! impl_instance {
+    ($twim:ident) => {
+        impl sealed::Sealed for $twim {
+            const PTR: *const twim0::RegisterBlock = $twim::PTR;
+
+            fn waker() -> &'static crate::waker::AtomicWaker {
+                static WAKER: crate::waker::AtomicWaker = crate::waker::AtomicWaker::new();
+                &WAKER
+            }
+        }
+        impl Instance for $twim {}
+    };
 }
 
-impl sealed::Sealed for TWIM0 {}
-impl Instance for TWIM0 {}
+impl_instance!(TWIM0);
 
 #[cfg(any(
     feature = "52832",
@@ -704,20 +1324,17 @@ impl Instance for TWIM0 {}
 ))]
 mod _twim1 {
     use super::*;
-    impl sealed::Sealed for TWIM1 {}
-    impl Instance for TWIM1 {}
+    impl_instance!(TWIM1);
 }
 
 #[cfg(any(feature = "9160", feature = "5340-app"))]
 mod _twim2 {
     use super::*;
-    impl sealed::Sealed for TWIM2 {}
-    impl Instance for TWIM2 {}
+    impl_instance!(TWIM2);
 }
 
 #[cfg(any(feature = "9160", feature = "5340-app"))]
 mod _twim3 {
     use super::*;
-    impl sealed::Sealed for TWIM3 {}
-    impl Instance for TWIM3 {}
+    impl_instance!(TWIM3);
 }
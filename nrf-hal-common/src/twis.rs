@@ -391,6 +391,148 @@ where
         Ok(())
     }
 
+    /// Waits for an I2C controller to read from this device and responds with `buffer`.
+    ///
+    /// The READ command is armed with the `READ_SUSPEND` shortcut, so the clock is stretched
+    /// until the buffer has been prepared. If the controller reads more than `buffer.len()`
+    /// bytes, the over-read character (see [`Twis::set_orc`]) is sent for the remaining ones.
+    /// Returns the number of bytes taken from `buffer`.
+    ///
+    /// If the controller issues a WRITE command instead, [`Error::UnexpectedCommand`] is
+    /// returned and the command is left pending for [`Twis::receive`]. The bus stays
+    /// suspended until it is handled.
+    ///
+    /// The buffer must reside in RAM and have a length of at most
+    /// 255 bytes on the nRF52832 and at most 65535 bytes on the nRF52840.
+    pub fn respond_to_read(&mut self, buffer: &[u8]) -> Result<usize, Error> {
+        slice_in_ram_or(buffer, Error::DMABufferNotInDataMemory)?;
+
+        if buffer.len() > EASY_DMA_SIZE {
+            return Err(Error::TxBufferTooLong);
+        }
+
+        if self.wait_for_command() != TwiEvent::Read {
+            return Err(Error::UnexpectedCommand);
+        }
+        self.0.events_read.reset();
+        // Drop a STOPPED left over from the previous transaction, the bus is suspended until
+        // RESUME, so the STOP of this one can't have happened yet.
+        self.0.events_stopped.reset();
+
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // before any DMA action has started.
+        compiler_fence(SeqCst);
+
+        self.0
+            .txd
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(buffer.as_ptr() as u32) });
+        self.0
+            .txd
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(buffer.len() as _) });
+        self.clear_errorsrc();
+
+        self.0.tasks_preparetx.write(|w| unsafe { w.bits(1) });
+        self.0.tasks_resume.write(|w| unsafe { w.bits(1) });
+
+        while self.0.events_stopped.read().bits() == 0 {}
+        self.0.events_stopped.reset();
+        self.0.shorts.reset();
+
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // after all possible DMA actions have completed.
+        compiler_fence(SeqCst);
+
+        Ok(self.0.txd.amount.read().bits() as usize)
+    }
+
+    /// Waits for an I2C controller to write to this device and receives the data into `buffer`.
+    ///
+    /// The WRITE command is armed with the `WRITE_SUSPEND` shortcut, so the clock is stretched
+    /// until the buffer has been prepared. Returns the number of bytes received, which may be
+    /// fewer than `buffer.len()`. Bytes written beyond the end of `buffer` are NACKed and
+    /// reported as [`Error::OverFlow`].
+    ///
+    /// If the controller issues a READ command instead, [`Error::UnexpectedCommand`] is
+    /// returned and the command is left pending for [`Twis::respond_to_read`]. The bus stays
+    /// suspended until it is handled.
+    ///
+    /// The buffer must have a length of at most 255 bytes on the nRF52832
+    /// and at most 65535 bytes on the nRF52840.
+    pub fn receive(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        // NOTE: RAM slice check is not necessary, as a mutable slice can only be
+        // built from data located in RAM.
+
+        if buffer.len() > EASY_DMA_SIZE {
+            return Err(Error::RxBufferTooLong);
+        }
+
+        if self.wait_for_command() != TwiEvent::Write {
+            return Err(Error::UnexpectedCommand);
+        }
+        self.0.events_write.reset();
+        self.0.events_stopped.reset();
+
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // before any DMA action has started.
+        compiler_fence(SeqCst);
+
+        self.0
+            .rxd
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(buffer.as_mut_ptr() as u32) });
+        self.0
+            .rxd
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(buffer.len() as _) });
+        self.clear_errorsrc();
+
+        self.0.tasks_preparerx.write(|w| unsafe { w.bits(1) });
+        self.0.tasks_resume.write(|w| unsafe { w.bits(1) });
+
+        while self.0.events_stopped.read().bits() == 0 {}
+        self.0.events_stopped.reset();
+        self.0.shorts.reset();
+
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // after all possible DMA actions have completed.
+        compiler_fence(SeqCst);
+
+        if self.0.errorsrc.read().overflow().is_detected() {
+            return Err(Error::OverFlow);
+        }
+
+        Ok(self.0.rxd.amount.read().bits() as usize)
+    }
+
+    /// Arms the READ and WRITE commands to suspend the bus, and waits for either of them.
+    ///
+    /// Returns `TwiEvent::Read` or `TwiEvent::Write`, leaving the event set.
+    fn wait_for_command(&mut self) -> TwiEvent {
+        self.0
+            .shorts
+            .write(|w| w.read_suspend().enabled().write_suspend().enabled());
+        loop {
+            if self.0.events_read.read().bits() != 0 {
+                return TwiEvent::Read;
+            }
+            if self.0.events_write.read().bits() != 0 {
+                return TwiEvent::Write;
+            }
+        }
+    }
+
+    /// Clears all error flags, which are write-one-to-clear.
+    fn clear_errorsrc(&mut self) {
+        let errors = self.0.errorsrc.read().bits();
+        self.0.errorsrc.write(|w| unsafe { w.bits(errors) });
+    }
+
     /// Receives data into the given `buffer`. Buffer must be located in RAM.
     /// Returns a value that represents the in-progress DMA transfer.
     pub fn rx<W, B>(self, mut buffer: B) -> Result<Transfer<T, B>, Error>
@@ -486,6 +628,8 @@ pub enum Error {
     DataNack,
     OverFlow,
     OverRead,
+    /// The controller issued the other command than the one being waited for.
+    UnexpectedCommand,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
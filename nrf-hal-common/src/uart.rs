@@ -1,4 +1,35 @@
 //! HAL interface to the UART peripheral.
+//!
+//! The legacy UART without EasyDMA, available on the nRF51 and the nRF52 series. It transfers
+//! one byte at a time through the `RXD` and `TXD` registers, which suits char-by-char protocols
+//! such as a line editor better than the [`uarte`](crate::uarte) driver does.
+//!
+//! The driver implements the blocking `embedded_io` traits and the non-blocking
+//! `embedded_hal_nb::serial` traits.
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::{gpio::{p0::Parts, Level}, pac::Peripherals};
+//! # use nrf_hal_common::uart::{Baudrate, Parity, Pins, Uart};
+//! use embedded_io::{Read, Write};
+//!
+//! let p = Peripherals::take().unwrap();
+//! let port0 = Parts::new(p.P0);
+//! let pins = Pins {
+//!     rxd: port0.p0_08.into_floating_input().degrade(),
+//!     txd: port0.p0_06.into_push_pull_output(Level::High).degrade(),
+//!     cts: None,
+//!     rts: None,
+//! };
+//! let mut uart = Uart::new(p.UART0, pins, Parity::EXCLUDED, Baudrate::BAUD115200);
+//!
+//! // Echo every byte.
+//! let mut byte = [0];
+//! loop {
+//!     uart.read(&mut byte).unwrap();
+//!     uart.write_all(&byte).unwrap();
+//! }
+//! ```
 
 use core::convert::Infallible;
 use core::fmt::{self, Write};
@@ -12,11 +43,86 @@ use crate::pac::{uart0, UART0};
 // Re-export SVD variants to allow user to directly set values.
 pub use uart0::{baudrate::BAUDRATE_A as Baudrate, config::PARITY_A as Parity};
 
+/// Accesses the PSEL register of a pin, which are named `PSELTXD` etc. on the nRF51 and
+/// nRF52832 and `PSEL.TXD` etc. on the newer chips. The bits are compatible.
+#[cfg(any(feature = "51", feature = "52832"))]
+macro_rules! psel {
+    ($uart:expr, txd) => {
+        $uart.pseltxd
+    };
+    ($uart:expr, rxd) => {
+        $uart.pselrxd
+    };
+    ($uart:expr, cts) => {
+        $uart.pselcts
+    };
+    ($uart:expr, rts) => {
+        $uart.pselrts
+    };
+}
+#[cfg(not(any(feature = "51", feature = "52832")))]
+macro_rules! psel {
+    ($uart:expr, $pin:ident) => {
+        $uart.psel.$pin
+    };
+}
+
 /// Interface to a UART instance.
 pub struct Uart<T>(T);
 
-#[derive(Debug)]
-pub enum Error {}
+/// A receive error reported by the UART.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// A start bit was received while the previous byte was still waiting in RXD.
+    Overrun,
+    /// A byte with the wrong parity was received.
+    Parity,
+    /// No valid stop bit was found at the end of a byte.
+    Framing,
+    /// RXD was held low for longer than a whole byte.
+    Break,
+}
+
+/// The receive errors latched in the ERRORSRC register, see [`Uart::take_error_source`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorSource {
+    /// See [`Error::Overrun`].
+    pub overrun: bool,
+    /// See [`Error::Parity`].
+    pub parity: bool,
+    /// See [`Error::Framing`].
+    pub framing: bool,
+    /// See [`Error::Break`].
+    pub break_condition: bool,
+}
+
+impl ErrorSource {
+    fn from_bits(bits: u32) -> Self {
+        ErrorSource {
+            overrun: bits & (1 << 0) != 0,
+            parity: bits & (1 << 1) != 0,
+            framing: bits & (1 << 2) != 0,
+            break_condition: bits & (1 << 3) != 0,
+        }
+    }
+
+    /// Returns the most significant of the latched errors, if any.
+    ///
+    /// A break also causes a framing error, so it takes precedence over it.
+    pub fn error(&self) -> Option<Error> {
+        if self.overrun {
+            Some(Error::Overrun)
+        } else if self.parity {
+            Some(Error::Parity)
+        } else if self.break_condition {
+            Some(Error::Break)
+        } else if self.framing {
+            Some(Error::Framing)
+        } else {
+            None
+        }
+    }
+}
 
 impl<T> Uart<T>
 where
@@ -27,24 +133,22 @@ where
         uart.txd.write(|w| unsafe { w.bits(0) });
 
         // Required pins
-        uart.pseltxd
-            .write(|w| unsafe { w.bits(pins.txd.pin().into()) });
-        uart.pselrxd
-            .write(|w| unsafe { w.bits(pins.rxd.pin().into()) });
+        psel!(uart, txd).write(|w| unsafe { w.bits(pins.txd.psel_bits()) });
+        psel!(uart, rxd).write(|w| unsafe { w.bits(pins.rxd.psel_bits()) });
 
         // Optional pins
-        uart.pselcts.write(|w| unsafe {
+        psel!(uart, cts).write(|w| unsafe {
             if let Some(ref pin) = pins.cts {
-                w.bits(pin.pin().into())
+                w.bits(pin.psel_bits())
             } else {
                 // Disconnect
                 w.bits(0xFFFFFFFF)
             }
         });
 
-        uart.pselrts.write(|w| unsafe {
+        psel!(uart, rts).write(|w| unsafe {
             if let Some(ref pin) = pins.rts {
-                w.bits(pin.pin().into())
+                w.bits(pin.psel_bits())
             } else {
                 // Disconnect
                 w.bits(0xFFFFFFFF)
@@ -69,16 +173,61 @@ where
         Uart(uart)
     }
 
+    /// Enables the interrupt for the specified event.
+    #[inline(always)]
+    pub fn enable_interrupt(&self, event: UartEvent) -> &Self {
+        self.0.intenset.write(|w| match event {
+            UartEvent::RxdReady => w.rxdrdy().set_bit(),
+            UartEvent::TxdReady => w.txdrdy().set_bit(),
+            UartEvent::Error => w.error().set_bit(),
+        });
+        self
+    }
+
+    /// Disables the interrupt for the specified event.
+    #[inline(always)]
+    pub fn disable_interrupt(&self, event: UartEvent) -> &Self {
+        self.0.intenclr.write(|w| match event {
+            UartEvent::RxdReady => w.rxdrdy().set_bit(),
+            UartEvent::TxdReady => w.txdrdy().set_bit(),
+            UartEvent::Error => w.error().set_bit(),
+        });
+        self
+    }
+
+    /// Checks if the specified event has been triggered.
+    ///
+    /// The `RxdReady` and `TxdReady` events are consumed by reading and writing bytes, so only
+    /// reset the `Error` event by hand.
+    #[inline(always)]
+    pub fn is_event_triggered(&self, event: UartEvent) -> bool {
+        match event {
+            UartEvent::RxdReady => self.0.events_rxdrdy.read().bits() != 0,
+            UartEvent::TxdReady => self.0.events_txdrdy.read().bits() != 0,
+            UartEvent::Error => self.0.events_error.read().bits() != 0,
+        }
+    }
+
+    /// Resets the `Error` event and returns the error source, clearing it.
+    #[inline(always)]
+    pub fn take_error_source(&self) -> ErrorSource {
+        self.0.events_error.reset();
+        let source = self.0.errorsrc.read().bits();
+        // Bits are cleared by writing 1 to them.
+        self.0.errorsrc.write(|w| unsafe { w.bits(source) });
+        ErrorSource::from_bits(source)
+    }
+
     /// Return the raw interface to the underlying UARTE peripheral.
     pub fn free(self) -> (T, Pins) {
-        let rxd = self.0.pselrxd.read();
-        let txd = self.0.pseltxd.read();
-        let cts = self.0.pselcts.read();
-        let rts = self.0.pselrts.read();
-        self.0.pselrxd.reset(); // Reset pins
-        self.0.pseltxd.reset();
-        self.0.pselcts.reset();
-        self.0.pselrts.reset();
+        let rxd = psel!(self.0, rxd).read();
+        let txd = psel!(self.0, txd).read();
+        let cts = psel!(self.0, cts).read();
+        let rts = psel!(self.0, rts).read();
+        psel!(self.0, rxd).reset(); // Reset pins
+        psel!(self.0, txd).reset();
+        psel!(self.0, cts).reset();
+        psel!(self.0, rts).reset();
         (
             self.0,
             Pins {
@@ -163,6 +312,69 @@ impl<T: Instance> embedded_io::Write for Uart<T> {
     }
 }
 
+impl embedded_hal_nb::serial::Error for Error {
+    fn kind(&self) -> embedded_hal_nb::serial::ErrorKind {
+        use embedded_hal_nb::serial::ErrorKind;
+
+        match *self {
+            Error::Overrun => ErrorKind::Overrun,
+            Error::Parity => ErrorKind::Parity,
+            Error::Framing => ErrorKind::FrameFormat,
+            Error::Break => ErrorKind::Other,
+        }
+    }
+}
+
+impl<T> embedded_hal_nb::serial::ErrorType for Uart<T> {
+    type Error = Error;
+}
+
+impl<T: Instance> embedded_hal_nb::serial::Read for Uart<T> {
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        // Report errors before the byte they belong to.
+        if self.0.events_error.read().bits() != 0 {
+            if let Some(error) = self.take_error_source().error() {
+                return Err(nb::Error::Other(error));
+            }
+        }
+
+        if self.0.events_rxdrdy.read().bits() == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // Reset ready for receive event.
+        self.0.events_rxdrdy.reset();
+
+        // Read one 8bit value.
+        Ok(self.0.rxd.read().bits() as u8)
+    }
+}
+
+impl<T: Instance> embedded_hal_nb::serial::Write for Uart<T> {
+    fn write(&mut self, byte: u8) -> nb::Result<(), Self::Error> {
+        // Are we ready for sending out next byte?
+        if self.0.events_txdrdy.read().bits() == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        // Reset ready for transmit event.
+        self.0.events_txdrdy.reset();
+
+        // Send byte.
+        self.0.txd.write(|w| unsafe { w.bits(u32::from(byte)) });
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        // The last byte has been sent once the transmitter is ready again.
+        if self.0.events_txdrdy.read().bits() == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<T> embedded_hal_02::serial::Read<u8> for Uart<T>
 where
@@ -223,6 +435,17 @@ where
     }
 }
 
+/// UART events.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum UartEvent {
+    /// A byte has been received and can be read.
+    RxdReady,
+    /// A byte has been sent and the next one can be written.
+    TxdReady,
+    /// A framing, parity, overrun or break error occurred.
+    Error,
+}
+
 pub struct Pins {
     pub rxd: Pin<Input<Floating>>,
     pub txd: Pin<Output<PushPull>>,
@@ -7,9 +7,15 @@
 
 use core::cmp::min;
 use core::fmt;
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+use core::future::poll_fn;
 use core::hint::spin_loop;
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+use core::mem;
 use core::ops::Deref;
 use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+use core::task::Poll;
 use embedded_hal::digital::OutputPin;
 use embedded_io::{ErrorKind, ErrorType, ReadReady, Write as _, WriteReady};
 
@@ -32,6 +38,8 @@ use crate::pac::UARTE1_NS as UARTE1;
 use crate::pac::{uarte0, UARTE0};
 
 use crate::gpio::{Floating, Input, Output, Pin, PushPull};
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+use crate::ppi::{ConfigurablePpi, Ppi};
 use crate::slice_in_ram_or;
 use crate::target_constants::EASY_DMA_SIZE;
 use crate::timer::{self, Timer};
@@ -204,6 +212,27 @@ where
         Ok(())
     }
 
+    /// Write via UARTE, copying the data through a RAM buffer first.
+    ///
+    /// EasyDMA can't read from flash, so [`Uarte::write`] returns [`Error::BufferNotInRAM`] for
+    /// constants such as `b"AT\r\n"`. This method copies `bytes` to an on-stack buffer in chunks
+    /// of [`FLASH_CHUNK_SIZE`] bytes and transmits them one after the other, so `bytes` may reside
+    /// anywhere and may be longer than a single DMA transfer.
+    pub fn write_from_flash(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let buf = &mut [0; FLASH_CHUNK_SIZE][..];
+        for block in bytes.chunks(FLASH_CHUNK_SIZE) {
+            buf[..block.len()].copy_from_slice(block);
+            self.write(&buf[..block.len()])?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a string via UARTE, see [`Uarte::write_from_flash`].
+    pub fn write_str_from_flash(&mut self, s: &str) -> Result<(), Error> {
+        self.write_from_flash(s.as_bytes())
+    }
+
     /// Read via UARTE.
     ///
     /// This method fills all bytes in `rx_buffer`, and blocks
@@ -287,6 +316,243 @@ where
         Ok(())
     }
 
+    /// Read via UARTE until the line goes idle, without blocking.
+    ///
+    /// Receives into `rx_buffer` until no further byte has arrived for [`IDLE_FRAMES`] character
+    /// times at the configured baud rate, or until the buffer is full, and returns the number of
+    /// bytes read. This suits variable-length messages such as NMEA sentences or modem responses.
+    ///
+    /// The idle gap is measured in hardware: `ppi_restart` clears and starts `timer` on every
+    /// RXDRDY event, and `ppi_stop` triggers STOPRX once the timer reaches CC\[0\]. The timer is
+    /// only started by the first byte, so the future stays pending until data arrives; drop it,
+    /// e.g. by racing it against a timeout, to give up waiting. Dropping the future stops the
+    /// reception.
+    ///
+    /// [`Uarte::on_interrupt`] has to be called from the interrupt handler of the UARTE instance,
+    /// and the interrupt has to be unmasked in the NVIC.
+    ///
+    /// The buffer must have a length of at most 255 bytes on the nRF52832
+    /// and at most 65535 bytes on the nRF52840.
+    #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+    pub async fn read_until_idle<I, P0, P1>(
+        &mut self,
+        rx_buffer: &mut [u8],
+        timer: &mut Timer<I>,
+        ppi_restart: &mut P0,
+        ppi_stop: &mut P1,
+    ) -> Result<usize, Error>
+    where
+        I: timer::Instance,
+        P0: ConfigurablePpi,
+        P1: ConfigurablePpi,
+    {
+        // The one-shot timer clears and stops itself on COMPARE[0], so every byte restarts the
+        // idle gap from zero.
+        timer.task_stop().write(|w| unsafe { w.bits(1) });
+        timer.task_clear().write(|w| unsafe { w.bits(1) });
+        timer.set_compare(0, idle_timeout_us(&self.0));
+        timer.reset_compare_event(0);
+
+        ppi_restart
+            .set_event_endpoint(&self.0.events_rxdrdy)
+            .set_task_endpoint(timer.task_clear())
+            .set_fork_task_endpoint(timer.task_start())
+            .enable();
+        ppi_stop
+            .set_event_endpoint(timer.event_compare_cc0())
+            .set_task_endpoint(&self.0.tasks_stoprx)
+            .enable();
+        let idle = IdleTimeout {
+            timer,
+            ppi_restart,
+            ppi_stop,
+        };
+
+        self.0.events_rxto.reset();
+        start_read(&self.0, rx_buffer)?;
+        self.0.intenset.write(|w| w.endrx().set());
+
+        // ENDRX fires both when the buffer is full and when STOPRX ended the reception.
+        let stop = StopRxOnDrop(&self.0);
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+            if self.0.events_endrx.read().bits() != 0 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        mem::forget(stop);
+        self.0.intenclr.write(|w| w.endrx().clear());
+
+        if idle.timer.is_compare_triggered(0) {
+            // STOPRX was triggered, RXTO follows right after ENDRX.
+            while self.0.events_rxto.read().bits() == 0 {}
+            self.0.events_rxto.reset();
+        }
+
+        finalize_read(&self.0);
+
+        Ok(self.0.rxd.amount.read().bits() as usize)
+    }
+
+    /// Wakes the task waiting for a reception of this UARTE instance to end.
+    ///
+    /// Call this from the interrupt handler of the UARTE instance when using
+    /// [`Uarte::read_until_idle`].
+    #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+    pub fn on_interrupt() {
+        let uarte = unsafe { &*T::ptr() };
+        if uarte.events_endrx.read().bits() != 0 {
+            uarte.intenclr.write(|w| w.endrx().clear());
+            T::waker().wake();
+        }
+    }
+
+    /// Changes the baud rate, e.g. after negotiating a faster rate with a modem.
+    ///
+    /// The peripheral is disabled while `BAUDRATE` is rewritten and re-enabled afterwards, the
+    /// rest of the configuration is kept. Besides the usual rates, [`Baudrate`] offers the
+    /// non-standard values supported by the hardware, such as `BAUD31250` for MIDI or
+    /// `BAUD250000`.
+    ///
+    /// Returns [`Error::Busy`] without changing anything if a transfer is still in progress.
+    pub fn set_baudrate(&mut self, baudrate: Baudrate) -> Result<(), Error> {
+        let tx_in_progress =
+            self.0.events_txstarted.read().bits() != 0 && self.0.events_endtx.read().bits() == 0;
+        let rx_in_progress =
+            self.0.events_rxstarted.read().bits() != 0 && self.0.events_endrx.read().bits() == 0;
+        if tx_in_progress || rx_in_progress {
+            return Err(Error::Busy);
+        }
+
+        self.0.enable.write(|w| w.enable().disabled());
+        self.0.baudrate.write(|w| w.baudrate().variant(baudrate));
+        self.0.enable.write(|w| w.enable().enabled());
+
+        Ok(())
+    }
+
+    /// Reconfigures hardware flow control, returning the previously used RTS and CTS pins.
+    ///
+    /// Flow control is enabled when both `rts` and `cts` are given, as in [`Uarte::new`]. Pins
+    /// that are not given are disconnected from the peripheral. The peripheral is disabled while
+    /// the pins are switched; since all transfers of `Uarte` are blocking, it is idle whenever
+    /// this method can be called.
+    pub fn set_flow_control(
+        &mut self,
+        rts: Option<Pin<Output<PushPull>>>,
+        cts: Option<Pin<Input<Floating>>>,
+    ) -> FlowControlPins {
+        let old_rts = self.0.psel.rts.read();
+        let old_cts = self.0.psel.cts.read();
+
+        self.0.enable.write(|w| w.enable().disabled());
+
+        self.0.psel.cts.write(|w| {
+            if let Some(ref pin) = cts {
+                unsafe { w.bits(pin.psel_bits()) };
+                w.connect().connected()
+            } else {
+                w.connect().disconnected()
+            }
+        });
+
+        self.0.psel.rts.write(|w| {
+            if let Some(ref pin) = rts {
+                unsafe { w.bits(pin.psel_bits()) };
+                w.connect().connected()
+            } else {
+                w.connect().disconnected()
+            }
+        });
+
+        let hardware_flow_control = rts.is_some() && cts.is_some();
+        self.0
+            .config
+            .modify(|_, w| w.hwfc().bit(hardware_flow_control));
+
+        self.0.enable.write(|w| w.enable().enabled());
+
+        FlowControlPins {
+            rts: if old_rts.connect().is_connected() {
+                Some(unsafe { Pin::from_psel_bits(old_rts.bits()) })
+            } else {
+                None
+            },
+            cts: if old_cts.connect().is_connected() {
+                Some(unsafe { Pin::from_psel_bits(old_cts.bits()) })
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Emergency blocking write, e.g. from a panic or HardFault handler.
+    ///
+    /// This is meant as a last gasp for post-mortem debugging, when the `Uarte` (or its split
+    /// halves) may be unreachable or in the middle of a transfer. It does not rely on interrupts
+    /// or on any state held by the driver: any ongoing transmission is stopped, interrupts of the
+    /// instance are disabled, hardware flow control is turned off so a deasserted CTS cannot stall
+    /// the write, and the instance is (re-)enabled before busy-waiting on `ENDTX` for each chunk.
+    ///
+    /// Pin selection, baud rate and parity are taken from the previous configuration, so the
+    /// instance must have been set up with [`Uarte::new`] at some point. If no TXD pin is
+    /// connected, nothing is sent.
+    ///
+    /// `bytes` may reside in flash, it is copied to the stack in small chunks before transmission.
+    ///
+    /// # Safety
+    ///
+    /// This accesses the peripheral without owning it and leaves it in a state that other users
+    /// of the instance do not expect. Use it for emergency output only, never as a regular
+    /// write path.
+    pub unsafe fn blocking_emit_raw(bytes: &[u8]) {
+        let uarte = &*T::ptr();
+
+        if uarte.psel.txd.read().connect().is_disconnected() {
+            return;
+        }
+
+        // Nothing must run in response to the events generated below.
+        uarte.intenclr.write(|w| w.bits(0xFFFF_FFFF));
+
+        // Stop an interrupted transmission like `UarteTx::drop` does. Without one, STOPTX
+        // generates no TXSTOPPED event to wait for.
+        if uarte.enable.read().bits() != 0 && uarte.events_txstarted.read().bits() != 0 {
+            uarte.events_txstopped.reset();
+            // `1` is a valid value to write to task registers.
+            uarte.tasks_stoptx.write(|w| w.bits(1));
+            while uarte.events_txstopped.read().bits() == 0 {
+                spin_loop();
+            }
+            uarte.events_txstarted.reset();
+        }
+
+        uarte.config.modify(|_, w| w.hwfc().disabled());
+        uarte.enable.write(|w| w.enable().enabled());
+
+        // Copy all data into an on-stack buffer so we never try to EasyDMA from
+        // flash.
+        let buf = &mut [0; 32][..];
+        for block in bytes.chunks(32) {
+            buf[..block.len()].copy_from_slice(block);
+            start_write(uarte, &buf[..block.len()]);
+
+            while uarte.events_endtx.read().bits() == 0 {
+                spin_loop();
+            }
+
+            // Conservative compiler fence to prevent optimizations that do not
+            // take in to account actions by DMA. The fence has been placed here,
+            // after all possible DMA actions have completed.
+            compiler_fence(SeqCst);
+
+            stop_write(uarte);
+        }
+    }
+
     /// Return the raw interface to the underlying UARTE peripheral.
     pub fn free(self) -> (T, Pins) {
         let rxd = self.0.psel.rxd.read();
@@ -321,6 +587,38 @@ where
     /// the size of the DMA transfers performed.
     /// The `rx_buf` slice is an array of size 1 since the embedded_hal
     /// traits only allow reading one byte at a time.
+    ///
+    /// Both halves access the shared register block through [`Instance::ptr`], but each of
+    /// them only touches its own tasks, events and DMA registers (`TXD.*` or `RXD.*`), so they
+    /// are `Send` and can be moved into different tasks or interrupt handlers. Dropping a half
+    /// stops its own direction only: a transmission in progress keeps running when the RX half
+    /// is dropped, and vice versa. The peripheral itself stays enabled.
+    ///
+    #[cfg_attr(feature = "52840", doc = "```no_run")]
+    #[cfg_attr(not(feature = "52840"), doc = "```ignore")]
+    /// # use nrf_hal_common as hal;
+    /// # use hal::uarte::{Baudrate, Parity, Pins, Uarte};
+    /// # fn assert_send<S: Send>(_: &S) {}
+    /// # let p = unsafe { hal::pac::Peripherals::steal() };
+    /// # let port0 = hal::gpio::p0::Parts::new(p.P0);
+    /// # let pins = Pins {
+    /// #     txd: port0.p0_06.into_push_pull_output(hal::gpio::Level::High).degrade(),
+    /// #     rxd: port0.p0_08.into_floating_input().degrade(),
+    /// #     cts: None,
+    /// #     rts: None,
+    /// # };
+    /// static mut TX_BUF: [u8; 16] = [0; 16];
+    /// static mut RX_BUF: [u8; 1] = [0; 1];
+    ///
+    /// let uarte = Uarte::new(p.UARTE0, pins, Parity::EXCLUDED, Baudrate::BAUD115200);
+    /// let (tx, rx) = uarte
+    ///     .split(unsafe { &mut *core::ptr::addr_of_mut!(TX_BUF) }, unsafe {
+    ///         &mut *core::ptr::addr_of_mut!(RX_BUF)
+    ///     })
+    ///     .unwrap();
+    /// # assert_send(&tx);
+    /// # assert_send(&rx);
+    /// ```
     pub fn split(
         self,
         tx_buf: &'static mut [u8],
@@ -445,10 +743,78 @@ fn cancel_read(uarte: &uarte0::RegisterBlock) {
     // The event flag itself is later reset by `finalize_read`.
 }
 
+/// Size of the on-stack buffer used by [`Uarte::write_from_flash`].
+pub const FLASH_CHUNK_SIZE: usize = 16;
+
+/// Number of character times without a received byte after which
+/// [`Uarte::read_until_idle`] considers the line idle.
+pub const IDLE_FRAMES: u32 = 2;
+
+/// Returns the idle line timeout in microseconds for the configured baud rate.
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+fn idle_timeout_us(uarte: &uarte0::RegisterBlock) -> u32 {
+    // The BAUDRATE register holds `baud * 2^32 / 16 MHz`, rounded.
+    let baud = ((u64::from(uarte.baudrate.read().bits()) * 16_000_000) >> 32).max(1);
+    // Start bit, 8 data bits, parity and stop bit, erring on the long side.
+    let frame_bits = 11;
+    let us = (1_000_000 * frame_bits * u64::from(IDLE_FRAMES) + baud - 1) / baud;
+    us as u32
+}
+
+/// Disconnects the idle timeout of [`Uarte::read_until_idle`] once the reception has ended or
+/// was cancelled.
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+struct IdleTimeout<'a, I, P0, P1>
+where
+    I: timer::Instance,
+    P0: Ppi,
+    P1: Ppi,
+{
+    timer: &'a Timer<I>,
+    ppi_restart: &'a mut P0,
+    ppi_stop: &'a mut P1,
+}
+
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+impl<I, P0, P1> Drop for IdleTimeout<'_, I, P0, P1>
+where
+    I: timer::Instance,
+    P0: Ppi,
+    P1: Ppi,
+{
+    fn drop(&mut self) {
+        self.ppi_restart.disable();
+        self.ppi_restart.clear_fork_task_endpoint();
+        self.ppi_stop.disable();
+        self.timer.task_stop().write(|w| unsafe { w.bits(1) });
+        self.timer.task_clear().write(|w| unsafe { w.bits(1) });
+        self.timer.reset_compare_event(0);
+    }
+}
+
+/// Stops a reception that is still running when an async read is cancelled.
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+struct StopRxOnDrop<'a>(&'a uarte0::RegisterBlock);
+
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+impl Drop for StopRxOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.intenclr.write(|w| w.endrx().clear());
+        // The idle timeout may already have stopped the reception.
+        if self.0.events_rxto.read().bits() == 0 {
+            self.0.tasks_stoprx.write(|w| unsafe { w.bits(1) });
+            while self.0.events_rxto.read().bits() == 0 {}
+        }
+        self.0.events_rxto.reset();
+        finalize_read(self.0);
+    }
+}
+
 /// Finalize a UARTE read transaction by clearing the event.
 fn finalize_read(uarte: &uarte0::RegisterBlock) {
-    // Reset the event, otherwise it will always read `1` from now on.
+    // Reset the events, otherwise they will always read `1` from now on.
     uarte.events_endrx.write(|w| w);
+    uarte.events_rxstarted.reset();
 
     // Conservative compiler fence to prevent optimizations that do not
     // take in to account actions by DMA. The fence has been placed here,
@@ -463,13 +829,7 @@ where
     fn write_str(&mut self, s: &str) -> fmt::Result {
         // Copy all data into an on-stack buffer so we never try to EasyDMA from
         // flash.
-        let buf = &mut [0; 16][..];
-        for block in s.as_bytes().chunks(16) {
-            buf[..block.len()].copy_from_slice(block);
-            self.write(&buf[..block.len()]).map_err(|_| fmt::Error)?;
-        }
-
-        Ok(())
+        self.write_str_from_flash(s).map_err(|_| fmt::Error)
     }
 }
 
@@ -480,6 +840,12 @@ pub struct Pins {
     pub rts: Option<Pin<Output<PushPull>>>,
 }
 
+/// Flow control pins released by [`Uarte::set_flow_control`].
+pub struct FlowControlPins {
+    pub rts: Option<Pin<Output<PushPull>>>,
+    pub cts: Option<Pin<Input<Floating>>>,
+}
+
 #[derive(Debug)]
 pub enum Error {
     TxBufferTooSmall,
@@ -490,6 +856,8 @@ pub enum Error {
     Receive,
     Timeout(usize),
     BufferNotInRAM,
+    /// A transfer is in progress.
+    Busy,
 }
 
 impl embedded_io::Error for Error {
@@ -502,6 +870,7 @@ impl embedded_io::Error for Error {
             | Self::BufferNotInRAM => ErrorKind::InvalidInput,
             Self::Transmit | Self::Receive => ErrorKind::Interrupted,
             Self::Timeout(_) => ErrorKind::TimedOut,
+            Self::Busy => ErrorKind::Other,
         }
     }
 }
@@ -511,10 +880,26 @@ pub trait Instance: Deref<Target = uarte0::RegisterBlock> + sealed::Sealed {
 }
 
 mod sealed {
-    pub trait Sealed {}
+    use crate::waker::AtomicWaker;
+
+    pub trait Sealed {
+        /// The waker of the task waiting in `Uarte::read_until_idle`.
+        fn waker() -> &'static AtomicWaker;
+    }
+}
+
+macro_rules! impl_sealed {
+    ($uarte:ident) => {
+        impl sealed::Sealed for $uarte {
+            fn waker() -> &'static crate::waker::AtomicWaker {
+                static WAKER: crate::waker::AtomicWaker = crate::waker::AtomicWaker::new();
+                &WAKER
+            }
+        }
+    };
 }
 
-impl sealed::Sealed for UARTE0 {}
+impl_sealed!(UARTE0);
 impl Instance for UARTE0 {
     fn ptr() -> *const uarte0::RegisterBlock {
         UARTE0::ptr()
@@ -529,7 +914,7 @@ impl Instance for UARTE0 {
 ))]
 mod _uarte1 {
     use super::*;
-    impl sealed::Sealed for UARTE1 {}
+    impl_sealed!(UARTE1);
     impl Instance for UARTE1 {
         fn ptr() -> *const uarte0::RegisterBlock {
             UARTE1::ptr()
@@ -540,7 +925,7 @@ mod _uarte1 {
 #[cfg(feature = "9160")]
 mod _uarte2 {
     use super::*;
-    impl sealed::Sealed for UARTE2 {}
+    impl_sealed!(UARTE2);
     impl Instance for UARTE2 {
         fn ptr() -> *const uarte0::RegisterBlock {
             UARTE2::ptr()
@@ -551,7 +936,7 @@ mod _uarte2 {
 #[cfg(feature = "9160")]
 mod _uarte3 {
     use super::*;
-    impl sealed::Sealed for UARTE3 {}
+    impl_sealed!(UARTE3);
     impl Instance for UARTE3 {
         fn ptr() -> *const uarte0::RegisterBlock {
             UARTE3::ptr()
@@ -563,7 +948,7 @@ mod _uarte3 {
 mod _uarte0_s {
     use super::*;
     use crate::pac::UARTE0_S;
-    impl sealed::Sealed for UARTE0_S {}
+    impl_sealed!(UARTE0_S);
     impl Instance for UARTE0_S {
         fn ptr() -> *const uarte0::RegisterBlock {
             UARTE0_S::ptr()
@@ -572,6 +957,9 @@ mod _uarte0_s {
 }
 
 /// Interface for the TX part of a UART instance that can be used independently of the RX part.
+///
+/// Created by [`Uarte::split`]. It only uses the TX tasks, events and DMA registers of the
+/// instance, so it can live in a different task than the [`UarteRx`] half.
 pub struct UarteTx<T>
 where
     T: Instance,
@@ -582,6 +970,9 @@ where
 }
 
 /// Interface for the RX part of a UART instance that can be used independently of the TX part.
+///
+/// Created by [`Uarte::split`]. It only uses the RX tasks, events and DMA registers of the
+/// instance, so it can live in a different task than the [`UarteTx`] half.
 pub struct UarteRx<T>
 where
     T: Instance,
@@ -676,6 +1067,153 @@ where
     }
 }
 
+/// Background receiver feeding a ring buffer from double-buffered EasyDMA.
+///
+/// The UARTE receives into one of two DMA buffers of `N` bytes while the other one is drained
+/// into the ring buffer. The `ENDRX -> STARTRX` shortcut restarts the reception into the next
+/// buffer in hardware, so no byte is lost as long as [`UarteRingRx::on_interrupt`] runs within
+/// `N` character times of the `ENDRX` event.
+///
+/// Received bytes only become visible once a DMA buffer is full, so `N` trades interrupt load
+/// against latency. Bytes that do not fit into the ring buffer are dropped and counted in
+/// [`UarteRingRx::dropped`].
+pub struct UarteRingRx<T, const N: usize>
+where
+    T: Instance,
+{
+    rx: UarteRx<T>,
+    dma: &'static mut [[u8; N]; 2],
+    ring: &'static mut [u8],
+    read: usize,
+    len: usize,
+    filling: usize,
+    dropped: usize,
+}
+
+impl<T, const N: usize> UarteRingRx<T, N>
+where
+    T: Instance,
+{
+    /// Takes over the RX half and starts receiving in the background.
+    ///
+    /// Note that the UARTE interrupt also has to be unmasked in the NVIC, or
+    /// [`UarteRingRx::on_interrupt`] won't get called.
+    pub fn new(
+        rx: UarteRx<T>,
+        dma: &'static mut [[u8; N]; 2],
+        ring: &'static mut [u8],
+    ) -> Result<Self, Error> {
+        if N == 0 || ring.is_empty() {
+            return Err(Error::RxBufferTooSmall);
+        }
+
+        if N > EASY_DMA_SIZE {
+            return Err(Error::RxBufferTooLong);
+        }
+
+        let uarte = unsafe { &*T::ptr() };
+
+        // Stop a reception left over by the RX half.
+        if uarte.events_rxstarted.read().bits() != 0 {
+            cancel_read(uarte);
+            uarte.events_rxstarted.reset();
+            finalize_read(uarte);
+        }
+
+        uarte.events_endrx.reset();
+        uarte.shorts.modify(|_, w| w.endrx_startrx().enabled());
+        uarte.intenset.write(|w| w.endrx().set().rxstarted().set());
+
+        start_read(uarte, &mut dma[0])?;
+
+        Ok(UarteRingRx {
+            rx,
+            dma,
+            ring,
+            read: 0,
+            len: 0,
+            filling: 0,
+            dropped: 0,
+        })
+    }
+
+    /// Moves received bytes into the ring buffer. Call this from the UARTE interrupt handler.
+    pub fn on_interrupt(&mut self) {
+        let uarte = unsafe { &*T::ptr() };
+
+        if uarte.events_endrx.read().bits() != 0 {
+            uarte.events_endrx.reset();
+            compiler_fence(SeqCst);
+
+            let amount = uarte.rxd.amount.read().bits() as usize;
+            self.push(self.filling, amount);
+            self.filling ^= 1;
+        }
+
+        if uarte.events_rxstarted.read().bits() != 0 {
+            uarte.events_rxstarted.reset();
+
+            // The current buffer is latched, point the DMA to the next one.
+            let next = self.dma[self.filling ^ 1].as_ptr();
+            uarte
+                .rxd
+                .ptr
+                .write(|w| unsafe { w.ptr().bits(next as u32) });
+        }
+    }
+
+    /// Returns the number of bytes that can be dequeued.
+    pub fn bytes_available(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of bytes dropped because the ring buffer was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Moves up to `buf.len()` received bytes into `buf`, returning how many were copied.
+    pub fn dequeue(&mut self, buf: &mut [u8]) -> usize {
+        let count = min(buf.len(), self.len);
+        for byte in buf[..count].iter_mut() {
+            *byte = self.ring[self.read];
+            self.read = (self.read + 1) % self.ring.len();
+        }
+        self.len -= count;
+        count
+    }
+
+    /// Stops the background reception and returns the RX half and the buffers.
+    ///
+    /// Bytes received into the partially filled DMA buffer are moved into the ring buffer first.
+    pub fn free(mut self) -> (UarteRx<T>, &'static mut [[u8; N]; 2], &'static mut [u8]) {
+        let uarte = unsafe { &*T::ptr() };
+
+        uarte
+            .intenclr
+            .write(|w| w.endrx().clear().rxstarted().clear());
+        uarte.shorts.modify(|_, w| w.endrx_startrx().disabled());
+
+        cancel_read(uarte);
+        let amount = uarte.rxd.amount.read().bits() as usize;
+        uarte.events_rxstarted.reset();
+        finalize_read(uarte);
+        self.push(self.filling, amount);
+
+        (self.rx, self.dma, self.ring)
+    }
+
+    fn push(&mut self, buffer: usize, amount: usize) {
+        let capacity = self.ring.len();
+        let count = min(amount, capacity - self.len);
+        for &byte in self.dma[buffer][..count].iter() {
+            self.ring[(self.read + self.len) % capacity] = byte;
+            self.len += 1;
+        }
+        self.dropped += amount - count;
+    }
+}
+
 impl<T> Drop for UarteTx<T>
 where
     T: Instance,
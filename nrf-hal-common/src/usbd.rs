@@ -1,7 +1,30 @@
+//! HAL interface to the USBD peripheral.
+//!
+//! The [`usb_device::bus::UsbBus`](https://docs.rs/usb-device) implementation is provided by the
+//! `nrf-usbd` crate and re-exported as [`Usbd`]. It handles the EasyDMA endpoint buffers, the
+//! SETUP stage of control transfers and the errata workarounds around enabling the peripheral.
+//! Wrap a [`UsbPeripheral`] in it and hand it to a `UsbBusAllocator`.
+//!
+//! The USBD needs the external high frequency oscillator, and it can only be enabled once the
+//! USB regulator is powered from VBUS. Enabling it earlier blocks until the host supplies power,
+//! so wait for [`power_ready`] first, or track the cable with a [`UsbRegulator`].
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::{pac::Peripherals, Clocks};
+//! # use nrf_hal_common::usbd::{UsbPeripheral, UsbRegulator, Usbd};
+//! let p = Peripherals::take().unwrap();
+//! let clocks = Clocks::new(p.CLOCK).enable_ext_hfosc();
+//!
+//! let mut regulator = UsbRegulator::new();
+//! regulator.wait_power_ready();
+//! let usbd = Usbd::new(UsbPeripheral::new(p.USBD, &clocks));
+//! ```
+
 use core::marker::PhantomData;
 
 use crate::clocks::ExternalOscillator;
-use crate::pac::USBD;
+use crate::pac::{POWER, USBD};
 use crate::Clocks;
 
 pub use nrf_usbd::Usbd;
@@ -23,3 +46,112 @@ impl<'a> UsbPeripheral<'a> {
 unsafe impl<'a> nrf_usbd::UsbPeripheral for UsbPeripheral<'a> {
     const REGISTERS: *const () = USBD::ptr() as *const _;
 }
+
+/// Returns `true` if VBUS is present, i.e. after the USBDETECTED event.
+pub fn vbus_present() -> bool {
+    let power = unsafe { &*POWER::ptr() };
+    power.usbregstatus.read().vbusdetect().is_vbus_present()
+}
+
+/// Returns `true` if the USB regulator is ready, i.e. after the USBPWRRDY event, so that the
+/// USBD can be enabled.
+pub fn power_ready() -> bool {
+    let power = unsafe { &*POWER::ptr() };
+    power.usbregstatus.read().outputrdy().is_ready()
+}
+
+/// State of the USB power supply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UsbState {
+    /// No VBUS, the cable is unplugged.
+    Disconnected,
+    /// VBUS is present and the USB regulator is starting up.
+    Detected,
+    /// The USB regulator is ready and the USBD can be enabled.
+    PowerReady,
+}
+
+/// Tracks the USBDETECTED, USBPWRRDY and USBREMOVED events of the POWER peripheral.
+///
+/// The USB regulator starts by itself once VBUS is detected. The USBD may only be enabled after
+/// the regulator is ready, and has to be disabled again when VBUS is removed. Call
+/// [`UsbRegulator::update`] from the `POWER_CLOCK` interrupt handler (see
+/// [`UsbRegulator::enable_interrupts`]) or poll it to follow the cable being plugged in and out.
+///
+/// Only the USB related events and interrupts of the POWER peripheral are touched.
+pub struct UsbRegulator {
+    state: UsbState,
+}
+
+impl UsbRegulator {
+    /// Clears pending USB power events and reads the current state.
+    pub fn new() -> Self {
+        let power = Self::power();
+        power.events_usbdetected.reset();
+        power.events_usbpwrrdy.reset();
+        power.events_usbremoved.reset();
+        Self {
+            state: Self::read_state(),
+        }
+    }
+
+    /// Returns the state as of the last [`UsbRegulator::update`].
+    pub fn state(&self) -> UsbState {
+        self.state
+    }
+
+    /// Enables the `POWER_CLOCK` interrupt on the USB power events.
+    pub fn enable_interrupts(&self) {
+        Self::power().intenset.write(|w| {
+            w.usbdetected().set();
+            w.usbpwrrdy().set();
+            w.usbremoved().set()
+        });
+    }
+
+    /// Disables the `POWER_CLOCK` interrupt on the USB power events.
+    pub fn disable_interrupts(&self) {
+        Self::power().intenclr.write(|w| {
+            w.usbdetected().clear();
+            w.usbpwrrdy().clear();
+            w.usbremoved().clear()
+        });
+    }
+
+    /// Handles pending USB power events and returns the new state.
+    ///
+    /// The state is read back from `USBREGSTATUS`, so events that are missed or handled out of
+    /// order can't leave it inconsistent.
+    pub fn update(&mut self) -> UsbState {
+        let power = Self::power();
+        power.events_usbdetected.reset();
+        power.events_usbpwrrdy.reset();
+        power.events_usbremoved.reset();
+        self.state = Self::read_state();
+        self.state
+    }
+
+    /// Blocks until the USB regulator is ready.
+    pub fn wait_power_ready(&mut self) {
+        while self.update() != UsbState::PowerReady {}
+    }
+
+    fn read_state() -> UsbState {
+        match (vbus_present(), power_ready()) {
+            (false, _) => UsbState::Disconnected,
+            (true, false) => UsbState::Detected,
+            (true, true) => UsbState::PowerReady,
+        }
+    }
+
+    fn power<'a>() -> &'a crate::pac::power::RegisterBlock {
+        unsafe { &*POWER::ptr() }
+    }
+}
+
+impl Default for UsbRegulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,42 @@
+//! Waker storage shared between async drivers and their interrupt handlers.
+
+use core::cell::RefCell;
+use core::task::Waker;
+
+use cortex_m::interrupt::{self, Mutex};
+
+/// Holds the waker of the task waiting for a peripheral event.
+///
+/// The future registers its waker before checking the event, and the interrupt handler wakes it
+/// once the event has fired, so no wakeup can be lost in between.
+pub struct AtomicWaker(Mutex<RefCell<Option<Waker>>>);
+
+impl AtomicWaker {
+    pub const fn new() -> Self {
+        AtomicWaker(Mutex::new(RefCell::new(None)))
+    }
+
+    /// Stores `waker` to be woken by the next call to [`AtomicWaker::wake`].
+    pub fn register(&self, waker: &Waker) {
+        interrupt::free(|cs| {
+            let mut slot = self.0.borrow(cs).borrow_mut();
+            match &*slot {
+                Some(stored) if stored.will_wake(waker) => {}
+                _ => *slot = Some(waker.clone()),
+            }
+        });
+    }
+
+    /// Wakes the registered task, if any.
+    pub fn wake(&self) {
+        if let Some(waker) = interrupt::free(|cs| self.0.borrow(cs).borrow_mut().take()) {
+            waker.wake();
+        }
+    }
+}
+
+impl Default for AtomicWaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
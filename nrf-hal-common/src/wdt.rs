@@ -1,9 +1,29 @@
 //! HAL interface to the WDT peripheral.
 //!
 //! This HAL implements a basic watchdog timer with 1..=8 handles.
-//! Once the watchdog has been started, it cannot be stopped.
+//! Once the watchdog has been started, it cannot be stopped, and its configuration (reload
+//! value, enabled handles, sleep and halt behavior) can no longer be changed until the next
+//! reset.
+//!
+//! The number of handles is chosen with one of the types in [`count`]. The device is only
+//! reloaded once every active handle has been pet, so each handle can be given to a different
+//! task to have them collectively prove liveness:
+//!
+#![cfg_attr(feature = "52840", doc = "```no_run")]
+#![cfg_attr(not(feature = "52840"), doc = "```ignore")]
+//! # use nrf_hal_common::{pac, wdt::{count, Watchdog}};
+//! let p = pac::Peripherals::take().unwrap();
+//! let watchdog = Watchdog::try_new(p.WDT).ok().unwrap();
+//! let parts = watchdog.activate::<count::Array<3>>();
+//! let [mut radio, mut sensors, mut idle] = parts.handles;
+//!
+//! radio.pet();
+//! sensors.pet();
+//! idle.pet();
+//! ```
 
 use cfg_if::cfg_if;
+use embedded_hal_async::delay::DelayNs;
 
 cfg_if! {
     if #[cfg(any(feature = "9160", feature = "5340-net"))] {
@@ -29,6 +49,25 @@ pub struct Watchdog<T: sealed::WdMode> {
     _state: T,
 }
 
+/// Behavior of the watchdog while the CPU is not running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    /// Keep counting while the CPU is sleeping.
+    pub run_during_sleep: bool,
+    /// Keep counting while the CPU is halted by the debugger.
+    pub run_during_halt: bool,
+}
+
+impl Default for WatchdogConfig {
+    /// Runs during sleep, but pauses while halted by the debugger, matching the reset values.
+    fn default() -> Self {
+        Self {
+            run_during_sleep: true,
+            run_during_halt: false,
+        }
+    }
+}
+
 /// A structure containing the active watchdog and all requested Watchdog handles.
 pub struct Parts<T> {
     pub watchdog: Watchdog<Active>,
@@ -63,6 +102,23 @@ where
         ((rd >> idx) & 0x1) == 0
     }
 
+    /// Pet this handle every `interval_ms` milliseconds, forever.
+    ///
+    /// Run the returned future as its own task on the async executor. The watchdog is only fed
+    /// while the task keeps being scheduled, so a hung executor or a task starved by a busy
+    /// loop elsewhere stops petting and lets the watchdog reset the chip.
+    ///
+    /// The timeout is `(CRV + 1) / 32_768` seconds, see [`Watchdog::lfosc_ticks`]. The interval
+    /// must leave a margin for the delay's own inaccuracy and for the scheduling latency of the
+    /// task; half the timeout is a reasonable starting point. An interval close to the timeout
+    /// risks spurious resets under load, while a much shorter one only costs wakeups.
+    pub async fn feeder<D: DelayNs>(&mut self, mut delay: D, interval_ms: u32) {
+        loop {
+            self.pet();
+            delay.delay_ms(interval_ms).await;
+        }
+    }
+
     /// Convert the handle into a generic handle.
     ///
     /// This is useful if you need to place handles into an array.
@@ -96,6 +152,17 @@ impl Watchdog<Inactive> {
         }
     }
 
+    /// Was the last reset caused by the watchdog?
+    ///
+    /// This reads the reset reason without clearing it, use
+    /// [`ResetReasons::read_and_clear`](crate::reset::ResetReasons::read_and_clear) to
+    /// decode and clear all reset reasons.
+    #[cfg(not(any(feature = "5340-app", feature = "5340-net")))]
+    #[inline]
+    pub fn caused_last_reset() -> bool {
+        crate::reset::ResetReasons::read().contains(crate::reset::ResetReason::Watchdog)
+    }
+
     /// Release the peripheral.
     ///
     /// Note: The peripheral cannot be released after activation.
@@ -154,6 +221,17 @@ impl Watchdog<Inactive> {
             .write(|w| unsafe { w.bits(ticks.max(0x0000_000F)) });
     }
 
+    /// Set whether the watchdog keeps counting while the CPU sleeps or is halted for debug.
+    #[inline]
+    pub fn set_config(&mut self, config: WatchdogConfig) {
+        self.wdt.config.write(|w| {
+            w.sleep()
+                .bit(config.run_during_sleep)
+                .halt()
+                .bit(config.run_during_halt)
+        });
+    }
+
     /// Should the watchdog continue to count during sleep modes?
     ///
     /// This value defaults to ENABLED on reset.
@@ -210,6 +288,12 @@ impl<T> Watchdog<T>
 where
     T: sealed::WdMode,
 {
+    /// The number of 32.768kHz ticks in each watchdog period.
+    #[inline(always)]
+    pub fn lfosc_ticks(&self) -> u32 {
+        self.wdt.crv.read().bits()
+    }
+
     /// Is the watchdog active?
     #[inline(always)]
     pub fn is_active(&self) -> bool {
@@ -315,7 +399,9 @@ pub mod handles {
 pub mod count {
     //! Type states representing the number of requested handles.
 
-    use super::{sealed::Handles, Hdl0, Hdl1, Hdl2, Hdl3, Hdl4, Hdl5, Hdl6, Hdl7, WatchdogHandle};
+    use super::{
+        sealed::Handles, Hdl0, Hdl1, Hdl2, Hdl3, Hdl4, Hdl5, Hdl6, Hdl7, HdlN, WatchdogHandle,
+    };
     /// A type state representing the request for One handles.
     pub struct One;
     /// A type state representing the request for Two handles.
@@ -332,6 +418,22 @@ pub mod count {
     pub struct Seven;
     /// A type state representing the request for Eight handles.
     pub struct Eight;
+    /// A type state representing the request for `N` handles, returned as an array of
+    /// [`HdlN`](super::HdlN) handles.
+    ///
+    /// `N` must be in the range `1..=8`, otherwise activating the watchdog fails to compile.
+    pub struct Array<const N: usize>;
+
+    impl<const N: usize> Handles for Array<N> {
+        type Handles = [WatchdogHandle<HdlN>; N];
+        const ENABLE: u32 = {
+            assert!(N >= 1 && N <= 8, "the watchdog has 1 to 8 handles");
+            (1 << N) - 1
+        };
+        fn create_handle() -> Self::Handles {
+            core::array::from_fn(|idx| WatchdogHandle(HdlN { idx: idx as u8 }))
+        }
+    }
 
     impl Handles for One {
         type Handles = (WatchdogHandle<Hdl0>,);
@@ -73,6 +73,17 @@ mod tests {
         assert!(state.nvmc.write(LAST_PAGE, &buf[..1]).is_err());
     }
 
+    #[test]
+    fn write_twice_stores_and(state: &mut State) {
+        assert!(state.nvmc.erase(LAST_PAGE, CONFIG_SIZE).is_ok());
+        assert!(state.nvmc.write(LAST_PAGE, &[0x0f, 0xff, 0, 0]).is_ok());
+        // The nRF52840 implements `MultiwriteNorFlash`, so a second write ANDs the data.
+        assert!(state.nvmc.write(LAST_PAGE, &[0xf3, 0x3c, 0xff, 0]).is_ok());
+        let mut read_buf = [0u8; 4];
+        assert!(state.nvmc.read(LAST_PAGE, &mut read_buf).is_ok());
+        assert_eq!(read_buf, [0x03, 0x3c, 0, 0]);
+    }
+
     #[test]
     fn read_write_and_then_read(state: &mut State) {
         assert!(state.nvmc.erase(LAST_PAGE, CONFIG_SIZE).is_ok());
@@ -100,4 +111,18 @@ mod tests {
             .is_ok());
         assert_eq!(partial_read_buf, write_buf[2..][..4]);
     }
+
+    #[test]
+    fn write_words_and_erase_page(state: &mut State) {
+        assert!(state.nvmc.erase_page(LAST_PAGE).is_ok());
+        assert!(state.nvmc.write_words(LAST_PAGE, &[0x0403_0201]).is_ok());
+        let mut read_buf = [0u8; 4];
+        assert!(state.nvmc.read(LAST_PAGE, &mut read_buf).is_ok());
+        assert_eq!(read_buf, [1, 2, 3, 4]);
+        assert!(state.nvmc.erase_page(LAST_PAGE).is_ok());
+        assert!(state.nvmc.read(LAST_PAGE, &mut read_buf).is_ok());
+        assert_eq!(read_buf, [0xff; 4]);
+        assert!(state.nvmc.erase_page(LAST_PAGE + 4).is_err());
+        assert!(state.nvmc.write_words(LAST_PAGE + 2, &[0]).is_err());
+    }
 }
@@ -0,0 +1,60 @@
+#![no_std]
+#![no_main]
+
+use defmt_rtt as _;
+use nrf52840_hal as _;
+use panic_probe as _;
+
+use nrf52840_hal::{pac::PWM0, pwm::Pwm};
+
+struct State {
+    pwm: Pwm<PWM0>,
+}
+
+/// Reads the duty word of `channel` from the sequence the PWM was last pointed at.
+fn duty_word(channel: usize) -> u16 {
+    let pwm = unsafe { &*PWM0::ptr() };
+    let ptr = pwm.seq0.ptr.read().bits() as *const u16;
+    unsafe { ptr.add(channel).read_volatile() }
+}
+
+#[defmt_test::tests]
+mod tests {
+    use defmt::{assert, assert_eq, unwrap};
+    use nrf52840_hal::{
+        pac,
+        pwm::{Channel, Polarity, Pwm},
+    };
+
+    use super::{duty_word, State};
+
+    #[init]
+    fn init() -> State {
+        let p = unwrap!(pac::Peripherals::take());
+        let pwm = Pwm::new(p.PWM0);
+
+        State { pwm }
+    }
+
+    #[test]
+    fn duty_on_is_falling_edge(state: &mut State) {
+        state.pwm.set_duty_on(Channel::C0, 100);
+
+        // Bit 15 clear: high for the duty, then low.
+        assert_eq!(duty_word(0), 100);
+        assert!(state.pwm.polarity(Channel::C0) == Polarity::FallingEdge);
+    }
+
+    #[test]
+    fn rising_edge_sets_bit_15(state: &mut State) {
+        state.pwm.set_duty_on(Channel::C0, 100);
+        state.pwm.set_polarity(Channel::C0, Polarity::RisingEdge);
+
+        assert_eq!(duty_word(0), 0x8000 | 100);
+        assert!(state.pwm.polarity(Channel::C0) == Polarity::RisingEdge);
+
+        state.pwm.set_polarity(Channel::C0, Polarity::FallingEdge);
+
+        assert_eq!(duty_word(0), 100);
+    }
+}
@@ -0,0 +1,77 @@
+#![no_std]
+#![no_main]
+
+use defmt_rtt as _;
+use nrf52840_hal as _;
+use panic_probe as _;
+
+use nrf52840_hal::{
+    clocks::{Clocks, Internal, LfOscStarted},
+    pac::RTC0,
+    rtc::Rtc,
+};
+
+struct State {
+    rtc: Rtc<RTC0>,
+    _clocks: Clocks<Internal, Internal, LfOscStarted>,
+}
+
+#[defmt_test::tests]
+mod tests {
+    use defmt::{assert, unwrap};
+    use nrf52840_hal::{
+        clocks::Clocks,
+        pac,
+        rtc::{Rtc, RtcInterrupt},
+    };
+
+    use super::State;
+
+    const WRAP: u64 = 1 << 24;
+
+    #[init]
+    fn init() -> State {
+        let p = unwrap!(pac::Peripherals::take());
+        let _clocks = Clocks::new(p.CLOCK).start_lfclk();
+
+        let mut rtc = Rtc::new(p.RTC0, 0).unwrap();
+        // No NVIC: the tests call `handle_overflow` by hand instead of from the interrupt.
+        rtc.enable_extended_counter(None);
+        rtc.enable_counter();
+
+        State { rtc, _clocks }
+    }
+
+    #[test]
+    fn starts_at_counter(state: &mut State) {
+        let now = state.rtc.now64();
+        assert!(now < WRAP);
+        assert!(now <= state.rtc.get_counter() as u64);
+    }
+
+    #[test]
+    fn extends_across_wrap(state: &mut State) {
+        state.rtc.trigger_overflow();
+        while !state.rtc.is_event_triggered(RtcInterrupt::Overflow) {}
+        state.rtc.handle_overflow();
+
+        let now = state.rtc.now64();
+        assert!(now >= WRAP);
+        assert!(now < WRAP + 0x1000);
+    }
+
+    #[test]
+    fn monotonic_across_wraps(state: &mut State) {
+        let mut last = state.rtc.now64();
+        for _ in 0..3 {
+            state.rtc.trigger_overflow();
+            while !state.rtc.is_event_triggered(RtcInterrupt::Overflow) {}
+            state.rtc.handle_overflow();
+
+            let now = state.rtc.now64();
+            assert!(now > last);
+            last = now;
+        }
+        assert!(last >= 4 * WRAP);
+    }
+}